@@ -0,0 +1,207 @@
+//! Calendar dates as Julian day numbers
+//!
+//! Dates are in the proleptic Gregorian calendar, written `YYYY-MM-DD` with a
+//! four-digit year. Each one maps to its Julian day number, a plain count of
+//! days, so differences and offsets are integer arithmetic, and the day of
+//! the week follows from the distance to a date whose weekday is known.
+
+use std::fmt;
+
+/// Julian day number of 1970-01-01, a Thursday
+const EPOCH_JULIAN_DAY: i64 = 2_440_588;
+
+/// Weekday of [`EPOCH_JULIAN_DAY`], counting from Sunday = 0
+const EPOCH_WEEKDAY: i64 = 4;
+
+const WEEKDAY_NAMES: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+
+const MONTH_NAMES: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November",
+    "December",
+];
+
+/// A valid calendar date
+#[derive(Clone, Copy)]
+pub struct Date {
+    year: i64,
+    month: i64,
+    day: i64,
+}
+
+impl Date {
+    /// Earliest date that can be written with a four-digit year
+    const MIN: Date = Date { year: 0, month: 1, day: 1 };
+
+    /// Latest date that can be written with a four-digit year
+    const MAX: Date = Date { year: 9999, month: 12, day: 31 };
+
+    /// Parse `YYYY-MM-DD`, checking that the day exists in that month
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let bytes = text.as_bytes();
+        let well_formed = bytes.len() == 10
+            && bytes[4] == b'-'
+            && bytes[7] == b'-'
+            && bytes.iter().enumerate().all(|(i, b)| i == 4 || i == 7 || b.is_ascii_digit());
+        if !well_formed {
+            return Err(format!("expected YYYY-MM-DD, got '{}'", text));
+        }
+
+        let number = |range: std::ops::Range<usize>| text[range].parse::<i64>().unwrap_or_default();
+        let date = Date {
+            year: number(0..4),
+            month: number(5..7),
+            day: number(8..10),
+        };
+
+        if !(1..=12).contains(&date.month) {
+            return Err(format!("month {} of '{}' is not between 1 and 12", date.month, text));
+        }
+        let days = days_in_month(date.year, date.month);
+        if !(1..=days).contains(&date.day) {
+            return Err(format!(
+                "day {} of '{}' does not exist, {:04}-{:02} has {} days",
+                date.day, text, date.year, date.month, days
+            ));
+        }
+        Ok(date)
+    }
+
+    /// Days since 1 January 4713 BC in the Julian calendar (Fliegel and
+    /// Van Flandern's formula). Years are never negative here, so every
+    /// division truncates the same way as a floor.
+    pub fn julian_day(self) -> i64 {
+        let a = (14 - self.month) / 12;
+        let y = self.year + 4800 - a;
+        let m = self.month + 12 * a - 3;
+        self.day + (153 * m + 2) / 5 + 365 * y + y / 4 - y / 100 + y / 400 - 32045
+    }
+
+    /// The date with Julian day number `jdn` (Richards' inverse of
+    /// [`Date::julian_day`]), or none outside years 0000 to 9999
+    pub fn from_julian_day(jdn: i64) -> Option<Self> {
+        if !(Self::MIN.julian_day()..=Self::MAX.julian_day()).contains(&jdn) {
+            return None;
+        }
+
+        let a = jdn + 32044;
+        let b = (4 * a + 3) / 146097;
+        let c = a - 146097 * b / 4;
+        let d = (4 * c + 3) / 1461;
+        let e = c - 1461 * d / 4;
+        let m = (5 * e + 2) / 153;
+        Some(Date {
+            year: 100 * b + d - 4800 + m / 10,
+            month: m + 3 - 12 * (m / 10),
+            day: e - (153 * m + 2) / 5 + 1,
+        })
+    }
+
+    /// Index into [`WEEKDAY_NAMES`], Sunday = 0
+    fn weekday(self) -> usize {
+        (self.julian_day() - EPOCH_JULIAN_DAY + EPOCH_WEEKDAY).rem_euclid(7) as usize
+    }
+
+    /// Expand `%Y`, `%m`, `%d`, `%A` (weekday name), `%B` (month name) and
+    /// `%%` in `pattern`; anything else after `%` is an error
+    pub fn format(self, pattern: &str) -> Result<String, String> {
+        let mut out = String::with_capacity(pattern.len());
+        let mut chars = pattern.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => out.push_str(&format!("{:04}", self.year)),
+                Some('m') => out.push_str(&format!("{:02}", self.month)),
+                Some('d') => out.push_str(&format!("{:02}", self.day)),
+                Some('A') => out.push_str(WEEKDAY_NAMES[self.weekday()]),
+                Some('B') => out.push_str(MONTH_NAMES[self.month as usize - 1]),
+                Some('%') => out.push('%'),
+                Some(other) => {
+                    return Err(format!(
+                        "unsupported directive '%{}', expected one of %Y, %m, %d, %A, %B, %%",
+                        other
+                    ))
+                }
+                None => return Err("pattern ends with a lone '%'; write %% for a literal percent sign".to_string()),
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jdn(text: &str) -> i64 {
+        Date::parse(text).expect("valid date").julian_day()
+    }
+
+    #[test]
+    fn julian_day_matches_known_dates() {
+        assert_eq!(jdn("1970-01-01"), EPOCH_JULIAN_DAY);
+        assert_eq!(jdn("2000-01-01"), 2_451_545);
+        assert_eq!(jdn("0000-01-01"), 1_721_060);
+    }
+
+    #[test]
+    fn every_julian_day_round_trips() {
+        for day in Date::MIN.julian_day()..=Date::MAX.julian_day() {
+            let date = Date::from_julian_day(day).expect("in range");
+            assert_eq!(date.julian_day(), day);
+            assert_eq!(Date::parse(&date.to_string()).expect("valid date").julian_day(), day);
+        }
+        assert!(Date::from_julian_day(Date::MIN.julian_day() - 1).is_none());
+        assert!(Date::from_julian_day(Date::MAX.julian_day() + 1).is_none());
+    }
+
+    #[test]
+    fn leap_days_follow_the_gregorian_rules() {
+        for leap in ["2024-02-29", "2000-02-29", "0000-02-29"] {
+            assert!(Date::parse(leap).is_ok(), "{} should exist", leap);
+        }
+        for missing in ["2023-02-29", "1900-02-29", "2100-02-29"] {
+            assert!(Date::parse(missing).is_err(), "{} should not exist", missing);
+        }
+        assert_eq!(jdn("2024-03-01") - jdn("2024-02-28"), 2);
+        assert_eq!(jdn("1900-03-01") - jdn("1900-02-28"), 1);
+    }
+
+    #[test]
+    fn differences_are_negative_when_the_end_comes_first() {
+        assert_eq!(jdn("2024-01-01") - jdn("2024-12-31"), -365);
+        assert_eq!(jdn("1999-12-31") - jdn("2000-01-01"), -1);
+    }
+
+    #[test]
+    fn weekday_and_month_names() {
+        let date = Date::parse("2024-02-29").expect("valid date");
+        assert_eq!(date.format("%A, %d %B %Y").as_deref(), Ok("Thursday, 29 February 2024"));
+        assert_eq!(
+            Date::parse("0000-01-01").expect("valid date").format("%A").as_deref(),
+            Ok("Saturday")
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! datetime-tools Tools Capability Provider
+//!
+//! A tools capability that provides calendar date arithmetic and formatting.
+//! Dates are `YYYY-MM-DD` in the proleptic Gregorian calendar, without times
+//! or time zones; see [`date`].
+//!
+//! Which tools are exposed is configured by [`tool_filter`], and `list_tools`
+//! returns them in pages (see [`pagination`]).
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "datetime-tools",
+        generate_all,
+    });
+}
+
+mod date;
+
+use bindings::exports::wasmcp::protocol::tools::Guest;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasi::io::streams::OutputStream;
+use mcp_utils::annotations::ToolHints;
+use mcp_utils::args::parse_arg;
+use mcp_utils::{pagination, tool_filter};
+
+use date::Date;
+
+mcp_utils::result_builders!();
+use result_builder::{error_result, success_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
+
+struct DatetimeTools;
+
+impl Guest for DatetimeTools {
+    fn list_tools(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: ListToolsRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Result<ListToolsResult, ErrorCode> {
+        let exposed: Vec<Tool> = vec![
+            Tool {
+                name: "date_diff".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "start": {"type": "string", "description": "Start date as YYYY-MM-DD"},
+                        "end": {"type": "string", "description": "End date as YYYY-MM-DD"}
+                    },
+                    "required": ["start", "end"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Number of days from start to end, as an integer; negative when end is before start. Dates are YYYY-MM-DD in the Gregorian calendar".to_string()),
+                    output_schema: None,
+                    title: Some("Date Difference".to_string()),
+                }),
+            },
+            Tool {
+                name: "add_days".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "date": {"type": "string", "description": "Date as YYYY-MM-DD"},
+                        "days": {"type": "integer", "description": "Days to add; negative to go back"}
+                    },
+                    "required": ["date", "days"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Add a signed number of days to a YYYY-MM-DD date, returning the new date as YYYY-MM-DD. Results must stay within years 0000 to 9999".to_string()),
+                    output_schema: None,
+                    title: Some("Add Days".to_string()),
+                }),
+            },
+            Tool {
+                name: "format_date".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "date": {"type": "string", "description": "Date as YYYY-MM-DD"},
+                        "format": {"type": "string", "description": "Pattern with %Y (4-digit year), %m (2-digit month), %d (2-digit day), %A (weekday name), %B (month name) and %% (literal %), e.g. \"%A, %B %d %Y\""}
+                    },
+                    "required": ["date", "format"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Write a YYYY-MM-DD date using a strftime-like pattern. Only %Y, %m, %d, %A, %B and %% are supported, with English names; other directives are errors".to_string()),
+                    output_schema: None,
+                    title: Some("Format Date".to_string()),
+                }),
+            },
+        ]
+        .into_iter()
+        .filter(|tool| tool_filter::is_exposed(&tool.name))
+        .collect();
+        let (tools, next_cursor) =
+            pagination::paginate(exposed, request.cursor.as_deref(), TOOLS_PAGE_SIZE);
+
+        Ok(ListToolsResult {
+            tools,
+            next_cursor,
+            meta: None,
+        })
+    }
+
+    fn call_tool(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: CallToolRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Option<CallToolResult> {
+        if !tool_filter::is_exposed(&request.name) {
+            return None;
+        }
+
+        match request.name.as_str() {
+            "date_diff" => Some(execute_date_diff(&request.arguments)),
+            "add_days" => Some(execute_add_days(&request.arguments)),
+            "format_date" => Some(execute_format_date(&request.arguments)),
+            _ => None, // We don't handle this tool
+        }
+    }
+}
+
+/// Largest number of tools returned by one `list_tools` call
+const TOOLS_PAGE_SIZE: usize = pagination::DEFAULT_PAGE_SIZE;
+
+fn execute_date_diff(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_date_arg(arguments, "start").and_then(|start| Ok((start, parse_date_arg(arguments, "end")?)));
+
+    match parsed {
+        Ok((start, end)) => success_result((end.julian_day() - start.julian_day()).to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_add_days(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_date_arg(arguments, "date").and_then(|date| Ok((date, parse_arg::<i64>(arguments, "days")?)));
+
+    match parsed.and_then(|(date, days)| {
+        date.julian_day()
+            .checked_add(days)
+            .and_then(Date::from_julian_day)
+            .ok_or_else(|| format!("Adding {} days to {} goes outside years 0000 to 9999", days, date))
+    }) {
+        Ok(result) => success_result(result.to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_format_date(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_date_arg(arguments, "date").and_then(|date| Ok((date, parse_arg::<String>(arguments, "format")?)));
+
+    match parsed.and_then(|(date, pattern)| {
+        date.format(&pattern).map_err(|e| format!("Parameter 'format' is not a valid pattern: {}", e))
+    }) {
+        Ok(formatted) => success_result(formatted),
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// The date parameter `name`, parsed
+fn parse_date_arg(arguments: &Option<String>, name: &str) -> Result<Date, String> {
+    let text: String = parse_arg(arguments, name)?;
+    Date::parse(&text).map_err(|e| format!("Parameter '{}' is not a valid date: {}", name, e))
+}
+
+bindings::export!(DatetimeTools with_types_in bindings);
@@ -20,6 +20,14 @@ use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasmcp::protocol::server_messages::Context;
 use bindings::wasmcp::server::handler as downstream;
 use bindings::wasi::io::streams::OutputStream;
+use mcp_utils::annotations::ToolHints;
+use mcp_utils::args::parse_two_number_args;
+
+mcp_utils::result_builders!();
+use result_builder::{error_result, number_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
 
 struct DistanceCalculator;
 
@@ -93,12 +101,12 @@ fn handle_tools_list(
         .to_string(),
         options: Some(ToolOptions {
             meta: None,
-            annotations: None,
+            annotations: Some(build_annotations(ToolHints::SAFE)),
             description: Some(
                 "Calculate Euclidean distance between two points: d = √((x2-x1)² + (y2-y1)²)"
                     .to_string(),
             ),
-            output_schema: None,
+            output_schema: Some(NUMBER_OUTPUT_SCHEMA.to_string()),
             title: Some("2D Distance Calculator".to_string()),
         }),
     });
@@ -184,9 +192,7 @@ fn handle_distance_call(
         Err(e) => return Ok(ServerResponse::ToolsCall(error_result(e))),
     };
 
-    Ok(ServerResponse::ToolsCall(success_result(
-        distance.to_string(),
-    )))
+    Ok(ServerResponse::ToolsCall(number_result(distance)))
 }
 
 fn call_downstream_tool(
@@ -210,33 +216,8 @@ fn call_downstream_tool(
 }
 
 fn parse_distance_args(arguments: &Option<String>) -> Result<(f64, f64, f64, f64), String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
-
-    let x1 = json
-        .get("x1")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'x1'".to_string())?;
-
-    let y1 = json
-        .get("y1")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'y1'".to_string())?;
-
-    let x2 = json
-        .get("x2")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'x2'".to_string())?;
-
-    let y2 = json
-        .get("y2")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'y2'".to_string())?;
-
+    let (x1, y1) = parse_two_number_args(arguments, "x1", "y1")?;
+    let (x2, y2) = parse_two_number_args(arguments, "x2", "y2")?;
     Ok((x1, y1, x2, y2))
 }
 
@@ -258,28 +239,13 @@ fn extract_number_from_result(result: &CallToolResult) -> Result<f64, String> {
     Err("No text content found in result".to_string())
 }
 
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
-    }
-}
-
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
-    }
-}
+/// Shape of the `structured_content` returned alongside the text result
+const NUMBER_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "result": {"type": "number"}
+    },
+    "required": ["result"]
+}"#;
 
 bindings::export!(DistanceCalculator with_types_in bindings);
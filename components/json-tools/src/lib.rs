@@ -0,0 +1,199 @@
+//! json-tools Tools Capability Provider
+//!
+//! A tools capability that provides JSON formatting, extraction and editing
+//! operations. Object keys keep their document order, so formatting never
+//! reorders a document. Values are addressed by dot-separated paths; see
+//! [`path`].
+//!
+//! Which tools are exposed is configured by [`tool_filter`], and `list_tools`
+//! returns them in pages (see [`pagination`]).
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "json-tools",
+        generate_all,
+    });
+}
+
+mod path;
+
+use bindings::exports::wasmcp::protocol::tools::Guest;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasi::io::streams::OutputStream;
+use mcp_utils::annotations::ToolHints;
+use mcp_utils::args::parse_arg;
+use mcp_utils::{pagination, tool_filter};
+
+mcp_utils::result_builders!();
+use result_builder::{error_result, success_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
+
+struct JsonTools;
+
+impl Guest for JsonTools {
+    fn list_tools(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: ListToolsRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Result<ListToolsResult, ErrorCode> {
+        let exposed: Vec<Tool> = vec![
+            Tool {
+                name: "json_format".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "json": {"type": "string", "description": "JSON document to pretty-print"}
+                    },
+                    "required": ["json"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Validate a JSON document and pretty-print it with 2-space indentation, keeping object keys in their original order".to_string()),
+                    output_schema: None,
+                    title: Some("JSON Format".to_string()),
+                }),
+            },
+            Tool {
+                name: "json_minify".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "json": {"type": "string", "description": "JSON document to minify"}
+                    },
+                    "required": ["json"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Validate a JSON document and re-serialize it compactly without whitespace, keeping object keys in their original order".to_string()),
+                    output_schema: None,
+                    title: Some("JSON Minify".to_string()),
+                }),
+            },
+            Tool {
+                name: "json_get".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "json": {"type": "string", "description": "JSON document to read from"},
+                        "path": {"type": "string", "description": "Dot-separated path, e.g. user.address.city or items.0.name; empty for the whole document"}
+                    },
+                    "required": ["json", "path"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Extract the value at a dot-separated path, returned as compact JSON (strings keep their quotes). Numeric segments index into arrays. A missing path is an error naming the first segment that was not found".to_string()),
+                    output_schema: None,
+                    title: Some("JSON Get".to_string()),
+                }),
+            },
+            Tool {
+                name: "json_set".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "json": {"type": "string", "description": "JSON document to modify"},
+                        "path": {"type": "string", "description": "Dot-separated path, e.g. user.address.city or items.0.name; empty to replace the whole document"},
+                        "value": {"type": "string", "description": "New value as JSON text, e.g. \"Oslo\" (with quotes), 42 or {\"a\": 1}"}
+                    },
+                    "required": ["json", "path", "value"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Insert or replace the value at a dot-separated path and return the modified document as compact JSON. Missing object keys along the path are created as empty objects. Numeric segments index into arrays, and an index past the end of an array is an error".to_string()),
+                    output_schema: None,
+                    title: Some("JSON Set".to_string()),
+                }),
+            },
+        ]
+        .into_iter()
+        .filter(|tool| tool_filter::is_exposed(&tool.name))
+        .collect();
+        let (tools, next_cursor) =
+            pagination::paginate(exposed, request.cursor.as_deref(), TOOLS_PAGE_SIZE);
+
+        Ok(ListToolsResult {
+            tools,
+            next_cursor,
+            meta: None,
+        })
+    }
+
+    fn call_tool(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: CallToolRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Option<CallToolResult> {
+        if !tool_filter::is_exposed(&request.name) {
+            return None;
+        }
+
+        match request.name.as_str() {
+            "json_format" => Some(execute_json_format(&request.arguments)),
+            "json_minify" => Some(execute_json_minify(&request.arguments)),
+            "json_get" => Some(execute_json_get(&request.arguments)),
+            "json_set" => Some(execute_json_set(&request.arguments)),
+            _ => None, // We don't handle this tool
+        }
+    }
+}
+
+/// Largest number of tools returned by one `list_tools` call
+const TOOLS_PAGE_SIZE: usize = pagination::DEFAULT_PAGE_SIZE;
+
+fn execute_json_format(arguments: &Option<String>) -> CallToolResult {
+    // serde_json's pretty printer indents with two spaces
+    match parse_json_arg(arguments).and_then(|value| {
+        serde_json::to_string_pretty(&value).map_err(|e| format!("Failed to serialize JSON: {}", e))
+    }) {
+        Ok(pretty) => success_result(pretty),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_json_minify(arguments: &Option<String>) -> CallToolResult {
+    match parse_json_arg(arguments) {
+        Ok(value) => success_result(value.to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_json_get(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_json_arg(arguments).and_then(|value| Ok((value, parse_arg::<String>(arguments, "path")?)));
+
+    match parsed.and_then(|(value, path)| path::get(&value, &path).map(|found| found.to_string())) {
+        Ok(found) => success_result(found),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_json_set(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_json_arg(arguments).and_then(|document| {
+        let path: String = parse_arg(arguments, "path")?;
+        let value: String = parse_arg(arguments, "value")?;
+        let value = serde_json::from_str(&value).map_err(|e| format!("Parameter 'value' is not valid JSON: {}", e))?;
+        Ok((document, path, value))
+    });
+
+    match parsed.and_then(|(mut document, path, value)| path::set(&mut document, &path, value).map(|()| document)) {
+        Ok(document) => success_result(document.to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// The `json` parameter, parsed
+fn parse_json_arg(arguments: &Option<String>) -> Result<serde_json::Value, String> {
+    let json: String = parse_arg(arguments, "json")?;
+    serde_json::from_str(&json).map_err(|e| format!("Parameter 'json' is not valid JSON: {}", e))
+}
+
+bindings::export!(JsonTools with_types_in bindings);
@@ -0,0 +1,98 @@
+//! Dot-separated paths into a JSON document
+//!
+//! A path such as `user.addresses.0.city` is split on `.`; each segment is an
+//! object key, or an array index when the value it is applied to is an array.
+//! The empty path refers to the whole document. Keys that contain `.` cannot
+//! be addressed.
+//!
+//! Setting a value creates missing object keys along the way, but never grows
+//! an array: an index must already exist.
+
+use serde_json::Value;
+
+/// Segments of `path`, none for the empty path
+pub fn segments(path: &str) -> Vec<&str> {
+    if path.is_empty() { Vec::new() } else { path.split('.').collect() }
+}
+
+/// The value at `path`, or an error naming the first segment that is missing
+pub fn get<'a>(value: &'a Value, path: &str) -> Result<&'a Value, String> {
+    let segments = segments(path);
+    let mut current = value;
+
+    for (i, segment) in segments.iter().enumerate() {
+        current = step(current, segment).ok_or_else(|| not_found(&segments[..i], segment, current))?;
+    }
+    Ok(current)
+}
+
+/// Insert or replace the value at `path` with `new`
+pub fn set(value: &mut Value, path: &str, new: Value) -> Result<(), String> {
+    let segments = segments(path);
+    let mut current = value;
+
+    for (i, segment) in segments.iter().enumerate() {
+        let reachable = match &*current {
+            Value::Object(_) => true,
+            Value::Array(items) => segment.parse::<usize>().is_ok_and(|index| index < items.len()),
+            _ => false,
+        };
+        if !reachable {
+            return Err(not_found(&segments[..i], segment, current));
+        }
+
+        current = match current {
+            Value::Object(map) => map
+                .entry(segment.to_string())
+                .or_insert_with(|| Value::Object(Default::default())),
+            Value::Array(items) => &mut items[segment.parse::<usize>().unwrap_or_default()],
+            _ => unreachable!("checked above"),
+        };
+    }
+
+    *current = new;
+    Ok(())
+}
+
+fn step<'a>(value: &'a Value, segment: &str) -> Option<&'a Value> {
+    match value {
+        Value::Object(map) => map.get(segment),
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+        _ => None,
+    }
+}
+
+/// Why `segment` could not be applied to `value`, found at `parent`
+fn not_found(parent: &[&str], segment: &str, value: &Value) -> String {
+    let at = if parent.is_empty() { "the document".to_string() } else { format!("'{}'", parent.join(".")) };
+
+    match value {
+        Value::Object(_) => format!("Path not found: {} has no key '{}'", at, segment),
+        Value::Array(items) => match segment.parse::<usize>() {
+            Ok(index) => format!(
+                "Path not found: index {} is out of bounds for {}, an array of length {}",
+                index,
+                at,
+                items.len()
+            ),
+            Err(_) => format!("Path not found: {} is an array, so '{}' must be an index", at, segment),
+        },
+        other => format!(
+            "Path not found: {} is {}, which has no '{}'",
+            at,
+            type_name(other),
+            segment
+        ),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
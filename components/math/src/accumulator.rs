@@ -0,0 +1,224 @@
+//! Running totals for the accumulator tools, kept in wasi:keyvalue
+//!
+//! Each accumulator is stored in the host's default bucket under
+//! `accumulator:<id>`, as the decimal text of its total. A missing key is a
+//! total of zero. Updates read, add and write back, so two concurrent
+//! `accumulator_add` calls on the same id may lose one of the additions.
+//!
+//! Store failures, including a host that has no key-value store configured,
+//! are returned as errors naming the missing capability rather than trapping.
+//!
+//! The bucket is used through the [`Store`] trait; tests swap it for an
+//! in-memory map, since the host's store is not available to them.
+
+use crate::bindings::wasi::keyvalue::store::Error;
+#[cfg(not(test))]
+use crate::bindings::wasi::keyvalue::store::{self, Bucket};
+
+/// Identifier passed to `store::open`; empty selects the host's default store
+#[cfg(not(test))]
+const BUCKET: &str = "";
+
+/// Longest accepted accumulator id, in bytes
+pub const MAX_ID_LEN: usize = 256;
+
+/// Current total of accumulator `id`, zero if it was never added to
+pub fn get(id: &str) -> Result<f64, String> {
+    read_total(&open()?, id)
+}
+
+/// Add `value` to accumulator `id` and return the new total
+pub fn add(id: &str, value: f64) -> Result<f64, String> {
+    add_in(&open()?, id, value)
+}
+
+/// Remove accumulator `id`, returning the total it held
+pub fn reset(id: &str) -> Result<f64, String> {
+    reset_in(&open()?, id)
+}
+
+fn add_in(bucket: &impl Store, id: &str, value: f64) -> Result<f64, String> {
+    let total = read_total(bucket, id)? + value;
+    if !total.is_finite() {
+        return Err(format!(
+            "Error: Adding {} to accumulator '{}' overflows to infinity",
+            value, id
+        ));
+    }
+
+    bucket
+        .set(&key(id), total.to_string().as_bytes())
+        .map_err(|e| store_error("write", id, e))?;
+    Ok(total)
+}
+
+fn reset_in(bucket: &impl Store, id: &str) -> Result<f64, String> {
+    let previous = read_total(bucket, id)?;
+    bucket
+        .delete(&key(id))
+        .map_err(|e| store_error("reset", id, e))?;
+    Ok(previous)
+}
+
+/// Reject ids that are empty or too long to be a reasonable key
+pub fn check_id(id: &str) -> Result<(), String> {
+    if id.is_empty() {
+        return Err("Parameter 'id' must not be empty".to_string());
+    }
+    if id.len() > MAX_ID_LEN {
+        return Err(format!(
+            "Parameter 'id' must be at most {} bytes",
+            MAX_ID_LEN
+        ));
+    }
+    Ok(())
+}
+
+/// The key-value operations the accumulators need
+trait Store {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error>;
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), Error>;
+    fn delete(&self, key: &str) -> Result<(), Error>;
+}
+
+#[cfg(not(test))]
+impl Store for Bucket {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        Bucket::get(self, key)
+    }
+
+    fn set(&self, key: &str, value: &[u8]) -> Result<(), Error> {
+        Bucket::set(self, key, value)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), Error> {
+        Bucket::delete(self, key)
+    }
+}
+
+#[cfg(not(test))]
+fn open() -> Result<Bucket, String> {
+    store::open(BUCKET).map_err(|e| {
+        format!(
+            "Error: The accumulator tools need a wasi:keyvalue store, but the host did not provide one ({}). \
+             Run the server on a host with a key-value store configured",
+            describe_error(&e)
+        )
+    })
+}
+
+fn read_total(bucket: &impl Store, id: &str) -> Result<f64, String> {
+    let Some(bytes) = bucket
+        .get(&key(id))
+        .map_err(|e| store_error("read", id, e))?
+    else {
+        return Ok(0.0);
+    };
+
+    std::str::from_utf8(&bytes)
+        .ok()
+        .and_then(|text| text.parse::<f64>().ok())
+        .filter(|total| total.is_finite())
+        .ok_or_else(|| format!("Error: Accumulator '{}' holds a value that is not a number; reset it to start again", id))
+}
+
+fn key(id: &str) -> String {
+    format!("accumulator:{}", id)
+}
+
+fn store_error(action: &str, id: &str, error: Error) -> String {
+    format!(
+        "Error: Could not {} accumulator '{}': {}",
+        action,
+        id,
+        describe_error(&error)
+    )
+}
+
+fn describe_error(error: &Error) -> String {
+    match error {
+        Error::NoSuchStore => "no such store".to_string(),
+        Error::AccessDenied => "access denied".to_string(),
+        Error::Other(msg) => msg.clone(),
+    }
+}
+
+#[cfg(test)]
+use tests::open;
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    thread_local! {
+        /// Contents of the stub store; each test runs on its own thread
+        static ENTRIES: RefCell<HashMap<String, Vec<u8>>> = RefCell::new(HashMap::new());
+    }
+
+    /// In-memory stand-in for the host's default bucket
+    pub struct MemoryStore;
+
+    impl Store for MemoryStore {
+        fn get(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+            Ok(ENTRIES.with(|entries| entries.borrow().get(key).cloned()))
+        }
+
+        fn set(&self, key: &str, value: &[u8]) -> Result<(), Error> {
+            ENTRIES.with(|entries| entries.borrow_mut().insert(key.to_string(), value.to_vec()));
+            Ok(())
+        }
+
+        fn delete(&self, key: &str) -> Result<(), Error> {
+            ENTRIES.with(|entries| entries.borrow_mut().remove(key));
+            Ok(())
+        }
+    }
+
+    pub fn open() -> Result<MemoryStore, String> {
+        Ok(MemoryStore)
+    }
+
+    #[test]
+    fn missing_accumulators_are_zero() {
+        assert_eq!(get("never-added"), Ok(0.0));
+        assert_eq!(reset("never-added"), Ok(0.0));
+    }
+
+    #[test]
+    fn add_get_and_reset() {
+        assert_eq!(add("groceries", 12.5), Ok(12.5));
+        assert_eq!(add("groceries", -2.5), Ok(10.0));
+        assert_eq!(add("fuel", 40.0), Ok(40.0));
+        assert_eq!(get("groceries"), Ok(10.0));
+        assert_eq!(
+            MemoryStore.get("accumulator:groceries").ok(),
+            Some(Some(b"10".to_vec()))
+        );
+
+        assert_eq!(reset("groceries"), Ok(10.0));
+        assert_eq!(get("groceries"), Ok(0.0));
+        assert_eq!(MemoryStore.get("accumulator:groceries").ok(), Some(None));
+        assert_eq!(get("fuel"), Ok(40.0));
+    }
+
+    #[test]
+    fn overflow_and_corrupt_totals_are_errors() {
+        add("big", f64::MAX).unwrap();
+        let error = add("big", f64::MAX).unwrap_err();
+        assert!(error.contains("overflows to infinity"), "{}", error);
+        assert_eq!(get("big"), Ok(f64::MAX));
+
+        MemoryStore.set("accumulator:corrupt", b"twelve").ok();
+        let error = get("corrupt").unwrap_err();
+        assert!(error.contains("not a number"), "{}", error);
+    }
+
+    #[test]
+    fn ids_are_checked() {
+        assert!(check_id("").is_err());
+        assert!(check_id(&"x".repeat(MAX_ID_LEN)).is_ok());
+        assert!(check_id(&"x".repeat(MAX_ID_LEN + 1)).is_err());
+    }
+}
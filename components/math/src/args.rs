@@ -0,0 +1,750 @@
+//! Typed argument parsing for the calculator tools
+//!
+//! Tool arguments arrive as a JSON object string. Each parameter is
+//! deserialized with serde into its Rust type, and failures name the
+//! parameter, its JSON pointer and the value that was actually received:
+//!
+//! ```text
+//! Missing parameter 'a' at /a
+//! Invalid parameter 'a' at /a: expected number, got string "12"
+//! Invalid parameter 'numbers' at /numbers/2: expected integer, got number 1.5
+//! Unknown parameter 'x', did you mean 'a'?
+//! ```
+//!
+//! Numbers quoted as strings, such as `"3"`, `" 1e3 "` or `"+4"`, are
+//! accepted wherever a tool's schema expects a number; see
+//! [`coerce_numeric_strings`].
+
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+/// The argument object passed to a tool call
+pub struct Args(Map<String, Value>);
+
+impl Args {
+    pub fn parse(arguments: &Option<String>) -> Result<Self, String> {
+        let args_str = arguments
+            .as_ref()
+            .ok_or_else(|| "Missing arguments".to_string())?;
+
+        let json: Value =
+            serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+        match json {
+            Value::Object(map) => Ok(Args(map)),
+            other => Err(format!(
+                "Invalid arguments: expected a JSON object, got {}",
+                describe(&other)
+            )),
+        }
+    }
+
+    /// Arguments nested inside another parameter, such as one item of an array
+    pub fn from_value(value: &Value) -> Result<Self, String> {
+        match value {
+            Value::Object(map) => Ok(Args(map.clone())),
+            other => Err(format!("Expected an object, got {}", describe(other))),
+        }
+    }
+
+    pub fn required<T: ArgType>(&self, name: &str) -> Result<T, String> {
+        self.optional(name)?
+            .ok_or_else(|| format!("Missing parameter '{}' at {}", name, pointer(name)))
+    }
+
+    /// Reject keys outside `expected`, suggesting the closest expected key
+    /// that was not given. Catches misnamed parameters such as `x` for `a`
+    /// before they surface as a less helpful missing-parameter error.
+    pub fn check_keys(&self, expected: &[&str], unknown: UnknownKeys) -> Result<(), String> {
+        if let UnknownKeys::Ignore = unknown {
+            return Ok(());
+        }
+        let Some(name) = self.0.keys().find(|key| !expected.contains(&key.as_str())) else {
+            return Ok(());
+        };
+
+        let missing: Vec<&str> = expected
+            .iter()
+            .copied()
+            .filter(|key| !self.0.contains_key(*key))
+            .collect();
+        match closest(name, &missing) {
+            Some(suggestion) => Err(format!(
+                "Unknown parameter '{}', did you mean '{}'?",
+                name, suggestion
+            )),
+            None if expected.is_empty() => Err(format!(
+                "Unknown parameter '{}'; this tool takes no parameters",
+                name
+            )),
+            None => Err(format!(
+                "Unknown parameter '{}'; expected {}",
+                name,
+                expected
+                    .iter()
+                    .map(|key| format!("'{}'", key))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// Absent and `null` parameters are both treated as not given
+    pub fn optional<T: ArgType>(&self, name: &str) -> Result<Option<T>, String> {
+        match self.0.get(name) {
+            None | Some(Value::Null) => Ok(None),
+            Some(value) => T::from_arg(value, name, &pointer(name)).map(Some),
+        }
+    }
+}
+
+/// How [`Args::check_keys`] treats keys a tool does not declare
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnknownKeys {
+    Reject,
+    /// For tools that accept passthrough metadata alongside their parameters
+    Ignore,
+}
+
+/// The candidate nearest to `name` by edit distance, if it is close enough
+/// to be a plausible typo: at most half the length of the longer name, and
+/// always allowing one edit. Ties go to the earlier candidate.
+fn closest<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    candidates
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), *candidate))
+        .filter(|(distance, candidate)| {
+            *distance <= (name.chars().count().max(candidate.chars().count()) / 2).max(1)
+        })
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings, counted in chars
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// A parameter type, with the wording used for it in error messages
+pub trait ArgType: DeserializeOwned {
+    fn expected() -> String;
+
+    fn from_arg(value: &Value, name: &str, pointer: &str) -> Result<Self, String> {
+        Self::deserialize(value).map_err(|_| {
+            format!(
+                "Invalid parameter '{}' at {}: expected {}, got {}",
+                name,
+                pointer,
+                Self::expected(),
+                describe(value)
+            )
+        })
+    }
+}
+
+impl ArgType for f64 {
+    fn expected() -> String {
+        "number".to_string()
+    }
+}
+
+impl ArgType for bool {
+    fn expected() -> String {
+        "boolean".to_string()
+    }
+}
+
+impl ArgType for String {
+    fn expected() -> String {
+        "string".to_string()
+    }
+}
+
+/// Any JSON value; used for parameters whose items are checked individually
+impl ArgType for Value {
+    fn expected() -> String {
+        "JSON value".to_string()
+    }
+}
+
+/// Arrays report the pointer of the offending element rather than the array
+impl<T: ArgType> ArgType for Vec<T> {
+    fn expected() -> String {
+        format!("array of {}s", T::expected())
+    }
+
+    fn from_arg(value: &Value, name: &str, pointer: &str) -> Result<Self, String> {
+        let items = value.as_array().ok_or_else(|| {
+            format!(
+                "Invalid parameter '{}' at {}: expected {}, got {}",
+                name,
+                pointer,
+                Self::expected(),
+                describe(value)
+            )
+        })?;
+
+        items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| T::from_arg(item, name, &format!("{}/{}", pointer, i)))
+            .collect()
+    }
+}
+
+/// Integer argument that also accepts integral floats such as `12.0`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Integer(pub i128);
+
+impl<'de> Deserialize<'de> for Integer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        value
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| value.as_u64().map(i128::from))
+            .or_else(|| {
+                value
+                    .as_f64()
+                    .filter(|f| f.fract() == 0.0 && f.abs() < 2f64.powi(64))
+                    .map(|f| f as i128)
+            })
+            .map(Integer)
+            .ok_or_else(|| D::Error::custom("expected an integer"))
+    }
+}
+
+impl ArgType for Integer {
+    fn expected() -> String {
+        "integer".to_string()
+    }
+}
+
+/// Operand of the `bitwise` tool: a signed 64-bit decimal integer (possibly
+/// quoted), or a hex string such as `"0xff"` of up to 16 digits. Either way
+/// the operation works on the 64-bit two's-complement bit pattern.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bits(pub u64);
+
+impl<'de> Deserialize<'de> for Bits {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::String(text) => {
+                match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+                    Some(digits) => Some(digits)
+                        .filter(|digits| !digits.is_empty() && digits.len() <= 16)
+                        .and_then(|digits| u64::from_str_radix(digits, 16).ok())
+                        .map(Bits),
+                    // A quoted decimal integer
+                    None => parse_number_string(&text)
+                        .and_then(|n| n.as_i64())
+                        .map(|n| Bits(n as u64)),
+                }
+                .ok_or_else(|| D::Error::custom("expected a hex string"))
+            }
+            value => value
+                .as_i64()
+                .or_else(|| {
+                    value
+                        .as_f64()
+                        .filter(|f| {
+                            f.fract() == 0.0 && *f >= i64::MIN as f64 && *f < i64::MAX as f64
+                        })
+                        .map(|f| f as i64)
+                })
+                .map(|n| Bits(n as u64))
+                .ok_or_else(|| D::Error::custom("expected a 64-bit integer")),
+        }
+    }
+}
+
+impl ArgType for Bits {
+    fn expected() -> String {
+        "64-bit integer or hex string such as \"0xff\"".to_string()
+    }
+}
+
+/// Operand of the `complex` tool: `{"re": 3, "im": 4}`, or the same number
+/// written as a string such as `"3+4i"`, `"-2.5i"` or `"7"`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl<'de> Deserialize<'de> for Complex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Parts { re: f64, im: f64 },
+            Text(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Parts { re, im } => Ok(Complex { re, im }),
+            Repr::Text(text) => parse_complex(&text)
+                .ok_or_else(|| D::Error::custom("expected a complex number such as 3+4i")),
+        }
+    }
+}
+
+impl ArgType for Complex {
+    fn expected() -> String {
+        "complex number {\"re\": number, \"im\": number} or string such as \"3+4i\"".to_string()
+    }
+}
+
+/// Parse `a+bi`, `a-bi`, `bi` or `a`; whitespace is ignored and a bare `i`
+/// stands for `1i`
+fn parse_complex(text: &str) -> Option<Complex> {
+    let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let Some(imaginary) = text.strip_suffix('i') else {
+        return Some(Complex {
+            re: parse_finite(&text)?,
+            im: 0.0,
+        });
+    };
+
+    // The imaginary part starts at the last sign that is not leading and not
+    // an exponent sign, as in "1e-3-2e+4i"
+    let split = imaginary
+        .char_indices()
+        .rev()
+        .find(|&(i, c)| (c == '+' || c == '-') && i > 0 && !imaginary[..i].ends_with(['e', 'E']))
+        .map(|(i, _)| i);
+    let (re, im) = match split {
+        Some(i) => (parse_finite(&imaginary[..i])?, &imaginary[i..]),
+        None => (0.0, imaginary),
+    };
+    let im = match im {
+        "" | "+" => 1.0,
+        "-" => -1.0,
+        im => parse_finite(im)?,
+    };
+    Some(Complex { re, im })
+}
+
+/// Like `str::parse`, but rejecting "inf" and "NaN"
+fn parse_finite(text: &str) -> Option<f64> {
+    text.parse().ok().filter(|n: &f64| n.is_finite())
+}
+
+/// Operands of the two-argument arithmetic tools
+pub struct Operands {
+    pub a: f64,
+    pub b: f64,
+}
+
+impl Operands {
+    pub fn parse(arguments: &Option<String>) -> Result<Self, String> {
+        let args = Args::parse(arguments)?;
+        Ok(Operands {
+            a: args.required("a")?,
+            b: args.required("b")?,
+        })
+    }
+}
+
+/// Arguments of the `power` tool
+pub struct PowerArgs {
+    pub base: f64,
+    pub exponent: f64,
+}
+
+impl PowerArgs {
+    pub fn parse(arguments: &Option<String>) -> Result<Self, String> {
+        let args = Args::parse(arguments)?;
+        Ok(PowerArgs {
+            base: args.required("base")?,
+            exponent: args.required("exponent")?,
+        })
+    }
+}
+
+/// One item of the `batch` tool
+pub struct BatchItem {
+    pub op: String,
+    pub a: Operand,
+    pub b: Operand,
+}
+
+impl BatchItem {
+    pub fn parse(value: &Value) -> Result<Self, String> {
+        let args = Args::from_value(value)?;
+        Ok(BatchItem {
+            op: args.required("op")?,
+            a: args.required("a")?,
+            b: args.required("b")?,
+        })
+    }
+}
+
+/// A batch operand: a literal number or the result of an earlier item
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum Operand {
+    Number(f64),
+    Ref {
+        #[serde(rename = "$ref")]
+        index: usize,
+    },
+}
+
+impl Operand {
+    pub fn resolve(&self, name: &str, results: &[Result<f64, String>]) -> Result<f64, String> {
+        match *self {
+            Operand::Number(value) => Ok(value),
+            Operand::Ref { index } => match results.get(index) {
+                Some(Ok(value)) => Ok(*value),
+                Some(Err(_)) => Err(format!(
+                    "Operand '{}' refers to item {}, which failed",
+                    name, index
+                )),
+                None => Err(format!(
+                    "Operand '{}' refers to item {}, but only earlier items can be referenced",
+                    name, index
+                )),
+            },
+        }
+    }
+}
+
+impl ArgType for Operand {
+    fn expected() -> String {
+        "number or {\"$ref\": index}".to_string()
+    }
+}
+
+/// Token a client passes to receive progress notifications for a call
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum ProgressToken {
+    String(String),
+    Integer(i64),
+}
+
+impl ArgType for ProgressToken {
+    fn expected() -> String {
+        "string or integer".to_string()
+    }
+}
+
+/// Replace strings that parse cleanly as numbers with those numbers, wherever
+/// `schema` expects a number or integer and does not also allow a string.
+/// Recurses into `properties`, `items` and the `oneOf` alternatives matching
+/// the value's kind, so array items and batch operands are covered too.
+/// Other strings are left alone, for validation to report as received.
+pub fn coerce_numeric_strings(schema: &Value, value: &mut Value) {
+    let alternatives: Vec<&Value> = match schema.get("oneOf") {
+        Some(Value::Array(options)) => options.iter().collect(),
+        _ => vec![schema],
+    };
+
+    match value {
+        Value::String(text) => {
+            let types: Vec<&str> = alternatives
+                .iter()
+                .flat_map(|option| schema_types(option))
+                .collect();
+            let wants_number = types.iter().any(|t| *t == "number" || *t == "integer");
+            if wants_number && !types.contains(&"string") {
+                if let Some(number) = parse_number_string(text) {
+                    *value = number;
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item_schema in alternatives.iter().filter_map(|option| option.get("items")) {
+                for item in items.iter_mut() {
+                    coerce_numeric_strings(item_schema, item);
+                }
+            }
+        }
+        Value::Object(map) => {
+            for properties in alternatives
+                .iter()
+                .filter_map(|option| option.get("properties")?.as_object())
+            {
+                for (name, property_schema) in properties {
+                    if let Some(property) = map.get_mut(name) {
+                        coerce_numeric_strings(property_schema, property);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The `type` keyword of a schema as a list
+fn schema_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse a quoted number: surrounding whitespace and one leading `+` are
+/// allowed, infinities and NaN are not. Integers stay exact up to 64 bits.
+fn parse_number_string(text: &str) -> Option<Value> {
+    let text = text.trim();
+    let unsigned = text.strip_prefix('+').unwrap_or(text);
+    if unsigned.is_empty() || (unsigned.len() != text.len() && unsigned.starts_with(['+', '-'])) {
+        return None;
+    }
+
+    if let Ok(n) = unsigned.parse::<i64>() {
+        return Some(n.into());
+    }
+    if let Ok(n) = unsigned.parse::<u64>() {
+        return Some(n.into());
+    }
+    unsigned
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite())
+        .and_then(serde_json::Number::from_f64)
+        .map(Value::Number)
+}
+
+/// JSON pointer (RFC 6901) of a top-level parameter
+fn pointer(name: &str) -> String {
+    format!("/{}", name.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Short description of a received value for error messages
+pub fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => format!("boolean {}", b),
+        Value::Number(n) => format!("number {}", n),
+        Value::String(_) => format!("string {}", value),
+        Value::Array(items) if items.len() == 1 => "array of 1 item".to_string(),
+        Value::Array(items) => format!("array of {} items", items.len()),
+        Value::Object(_) => "object".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Result<Args, String> {
+        Args::parse(&Some(json.to_string()))
+    }
+
+    fn operands_error(json: &str) -> String {
+        match Operands::parse(&Some(json.to_string())) {
+            Ok(_) => panic!("{} should not parse", json),
+            Err(message) => message,
+        }
+    }
+
+    #[test]
+    fn absent_arguments() {
+        assert_eq!(
+            Args::parse(&None).err().as_deref(),
+            Some("Missing arguments")
+        );
+    }
+
+    #[test]
+    fn arguments_that_are_not_json() {
+        let message = parse("{\"a\": 1,").err().expect("truncated JSON");
+        assert!(
+            message.starts_with("Invalid JSON arguments: "),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn arguments_that_are_not_an_object() {
+        for (json, got) in [
+            ("[1, 2]", "array of 2 items"),
+            ("42", "number 42"),
+            ("\"a\"", "string \"a\""),
+            ("null", "null"),
+        ] {
+            assert_eq!(
+                parse(json).err(),
+                Some(format!(
+                    "Invalid arguments: expected a JSON object, got {}",
+                    got
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn missing_and_null_parameters() {
+        assert_eq!(operands_error(r#"{"b": 1}"#), "Missing parameter 'a' at /a");
+        assert_eq!(
+            operands_error(r#"{"a": 1, "b": null}"#),
+            "Missing parameter 'b' at /b"
+        );
+    }
+
+    #[test]
+    fn parameter_of_the_wrong_type() {
+        assert_eq!(
+            operands_error(r#"{"a": "12", "b": 1}"#),
+            "Invalid parameter 'a' at /a: expected number, got string \"12\""
+        );
+        assert_eq!(
+            operands_error(r#"{"a": 1, "b": {"x": 1}}"#),
+            "Invalid parameter 'b' at /b: expected number, got object"
+        );
+    }
+
+    #[test]
+    fn array_element_of_the_wrong_type() {
+        let args = parse(r#"{"numbers": [1, 2, 1.5]}"#).expect("object");
+        assert_eq!(
+            args.required::<Vec<Integer>>("numbers").err().as_deref(),
+            Some("Invalid parameter 'numbers' at /numbers/2: expected integer, got number 1.5")
+        );
+
+        let args = parse(r#"{"numbers": true}"#).expect("object");
+        assert_eq!(
+            args.required::<Vec<f64>>("numbers").err().as_deref(),
+            Some("Invalid parameter 'numbers' at /numbers: expected array of numbers, got boolean true")
+        );
+    }
+
+    #[test]
+    fn pointers_escape_special_characters() {
+        let args = parse(r#"{"a/b~c": "x"}"#).expect("object");
+        assert_eq!(
+            args.required::<f64>("a/b~c").err().as_deref(),
+            Some("Invalid parameter 'a/b~c' at /a~1b~0c: expected number, got string \"x\"")
+        );
+    }
+
+    #[test]
+    fn unknown_parameters() {
+        let args = parse(r#"{"x": 1, "b": 2}"#).expect("object");
+        assert_eq!(
+            args.check_keys(&["a", "b"], UnknownKeys::Reject)
+                .err()
+                .as_deref(),
+            Some("Unknown parameter 'x', did you mean 'a'?")
+        );
+        assert!(args.check_keys(&["a", "b"], UnknownKeys::Ignore).is_ok());
+
+        let args = parse(r#"{"verbose": true}"#).expect("object");
+        assert_eq!(
+            args.check_keys(&[], UnknownKeys::Reject).err().as_deref(),
+            Some("Unknown parameter 'verbose'; this tool takes no parameters")
+        );
+    }
+
+    #[test]
+    fn empty_object_reports_missing_not_unknown_parameters() {
+        let args = parse("{}").expect("object");
+        assert!(args.check_keys(&["a", "b"], UnknownKeys::Reject).is_ok());
+        assert_eq!(
+            args.required::<f64>("a").err().as_deref(),
+            Some("Missing parameter 'a' at /a")
+        );
+        assert_eq!(operands_error("{}"), "Missing parameter 'a' at /a");
+    }
+
+    #[test]
+    fn numeric_strings() {
+        assert_eq!(parse_number_string("1e3"), Some(Value::from(1000.0)));
+        assert_eq!(parse_number_string("-2.5E-2"), Some(Value::from(-0.025)));
+        assert_eq!(parse_number_string("  42\n"), Some(Value::from(42)));
+        assert_eq!(parse_number_string(" +1e3 "), Some(Value::from(1000.0)));
+        assert_eq!(
+            parse_number_string("18446744073709551615"),
+            Some(Value::from(u64::MAX))
+        );
+        for text in ["", "   ", "+", "+-3", "3abc", "1e", "inf", "NaN", "1e400"] {
+            assert_eq!(parse_number_string(text), None, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn numeric_strings_are_coerced_where_the_schema_wants_a_number() {
+        let schema: Value = serde_json::from_str(
+            r#"{"properties": {"a": {"type": "number"}, "b": {"type": "number"}, "label": {"type": "string"}}}"#,
+        )
+        .unwrap();
+        let mut value: Value =
+            serde_json::from_str(r#"{"a": " 1e3 ", "b": "", "label": "7"}"#).unwrap();
+        coerce_numeric_strings(&schema, &mut value);
+        assert_eq!(
+            value,
+            serde_json::json!({"a": 1000.0, "b": "", "label": "7"})
+        );
+
+        let args = Args(value.as_object().cloned().unwrap());
+        assert_eq!(
+            args.required::<f64>("b").err().as_deref(),
+            Some("Invalid parameter 'b' at /b: expected number, got string \"\"")
+        );
+    }
+
+    #[test]
+    fn batch_item_that_is_not_an_object() {
+        assert_eq!(
+            BatchItem::parse(&Value::from(3)).err().as_deref(),
+            Some("Expected an object, got number 3")
+        );
+    }
+
+    fn complex(json: &str) -> Result<Args, String> {
+        parse(&format!(r#"{{"z": {}}}"#, json))
+    }
+
+    #[test]
+    fn complex_numbers_as_parts() {
+        let z = |json: &str| complex(json).and_then(|args| args.required::<Complex>("z"));
+        assert_eq!(
+            z(r#"{"re": 3, "im": -4.5}"#),
+            Ok(Complex { re: 3.0, im: -4.5 })
+        );
+        assert_eq!(z(r#"{"re": 0, "im": 0}"#), Ok(Complex { re: 0.0, im: 0.0 }));
+        assert!(z(r#"{"re": 3}"#).is_err());
+        assert!(z(r#"{"re": "3", "im": 4}"#).is_err());
+    }
+
+    #[test]
+    fn complex_numbers_as_strings() {
+        let z = |text: &str| parse_complex(text);
+        assert_eq!(z("3+4i"), Some(Complex { re: 3.0, im: 4.0 }));
+        assert_eq!(z(" 3 - 4i "), Some(Complex { re: 3.0, im: -4.0 }));
+        assert_eq!(z("-i"), Some(Complex { re: 0.0, im: -1.0 }));
+        assert_eq!(z("i"), Some(Complex { re: 0.0, im: 1.0 }));
+        assert_eq!(z("2"), Some(Complex { re: 2.0, im: 0.0 }));
+        assert_eq!(z("-2.5i"), Some(Complex { re: 0.0, im: -2.5 }));
+        assert_eq!(z("1e-3-2e+4i"), Some(Complex { re: 1e-3, im: -2e4 }));
+        assert_eq!(z("1+i"), Some(Complex { re: 1.0, im: 1.0 }));
+    }
+
+    #[test]
+    fn malformed_complex_strings() {
+        for text in ["", "3+4j", "3+4ii", "abc", "3++4i", "i3", "inf+1i", "NaN"] {
+            assert_eq!(parse_complex(text), None, "{:?}", text);
+        }
+        let error = complex(r#""3+4j""#)
+            .and_then(|args| args.required::<Complex>("z"))
+            .expect_err("malformed complex string");
+        assert!(error.contains("Invalid parameter 'z' at /z"), "{}", error);
+    }
+}
@@ -0,0 +1,128 @@
+//! Usage examples advertised in each tool's `_meta`
+//!
+//! Only the inputs are written down here. `list_tools` runs each one through
+//! the tool itself to fill in the output, so an example cannot drift from
+//! what the tool actually returns; an example whose tool reports an error is
+//! left out rather than advertised. The accumulator tools keep state in the
+//! host's key-value store, so listing them must not run them and their
+//! outputs are given instead.
+
+use serde_json::{json, Value};
+
+pub struct Example {
+    pub tool: &'static str,
+    /// Arguments as a JSON object
+    pub input: &'static str,
+    /// Output of tools that cannot be run just to describe them
+    pub fixed_output: Option<&'static str>,
+}
+
+const fn computed(tool: &'static str, input: &'static str) -> Example {
+    Example {
+        tool,
+        input,
+        fixed_output: None,
+    }
+}
+
+const fn fixed(tool: &'static str, input: &'static str, output: &'static str) -> Example {
+    Example {
+        tool,
+        input,
+        fixed_output: Some(output),
+    }
+}
+
+pub const EXAMPLES: &[Example] = &[
+    computed("add", r#"{"a": 2, "b": 3}"#),
+    computed("subtract", r#"{"a": 10, "b": 4}"#),
+    computed("multiply", r#"{"a": 6, "b": 7}"#),
+    computed("divide", r#"{"a": 10, "b": 4}"#),
+    computed(
+        "batch",
+        r#"{"operations": [{"op": "add", "a": 2, "b": 3}, {"op": "multiply", "a": {"$ref": 0}, "b": 4}]}"#,
+    ),
+    computed("sum", r#"{"numbers": [1.5, 2.5, 3]}"#),
+    computed("square", r#"{"x": 12}"#),
+    computed("square_root", r#"{"x": 2}"#),
+    computed("power", r#"{"base": 2, "exponent": 10}"#),
+    computed(
+        "complex",
+        r#"{"op": "multiply", "a": "3+4i", "b": {"re": 1, "im": -2}}"#,
+    ),
+    computed("matrix", r#"{"op": "determinant", "a": [[1, 2], [3, 4]]}"#),
+    computed(
+        "matrix",
+        r#"{"op": "multiply", "a": [[1, 2], [3, 4]], "b": [[5], [6]]}"#,
+    ),
+    computed("solve", r#"{"degree": 2, "a": 1, "b": -3, "c": 2}"#),
+    computed("gcd", r#"{"numbers": [12, 18, 24]}"#),
+    computed("lcm", r#"{"numbers": [4, 6]}"#),
+    computed("is_prime", r#"{"n": 97}"#),
+    computed("bitwise", r#"{"op": "xor", "a": "0xff", "b": 15}"#),
+    computed("percentage_of", r#"{"percent": 15, "value": 80}"#),
+    computed("percent_change", r#"{"from": 50, "to": 65}"#),
+    computed("ratio_simplify", r#"{"a": 1920, "b": 1080}"#),
+    computed("clamp", r#"{"value": 15, "min": 0, "max": 10}"#),
+    computed("lerp", r#"{"a": 0, "b": 100, "t": 0.25}"#),
+    computed(
+        "map_range",
+        r#"{"value": 5, "in_min": 0, "in_max": 10, "out_min": 0, "out_max": 100}"#,
+    ),
+    computed("round", r#"{"value": 2.675, "digits": 2}"#),
+    computed("format_number", r#"{"value": 1234567.891, "decimals": 2}"#),
+    computed(
+        "compound_interest",
+        r#"{"principal": 1000, "rate": 5, "periods_per_year": 12, "years": 10}"#,
+    ),
+    computed(
+        "loan_payment",
+        r#"{"principal": 200000, "annual_rate": 6.5, "months": 360}"#,
+    ),
+    computed("duration_between", r#"{"start": 0, "end": 90061}"#),
+    computed(
+        "add_duration",
+        r#"{"timestamp": 0, "duration": {"days": 1, "hours": 2}}"#,
+    ),
+    computed(
+        "random_number",
+        r#"{"min": 1, "max": 6, "integer": true, "seed": 42}"#,
+    ),
+    fixed(
+        "accumulator_add",
+        r#"{"id": "groceries", "value": 12.5}"#,
+        "groceries: 12.5",
+    ),
+    fixed(
+        "accumulator_get",
+        r#"{"id": "groceries"}"#,
+        "groceries: 12.5",
+    ),
+    fixed(
+        "accumulator_reset",
+        r#"{"id": "groceries"}"#,
+        "groceries: 0 (was 12.5)",
+    ),
+];
+
+/// `_meta` for `tool`, with `run` producing the text output of computed
+/// examples. `None` when the tool has no usable examples.
+pub fn meta(tool: &str, run: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let examples: Vec<Value> = EXAMPLES
+        .iter()
+        .filter(|example| example.tool == tool)
+        .filter_map(|example| {
+            let output = match example.fixed_output {
+                Some(output) => output.to_string(),
+                None => run(example.input)?,
+            };
+            let input: Value = serde_json::from_str(example.input).ok()?;
+            Some(json!({ "input": input, "output": output }))
+        })
+        .collect();
+
+    if examples.is_empty() {
+        return None;
+    }
+    Some(json!({ "examples": examples }).to_string())
+}
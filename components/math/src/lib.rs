@@ -1,8 +1,29 @@
 //! Math Tools Capability Provider
 //!
 //! A tools capability that provides comprehensive mathematical operations including:
-//! - Basic arithmetic: add, subtract, multiply, divide
+//! - Basic arithmetic: add, subtract, multiply, divide, batch, sum
 //! - Advanced operations: square, square_root, power
+//! - Complex numbers: complex
+//! - Linear algebra: matrix
+//! - Equations: solve
+//! - Integer utilities: gcd, lcm, is_prime, bitwise
+//! - Percentages and ratios: percentage_of, percent_change, ratio_simplify
+//! - Ranges and interpolation: clamp, lerp, map_range
+//! - Rounding and formatting: round, format_number
+//! - Financial: compound_interest, loan_payment
+//! - Durations on Unix timestamps: duration_between, add_duration
+//! - Random numbers: random_number
+//! - Running totals: accumulator_add, accumulator_get, accumulator_reset
+//!
+//! Arguments are parsed by [`args`], which reports the JSON pointer and the
+//! received value for missing or mistyped parameters. Structured results and
+//! their advertised output schemas are defined together in [`output`].
+//! Long-running tools report progress through [`progress`], and arguments
+//! are checked against each tool's input schema by [`schema`] before dispatch.
+//! Which tools are exposed is configured by [`tool_filter`], and `list_tools`
+//! returns them in pages (see [`pagination`]), each with worked usage
+//! examples from [`examples`] in its `_meta`. The accumulator tools keep
+//! their totals in a host key-value store through [`accumulator`].
 
 mod bindings {
     wit_bindgen::generate!({
@@ -11,153 +32,62 @@ mod bindings {
     });
 }
 
+mod accumulator;
+mod args;
+mod examples;
+mod output;
+mod progress;
+mod schema;
+
+use args::{
+    Args, BatchItem, Bits, Complex, Integer, Operands, PowerArgs, ProgressToken, UnknownKeys,
+};
+use output::{
+    AccumulatorResult, AddDurationResult, Amortization, BatchEntry, BatchResult, BitwiseResult,
+    ComplexResult, CompoundInterestResult, DurationBetweenResult, DurationParts, FormulaResult,
+    IntegerResult, InterpolationResult, LoanPaymentResult, MatrixResult, NumberResult, Output,
+    PaymentSplit, PrimeResult, RandomNumberResult, RatioResult, Root, SolveResult,
+};
+
 use bindings::exports::wasmcp::protocol::tools::Guest;
-use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasi::io::streams::OutputStream;
+use bindings::wasmcp::protocol::mcp::*;
+use mcp_utils::annotations::ToolHints;
+use mcp_utils::{pagination, tool_filter};
+
+mcp_utils::result_builders!();
+use result_builder::error_result;
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
 
 struct Math;
 
 impl Guest for Math {
     fn list_tools(
         _ctx: bindings::wasmcp::protocol::server_messages::Context,
-        _request: ListToolsRequest,
+        request: ListToolsRequest,
         _client_stream: Option<&OutputStream>,
     ) -> Result<ListToolsResult, ErrorCode> {
+        let exposed: Vec<Tool> = tools()
+            .into_iter()
+            .filter(|tool| tool_filter::is_exposed(&tool.name))
+            .collect();
+        let (mut tools, next_cursor) =
+            pagination::paginate(exposed, request.cursor.as_deref(), TOOLS_PAGE_SIZE);
+
+        // Only the returned page is annotated, since computing the examples
+        // runs every tool once
+        for tool in &mut tools {
+            let meta = examples::meta(&tool.name, |input| example_output(tool, input));
+            if let Some(options) = tool.options.as_mut() {
+                options.meta = meta;
+            }
+        }
+
         Ok(ListToolsResult {
-            tools: vec![
-                Tool {
-                    name: "add".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "a": {"type": "number", "description": "First number"},
-                            "b": {"type": "number", "description": "Second number"}
-                        },
-                        "required": ["a", "b"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Add two numbers together".to_string()),
-                        output_schema: None,
-                        title: Some("Add".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "subtract".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "a": {"type": "number", "description": "Number to subtract from"},
-                            "b": {"type": "number", "description": "Number to subtract"}
-                        },
-                        "required": ["a", "b"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Subtract b from a".to_string()),
-                        output_schema: None,
-                        title: Some("Subtract".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "multiply".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "a": {"type": "number", "description": "First number"},
-                            "b": {"type": "number", "description": "Second number"}
-                        },
-                        "required": ["a", "b"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Multiply two numbers".to_string()),
-                        output_schema: None,
-                        title: Some("Multiply".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "divide".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "a": {"type": "number", "description": "Dividend"},
-                            "b": {"type": "number", "description": "Divisor"}
-                        },
-                        "required": ["a", "b"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Divide a by b".to_string()),
-                        output_schema: None,
-                        title: Some("Divide".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "square".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "x": {"type": "number", "description": "Number to square"}
-                        },
-                        "required": ["x"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Calculate the square of a number (x²)".to_string()),
-                        output_schema: None,
-                        title: Some("Square".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "square_root".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "x": {"type": "number", "description": "Number to take square root of"}
-                        },
-                        "required": ["x"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Calculate the square root of a number (√x)".to_string()),
-                        output_schema: None,
-                        title: Some("Square Root".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "power".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "base": {"type": "number", "description": "Base number"},
-                            "exponent": {"type": "number", "description": "Exponent"}
-                        },
-                        "required": ["base", "exponent"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Calculate base raised to exponent (base^exponent)".to_string()),
-                        output_schema: None,
-                        title: Some("Power".to_string()),
-                    }),
-                },
-            ],
-            next_cursor: None,
+            tools,
+            next_cursor,
             meta: None,
         })
     }
@@ -165,75 +95,791 @@ impl Guest for Math {
     fn call_tool(
         _ctx: bindings::wasmcp::protocol::server_messages::Context,
         request: CallToolRequest,
-        _client_stream: Option<&OutputStream>,
+        client_stream: Option<&OutputStream>,
     ) -> Option<CallToolResult> {
-        match request.name.as_str() {
-            "add" => Some(execute_operation(&request.arguments, |a, b| a + b)),
-            "subtract" => Some(execute_operation(&request.arguments, |a, b| a - b)),
-            "multiply" => Some(execute_operation(&request.arguments, |a, b| a * b)),
-            "divide" => Some(execute_divide(&request.arguments)),
-            "square" => Some(execute_square(&request.arguments)),
-            "square_root" => Some(execute_square_root(&request.arguments)),
-            "power" => Some(execute_power(&request.arguments)),
-            _ => None, // We don't handle this tool
-        }
+        let tool = tools()
+            .into_iter()
+            .find(|tool| tool.name == request.name && tool_filter::is_exposed(&tool.name))?;
+        run_tool(&tool, &request.arguments, client_stream)
     }
 }
 
-fn execute_operation<F>(arguments: &Option<String>, op: F) -> CallToolResult
-where
-    F: FnOnce(f64, f64) -> f64,
-{
-    match parse_args(arguments) {
-        Ok((a, b)) => {
-            let result = op(a, b);
-            success_result(result.to_string())
-        }
+/// Validate `arguments` against `tool` and run it, or `None` for a tool this
+/// component doesn't handle
+fn run_tool(
+    tool: &Tool,
+    arguments: &Option<String>,
+    client_stream: Option<&OutputStream>,
+) -> Option<CallToolResult> {
+    let arguments = match prepare_arguments(tool, arguments) {
+        Ok(arguments) => arguments,
+        Err(msg) => return Some(error_result(msg)),
+    };
+
+    // The only tool that writes to the client stream
+    if tool.name == "sum" {
+        return Some(execute_sum(&arguments, client_stream));
+    }
+
+    let execute: fn(&Option<String>) -> CallToolResult = match tool.name.as_str() {
+        "add" => |args| execute_arithmetic(args, "add"),
+        "subtract" => |args| execute_arithmetic(args, "subtract"),
+        "multiply" => |args| execute_arithmetic(args, "multiply"),
+        "divide" => |args| execute_arithmetic(args, "divide"),
+        "batch" => execute_batch,
+        "square" => execute_square,
+        "square_root" => execute_square_root,
+        "power" => execute_power,
+        "gcd" => execute_gcd,
+        "lcm" => execute_lcm,
+        "is_prime" => execute_is_prime,
+        "bitwise" => execute_bitwise,
+        "complex" => execute_complex,
+        "matrix" => execute_matrix,
+        "solve" => execute_solve,
+        "percentage_of" => execute_percentage_of,
+        "percent_change" => execute_percent_change,
+        "ratio_simplify" => execute_ratio_simplify,
+        "clamp" => execute_clamp,
+        "lerp" => execute_lerp,
+        "map_range" => execute_map_range,
+        "round" => execute_round,
+        "format_number" => execute_format_number,
+        "compound_interest" => execute_compound_interest,
+        "loan_payment" => execute_loan_payment,
+        "duration_between" => execute_duration_between,
+        "add_duration" => execute_add_duration,
+        "random_number" => execute_random_number,
+        "accumulator_add" => execute_accumulator_add,
+        "accumulator_get" => execute_accumulator_get,
+        "accumulator_reset" => execute_accumulator_reset,
+        _ => return None, // We don't handle this tool
+    };
+
+    Some(execute(&arguments))
+}
+
+/// Text output of running `tool` on an example input, `None` if it fails
+fn example_output(tool: &Tool, input: &str) -> Option<String> {
+    let result = run_tool(tool, &Some(input.to_string()), None)?;
+    if result.is_error == Some(true) {
+        return None;
+    }
+    result
+        .content
+        .into_iter()
+        .find_map(|content| match content {
+            ContentBlock::Text(TextContent {
+                text: TextData::Text(text),
+                ..
+            }) => Some(text),
+            _ => None,
+        })
+}
+
+/// Largest number of tools returned by one `list_tools` call
+const TOOLS_PAGE_SIZE: usize = pagination::DEFAULT_PAGE_SIZE;
+
+/// Every tool this component provides, as advertised by `list_tools`
+fn tools() -> Vec<Tool> {
+    vec![
+        Tool {
+            name: "add".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "a": {"type": "number", "description": "First number"},
+                    "b": {"type": "number", "description": "Second number"}
+                },
+                "required": ["a", "b"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Add", "Add two numbers together", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "subtract".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "a": {"type": "number", "description": "Number to subtract from"},
+                    "b": {"type": "number", "description": "Number to subtract"}
+                },
+                "required": ["a", "b"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Subtract", "Subtract b from a", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "multiply".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "a": {"type": "number", "description": "First number"},
+                    "b": {"type": "number", "description": "Second number"}
+                },
+                "required": ["a", "b"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Multiply", "Multiply two numbers", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "divide".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "a": {"type": "number", "description": "Dividend"},
+                    "b": {"type": "number", "description": "Divisor; must not be zero"}
+                },
+                "required": ["a", "b"]
+            }"#
+            .to_string(),
+            // Still annotated as safe: an error result has no side effects
+            options: Some(safe_tool_options("Divide", "Divide a by b. Returns an error result when b is zero or the quotient overflows", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "square".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "x": {"type": "number", "description": "Number to square"}
+                },
+                "required": ["x"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Square", "Calculate the square of a number (x²)", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "square_root".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "x": {"type": "number", "description": "Number to take square root of"}
+                },
+                "required": ["x"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Square Root", "Calculate the square root of a number (√x)", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "power".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "base": {"type": "number", "description": "Base number"},
+                    "exponent": {"type": "number", "description": "Exponent"}
+                },
+                "required": ["base", "exponent"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Power", "Calculate base raised to exponent (base^exponent)", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "complex".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "op": {"type": "string", "enum": ["add", "subtract", "multiply", "divide"]},
+                    "a": {"description": "First operand as {\"re\": 3, \"im\": 4}, or a string such as \"3+4i\"", "oneOf": [
+                        {"type": "object", "properties": {"re": {"type": "number"}, "im": {"type": "number"}}, "required": ["re", "im"]},
+                        {"type": "string"}
+                    ]},
+                    "b": {"description": "Second operand, in the same form as a", "oneOf": [
+                        {"type": "object", "properties": {"re": {"type": "number"}, "im": {"type": "number"}}, "required": ["re", "im"]},
+                        {"type": "string"}
+                    ]}
+                },
+                "required": ["op", "a", "b"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Complex", "Add, subtract, multiply or divide complex numbers. Pass each operand as an object {\"re\": 3, \"im\": 4} for 3+4i; a string such as \"3+4i\", \"-2i\" or \"5\" is also accepted. Returns the result with its magnitude and phase in radians. Dividing by 0+0i is an error", ComplexResult::SCHEMA)),
+        },
+        Tool {
+            name: "matrix".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "op": {"type": "string", "enum": ["multiply", "transpose", "determinant", "inverse"], "description": "Operation; determinant and inverse need a square matrix"},
+                    "a": {"type": "array", "minItems": 1, "maxItems": 10, "description": "Matrix as an array of rows, e.g. [[1, 2], [3, 4]]", "items": {
+                        "type": "array", "minItems": 1, "maxItems": 10, "items": {"type": "number"}
+                    }},
+                    "b": {"type": "array", "minItems": 1, "maxItems": 10, "description": "Right-hand matrix; only used by multiply", "items": {
+                        "type": "array", "minItems": 1, "maxItems": 10, "items": {"type": "number"}
+                    }}
+                },
+                "required": ["op", "a"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Matrix", "Multiply, transpose, or take the determinant or inverse of matrices of up to 10x10, given as arrays of rows such as [[1, 2], [3, 4]]. Multiplying needs as many columns in a as rows in b; inverting a singular matrix is an error", MatrixResult::SCHEMA)),
+        },
+        Tool {
+            name: "solve".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "degree": {"type": "integer", "enum": [1, 2], "description": "1 for ax + b = 0, 2 for ax^2 + bx + c = 0"},
+                    "a": {"type": "number", "description": "Coefficient of x (degree 1) or x^2 (degree 2)"},
+                    "b": {"type": "number", "description": "Constant term (degree 1) or coefficient of x (degree 2)"},
+                    "c": {"type": "number", "description": "Constant term; required for degree 2"}
+                },
+                "required": ["degree", "a", "b"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Solve", "Solve ax + b = 0 (degree 1) or ax^2 + bx + c = 0 (degree 2). Quadratics give two real roots, one double root, or two complex roots as {\"re\", \"im\"} when the discriminant is negative; a quadratic with a = 0 is solved as linear. Structured content includes the discriminant and root multiplicities", SolveResult::SCHEMA)),
+        },
+        Tool {
+            name: "gcd".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "numbers": {
+                        "type": "array",
+                        "items": {"type": "integer"},
+                        "minItems": 1,
+                        "description": "Integers to find the greatest common divisor of"
+                    }
+                },
+                "required": ["numbers"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Greatest Common Divisor", "Calculate the greatest common divisor of a list of integers", IntegerResult::SCHEMA)),
+        },
+        Tool {
+            name: "lcm".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "numbers": {
+                        "type": "array",
+                        "items": {"type": "integer"},
+                        "minItems": 1,
+                        "description": "Integers to find the least common multiple of"
+                    }
+                },
+                "required": ["numbers"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Least Common Multiple", "Calculate the least common multiple of a list of integers", IntegerResult::SCHEMA)),
+        },
+        Tool {
+            name: "is_prime".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "n": {"type": "integer", "minimum": 0, "description": "Integer to test (up to 2^64 - 1)"}
+                },
+                "required": ["n"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Is Prime", "Test whether an integer is prime, reporting its smallest factor when composite", PrimeResult::SCHEMA)),
+        },
+        Tool {
+            name: "bitwise".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "op": {"type": "string", "enum": ["and", "or", "xor", "not", "shl", "shr"], "description": "Operation; shl and shr shift a left or right by b bits"},
+                    "a": {"description": "64-bit integer, or hex string such as \"0xff\"", "oneOf": [
+                        {"type": "integer", "minimum": -9223372036854775808, "maximum": 9223372036854775807},
+                        {"type": "string"}
+                    ]},
+                    "b": {"description": "Second operand, or the shift amount (0-63); not used by not", "oneOf": [
+                        {"type": "integer", "minimum": -9223372036854775808, "maximum": 9223372036854775807},
+                        {"type": "string"}
+                    ]}
+                },
+                "required": ["op", "a"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Bitwise", "Bitwise and, or, xor, not and logical shifts on 64-bit integers given as decimals or hex strings (\"0xff\"). Returns the result in decimal, hex and binary", BitwiseResult::SCHEMA)),
+        },
+        Tool {
+            name: "percentage_of".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "percent": {"type": "number", "description": "The percentage to take, e.g. 15 for 15%"},
+                    "value": {"type": "number", "description": "The whole amount the percentage is taken of"}
+                },
+                "required": ["percent", "value"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Percentage Of", "Answer 'what is X% of Y?': returns percent / 100 * value (e.g. 15% of 80 = 12)", FormulaResult::SCHEMA)),
+        },
+        Tool {
+            name: "percent_change".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "from": {"type": "number", "description": "Original (old) value; must not be zero"},
+                    "to": {"type": "number", "description": "New value"}
+                },
+                "required": ["from", "to"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Percent Change", "Signed percentage change from an old value to a new one: (to - from) / |from| * 100 (e.g. 50 -> 75 = +50%, 80 -> 60 = -25%)", FormulaResult::SCHEMA)),
+        },
+        Tool {
+            name: "ratio_simplify".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "a": {"type": "number", "description": "First term of the ratio a:b"},
+                    "b": {"type": "number", "description": "Second term of the ratio a:b"},
+                    "precision": {"type": "integer", "minimum": 0, "maximum": 12, "description": "Decimal places kept when scaling fractional terms to integers (default 6)"}
+                },
+                "required": ["a", "b"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Simplify Ratio", "Reduce a ratio a:b to lowest integer terms (e.g. 12:18 -> 2:3, 0.5:1.25 -> 2:5)", RatioResult::SCHEMA)),
+        },
+        Tool {
+            name: "clamp".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "value": {"type": "number", "description": "Value to limit"},
+                    "min": {"type": "number", "description": "Lower bound"},
+                    "max": {"type": "number", "description": "Upper bound; must not be less than min"}
+                },
+                "required": ["value", "min", "max"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Clamp", "Limit a value to the range [min, max] (e.g. clamp 15 to [0, 10] = 10)", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "lerp".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "a": {"type": "number", "description": "Value at t = 0"},
+                    "b": {"type": "number", "description": "Value at t = 1"},
+                    "t": {"type": "number", "description": "Interpolation parameter; values outside [0, 1] extrapolate"}
+                },
+                "required": ["a", "b", "t"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Linear Interpolation", "Linear interpolation between a and b: a + (b - a) * t (e.g. a=10, b=20, t=0.25 gives 12.5). A t outside [0, 1] extrapolates, which the result notes", InterpolationResult::SCHEMA)),
+        },
+        Tool {
+            name: "map_range".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "value": {"type": "number", "description": "Value in the input range"},
+                    "in_min": {"type": "number", "description": "Start of the input range"},
+                    "in_max": {"type": "number", "description": "End of the input range; must differ from in_min"},
+                    "out_min": {"type": "number", "description": "Start of the output range"},
+                    "out_max": {"type": "number", "description": "End of the output range"}
+                },
+                "required": ["value", "in_min", "in_max", "out_min", "out_max"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Map Range", "Linearly map a value from [in_min, in_max] to [out_min, out_max] (e.g. 5 in [0, 10] maps to 50 in [0, 100]). Values outside the input range extrapolate, which the result notes", InterpolationResult::SCHEMA)),
+        },
+        Tool {
+            name: "round".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "value": {"type": "number", "description": "Number to round"},
+                    "digits": {"type": "integer", "minimum": -15, "maximum": 15, "description": "Decimal places to keep; negative values round to tens, hundreds, ... (default 0)"},
+                    "mode": {
+                        "type": "string",
+                        "enum": ["half_up", "half_even", "floor", "ceil", "truncate"],
+                        "description": "half_up: ties away from zero (default); half_even: ties to even (banker's); floor: toward -infinity; ceil: toward +infinity; truncate: toward zero"
+                    }
+                },
+                "required": ["value"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Round", "Round a number to a number of decimal places using an explicit rounding mode", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "format_number".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "value": {"type": "number", "description": "Number to format"},
+                    "decimals": {"type": "integer", "minimum": 0, "maximum": 15, "description": "Decimal places to show (default 2)"},
+                    "thousands_separator": {"type": "string", "description": "Digit group separator (default \",\")"},
+                    "decimal_separator": {"type": "string", "description": "Decimal point (default \".\")"}
+                },
+                "required": ["value"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Format Number", "Format a number with thousands separators, e.g. 1234567.891 -> \"1,234,567.89\"; separators can be changed for other locales (\"1.234.567,89\")", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "compound_interest".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "principal": {"type": "number", "minimum": 0, "description": "Initial amount invested or borrowed"},
+                    "rate": {"type": "number", "minimum": 0, "maximum": 1000, "description": "Annual interest rate as a percentage (e.g. 5 for 5%)"},
+                    "periods_per_year": {"type": "integer", "minimum": 1, "description": "Compounding periods per year (1 = yearly, 12 = monthly, 365 = daily)"},
+                    "years": {"type": "number", "minimum": 0, "description": "Investment duration in years"}
+                },
+                "required": ["principal", "rate", "periods_per_year", "years"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Compound Interest", "Final balance with compound interest: principal * (1 + rate / periods_per_year) ^ (periods_per_year * years). Text is rounded to 2 decimals; structured content keeps full precision and includes total interest", CompoundInterestResult::SCHEMA)),
+        },
+        Tool {
+            name: "loan_payment".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "principal": {"type": "number", "minimum": 0, "description": "Amount borrowed"},
+                    "annual_rate": {"type": "number", "minimum": 0, "maximum": 1000, "description": "Annual interest rate as a percentage (e.g. 6.5 for 6.5%)"},
+                    "months": {"type": "integer", "minimum": 1, "description": "Number of monthly payments"},
+                    "amortization": {"type": "boolean", "description": "Include the interest/principal split of the first and last payments (default false)"}
+                },
+                "required": ["principal", "annual_rate", "months"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Loan Payment", "Fixed monthly payment for an amortizing loan. Text is rounded to 2 decimals; structured content keeps full precision and includes total paid, total interest and an optional amortization summary", LoanPaymentResult::SCHEMA)),
+        },
+        Tool {
+            name: "duration_between".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "start": {"type": "integer", "description": "Start as a Unix timestamp in seconds"},
+                    "end": {"type": "integer", "description": "End as a Unix timestamp in seconds"}
+                },
+                "required": ["start", "end"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Duration Between", "Seconds from start to end (negative if end is earlier), with a days/hours/minutes/seconds breakdown such as \"3 days, 4 hours\"", DurationBetweenResult::SCHEMA)),
+        },
+        Tool {
+            name: "add_duration".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "timestamp": {"type": "integer", "description": "Unix timestamp in seconds"},
+                    "duration": {
+                        "type": "object",
+                        "description": "Duration to add; each unit defaults to 0 and may be negative",
+                        "properties": {
+                            "days": {"type": "integer"},
+                            "hours": {"type": "integer"},
+                            "minutes": {"type": "integer"},
+                            "seconds": {"type": "integer"}
+                        }
+                    }
+                },
+                "required": ["timestamp", "duration"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Add Duration", "Add a duration given as {\"days\", \"hours\", \"minutes\", \"seconds\"} to a Unix timestamp in seconds. Results outside the 64-bit range are an error", AddDurationResult::SCHEMA)),
+        },
+        Tool {
+            name: "random_number".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "min": {"type": "number", "description": "Lower bound (inclusive)"},
+                    "max": {"type": "number", "description": "Upper bound (inclusive for integers, exclusive for decimals)"},
+                    "integer": {"type": "boolean", "description": "Return whole numbers drawn uniformly from [min, max] (default false)"},
+                    "count": {"type": "integer", "minimum": 1, "maximum": 1000, "description": "How many values to return; when given, the result is an array"},
+                    "seed": {"type": "integer", "minimum": 0, "maximum": 18446744073709551615, "description": "Seed for reproducible output: the same seed and arguments always give the same numbers"}
+                },
+                "required": ["min", "max"]
+            }"#
+            .to_string(),
+            options: Some(ToolOptions {
+                // Each call draws new numbers unless a seed is given
+                annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                ..safe_tool_options("Random Number", "Generate uniformly distributed random numbers in a range. Uses a fast non-cryptographic PRNG seeded from the clock, or from 'seed' for reproducible tests; do not use the output for passwords, keys or tokens", RandomNumberResult::SCHEMA)
+            }),
+        },
+        Tool {
+            name: "batch".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "operations": {
+                        "type": "array",
+                        "minItems": 1,
+                        "maxItems": 100,
+                        "description": "Operations to run in order",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "op": {"type": "string", "enum": ["add", "subtract", "multiply", "divide"]},
+                                "a": {"description": "A number, or {\"$ref\": i} to use the result of item i", "oneOf": [
+                                    {"type": "number"},
+                                    {"type": "object", "properties": {"$ref": {"type": "integer", "minimum": 0}}, "required": ["$ref"]}
+                                ]},
+                                "b": {"description": "A number, or {\"$ref\": i} to use the result of item i", "oneOf": [
+                                    {"type": "number"},
+                                    {"type": "object", "properties": {"$ref": {"type": "integer", "minimum": 0}}, "required": ["$ref"]}
+                                ]}
+                            },
+                            "required": ["op", "a", "b"]
+                        }
+                    }
+                },
+                "required": ["operations"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Batch", "Run up to 100 add/subtract/multiply/divide operations in one call. An operand of {\"$ref\": i} uses the result of earlier item i, e.g. (2 + 3) * 4 is [{\"op\":\"add\",\"a\":2,\"b\":3}, {\"op\":\"multiply\",\"a\":{\"$ref\":0},\"b\":4}]. A failing item is reported in its own result without stopping the batch", BatchResult::SCHEMA)),
+        },
+        Tool {
+            name: "sum".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "numbers": {
+                        "type": "array",
+                        "items": {"type": "number"},
+                        "maxItems": 1000000,
+                        "description": "Numbers to add up"
+                    },
+                    "progress_token": {
+                        "type": ["string", "integer"],
+                        "description": "When given, progress notifications carrying this token are sent while summing"
+                    }
+                },
+                "required": ["numbers"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Sum", "Add up a large array of numbers (up to 1,000,000), reporting progress to the client as it goes", NumberResult::SCHEMA)),
+        },
+        Tool {
+            name: "accumulator_add".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "description": "Name of the accumulator; different ids keep independent totals"},
+                    "value": {"type": "number", "description": "Amount to add; negative to subtract"}
+                },
+                "required": ["id", "value"]
+            }"#
+            .to_string(),
+            options: Some(ToolOptions {
+                annotations: Some(build_annotations(ToolHints { read_only: false, idempotent: false, ..ToolHints::SAFE })),
+                ..safe_tool_options("Accumulator Add", "Add a number to a running total kept under 'id' and return the new total. Totals start at 0 and persist across calls and sessions in the host's wasi:keyvalue store, shared by every client of this server; the host must provide that store or the call returns an error", AccumulatorResult::SCHEMA)
+            }),
+        },
+        Tool {
+            name: "accumulator_get".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "description": "Name of the accumulator"}
+                },
+                "required": ["id"]
+            }"#
+            .to_string(),
+            options: Some(safe_tool_options("Accumulator Get", "Return the running total kept under 'id', or 0 if nothing was added to it. Reads the host's wasi:keyvalue store; the host must provide that store or the call returns an error", AccumulatorResult::SCHEMA)),
+        },
+        Tool {
+            name: "accumulator_reset".to_string(),
+            input_schema: r#"{
+                "type": "object",
+                "properties": {
+                    "id": {"type": "string", "description": "Name of the accumulator"}
+                },
+                "required": ["id"]
+            }"#
+            .to_string(),
+            options: Some(ToolOptions {
+                annotations: Some(build_annotations(ToolHints { read_only: false, destructive: true, ..ToolHints::SAFE })),
+                ..safe_tool_options("Accumulator Reset", "Reset the running total kept under 'id' to 0, returning the previous total. Removes it from the host's wasi:keyvalue store; the host must provide that store or the call returns an error", AccumulatorResult::SCHEMA)
+            }),
+        },
+    ]
+}
+
+/// Reject arguments that are not a JSON object matching the tool's input
+/// schema, and return them with quoted numbers such as `"3"` converted where
+/// the schema expects a number. Keys the schema does not declare are rejected
+/// too, unless it sets `"additionalProperties": true`.
+fn prepare_arguments(tool: &Tool, arguments: &Option<String>) -> Result<Option<String>, String> {
+    let args = Args::parse(arguments)?;
+
+    let schema: serde_json::Value = serde_json::from_str(&tool.input_schema)
+        .map_err(|e| format!("Invalid input schema for '{}': {}", tool.name, e))?;
+    let declared: Vec<&str> = schema
+        .get("properties")
+        .and_then(serde_json::Value::as_object)
+        .map(|properties| properties.keys().map(String::as_str).collect())
+        .unwrap_or_default();
+    let unknown = match schema.get("additionalProperties") {
+        Some(serde_json::Value::Bool(true)) => UnknownKeys::Ignore,
+        _ => UnknownKeys::Reject,
+    };
+    args.check_keys(&declared, unknown)?;
+    let mut value: serde_json::Value = arguments
+        .as_deref()
+        .and_then(|args| serde_json::from_str(args).ok())
+        .unwrap_or_default();
+    args::coerce_numeric_strings(&schema, &mut value);
+
+    schema::validate(&schema, &value).map_err(|msg| {
+        format!(
+            "Arguments for '{}' do not match its input schema at {}",
+            tool.name, msg
+        )
+    })?;
+    Ok(Some(value.to_string()))
+}
+
+/// Options for a read-only, idempotent, non-destructive tool
+fn safe_tool_options(title: &str, description: &str, output_schema: &str) -> ToolOptions {
+    ToolOptions {
+        meta: None,
+        annotations: Some(build_annotations(ToolHints::SAFE)),
+        description: Some(description.to_string()),
+        output_schema: Some(output_schema.to_string()),
+        title: Some(title.to_string()),
+    }
+}
+
+fn execute_arithmetic(arguments: &Option<String>, op: &str) -> CallToolResult {
+    match Operands::parse(arguments).and_then(|Operands { a, b }| arithmetic(op, a, b)) {
+        Ok(result) => number_result(result),
         Err(msg) => error_result(msg),
     }
 }
 
-fn execute_divide(arguments: &Option<String>) -> CallToolResult {
-    match parse_args(arguments) {
-        Ok((a, b)) => {
-            if b == 0.0 {
-                error_result("Error: Division by zero".to_string())
-            } else {
-                let result = a / b;
-                success_result(result.to_string())
-            }
+/// Two-operand arithmetic shared by the individual tools and `batch`
+fn arithmetic(op: &str, a: f64, b: f64) -> Result<f64, String> {
+    let result = match op {
+        "add" => a + b,
+        "subtract" => a - b,
+        "multiply" => a * b,
+        // Also true for -0.0, which would otherwise give -inf
+        "divide" if b == 0.0 => return Err("Error: Division by zero".to_string()),
+        // Tiny (subnormal) divisors can still overflow to infinity, caught below
+        "divide" => a / b,
+        _ => {
+            return Err(format!(
+                "Unsupported operation '{}': expected one of {}",
+                op,
+                ARITHMETIC_OPS.join(", ")
+            ))
         }
-        Err(msg) => error_result(msg),
+    };
+    finite_result(op, &[("a", a), ("b", b)], result)
+}
+
+/// Operations `batch` items may use
+const ARITHMETIC_OPS: [&str; 4] = ["add", "subtract", "multiply", "divide"];
+
+/// Largest number of operations one `batch` call may run
+const MAX_BATCH_SIZE: usize = 100;
+
+fn execute_batch(arguments: &Option<String>) -> CallToolResult {
+    let items: Vec<serde_json::Value> =
+        match Args::parse(arguments).and_then(|args| args.required("operations")) {
+            Ok(items) => items,
+            Err(msg) => return error_result(msg),
+        };
+    if items.is_empty() {
+        return error_result(
+            "Parameter 'operations' must contain at least one operation".to_string(),
+        );
+    }
+    if items.len() > MAX_BATCH_SIZE {
+        return error_result(format!(
+            "Batch has {} operations, which exceeds the maximum of {}",
+            items.len(),
+            MAX_BATCH_SIZE
+        ));
+    }
+
+    // Results so far, so that later items can reference earlier ones
+    let mut results: Vec<Result<f64, String>> = Vec::with_capacity(items.len());
+    for item in &items {
+        let result = BatchItem::parse(item).and_then(|item| {
+            let a = item.a.resolve("a", &results)?;
+            let b = item.b.resolve("b", &results)?;
+            arithmetic(&item.op, a, b)
+        });
+        results.push(result);
     }
+
+    let lines: Vec<String> = results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| match result {
+            Ok(value) => format!("{}: {}", index, value),
+            Err(msg) => format!("{}: failed: {}", index, msg),
+        })
+        .collect();
+    let failed = results.iter().filter(|r| r.is_err()).count();
+    let succeeded = results.len() - failed;
+    let entries = results
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| BatchEntry {
+            index,
+            result: result.as_ref().ok().copied(),
+            error: result.err(),
+        })
+        .collect();
+
+    structured_success_result(
+        lines.join("\n"),
+        &BatchResult {
+            results: entries,
+            succeeded,
+            failed,
+        },
+    )
 }
 
-fn parse_args(arguments: &Option<String>) -> Result<(f64, f64), String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
+/// Largest array `sum` accepts, and how many numbers are added between progress notifications
+const MAX_SUM_SIZE: usize = 1_000_000;
+const SUM_PROGRESS_INTERVAL: usize = 10_000;
 
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+fn execute_sum(arguments: &Option<String>, client_stream: Option<&OutputStream>) -> CallToolResult {
+    let args = match Args::parse(arguments) {
+        Ok(args) => args,
+        Err(msg) => return error_result(msg),
+    };
+    let (numbers, token) = match args
+        .required::<Vec<f64>>("numbers")
+        .and_then(|numbers| Ok((numbers, args.optional::<ProgressToken>("progress_token")?)))
+    {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+    if numbers.len() > MAX_SUM_SIZE {
+        return error_result(format!(
+            "Parameter 'numbers' has {} items, which exceeds the maximum of {}",
+            numbers.len(),
+            MAX_SUM_SIZE
+        ));
+    }
 
-    let a = json
-        .get("a")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'a'".to_string())?;
+    let total = numbers.len() as u64;
 
-    let b = json
-        .get("b")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'b'".to_string())?;
+    let mut sum = 0.0;
+    for (chunk_index, chunk) in numbers.chunks(SUM_PROGRESS_INTERVAL).enumerate() {
+        sum += chunk.iter().sum::<f64>();
+        // Progress notifications require a token to tie them to this call
+        if let Some(token) = &token {
+            let done = (chunk_index * SUM_PROGRESS_INTERVAL + chunk.len()) as u64;
+            progress::notify_progress(client_stream, token, done, total);
+        }
+    }
 
-    Ok((a, b))
+    match finite_result("sum", &[("count", total as f64)], sum) {
+        Ok(result) => number_result(result),
+        Err(msg) => error_result(msg),
+    }
 }
 
 fn execute_square(arguments: &Option<String>) -> CallToolResult {
     match parse_single_arg(arguments, "x") {
-        Ok(x) => {
-            let result = x * x;
-            success_result(result.to_string())
-        }
+        Ok(x) => match finite_result("square", &[("x", x)], x * x) {
+            Ok(result) => number_result(result),
+            Err(msg) => error_result(msg),
+        },
         Err(msg) => error_result(msg),
     }
 }
@@ -245,7 +891,7 @@ fn execute_square_root(arguments: &Option<String>) -> CallToolResult {
                 error_result("Error: Cannot take square root of negative number".to_string())
             } else {
                 let result = x.sqrt();
-                success_result(result.to_string())
+                number_result(result)
             }
         }
         Err(msg) => error_result(msg),
@@ -253,74 +899,2171 @@ fn execute_square_root(arguments: &Option<String>) -> CallToolResult {
 }
 
 fn execute_power(arguments: &Option<String>) -> CallToolResult {
-    match parse_power_args(arguments) {
-        Ok((base, exponent)) => {
-            let result = base.powf(exponent);
-            success_result(result.to_string())
+    match PowerArgs::parse(arguments) {
+        Ok(PowerArgs { base, exponent }) => {
+            let operands = [("base", base), ("exponent", exponent)];
+            match finite_result("power", &operands, base.powf(exponent)) {
+                Ok(result) => number_result(result),
+                Err(msg) => error_result(msg),
+            }
         }
         Err(msg) => error_result(msg),
     }
 }
 
 fn parse_single_arg(arguments: &Option<String>, arg_name: &str) -> Result<f64, String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
+    Args::parse(arguments)?.required(arg_name)
+}
+
+fn execute_gcd(arguments: &Option<String>) -> CallToolResult {
+    match parse_integers(arguments, "numbers") {
+        Ok(numbers) => {
+            let result = numbers
+                .iter()
+                .fold(0u128, |acc, n| gcd(acc, n.unsigned_abs()));
+            structured_success_result(result.to_string(), &IntegerResult { result })
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_lcm(arguments: &Option<String>) -> CallToolResult {
+    match parse_integers(arguments, "numbers") {
+        Ok(numbers) => {
+            let mut result: u128 = 1;
+            for n in numbers.iter().map(|n| n.unsigned_abs()) {
+                if n == 0 {
+                    return structured_success_result(
+                        "0".to_string(),
+                        &IntegerResult { result: 0 },
+                    );
+                }
+                // lcm(a, b) = a / gcd(a, b) * b, dividing first to delay overflow
+                match (result / gcd(result, n)).checked_mul(n) {
+                    Some(value) => result = value,
+                    None => {
+                        return error_result(
+                            "Error: LCM exceeds the 128-bit integer range".to_string(),
+                        )
+                    }
+                }
+            }
+            structured_success_result(result.to_string(), &IntegerResult { result })
+        }
+        Err(msg) => error_result(msg),
+    }
+}
 
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+fn execute_is_prime(arguments: &Option<String>) -> CallToolResult {
+    let n = match parse_integer_arg(arguments, "n") {
+        Ok(n) => n,
+        Err(msg) => return error_result(msg),
+    };
 
-    let value = json
-        .get(arg_name)
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| format!("Missing or invalid parameter '{}'", arg_name))?;
+    if n < 0 {
+        return error_result("Error: Parameter 'n' must be non-negative".to_string());
+    }
+    let n = match u64::try_from(n) {
+        Ok(n) => n,
+        Err(_) => {
+            return error_result(
+                "Error: Parameter 'n' must fit in a 64-bit unsigned integer".to_string(),
+            )
+        }
+    };
 
-    Ok(value)
+    if is_prime(n) {
+        structured_success_result(
+            format!("{} is prime", n),
+            &PrimeResult {
+                n,
+                prime: true,
+                smallest_factor: None,
+            },
+        )
+    } else {
+        match smallest_factor(n) {
+            Some(factor) => structured_success_result(
+                format!("{} is not prime (smallest factor: {})", n, factor),
+                &PrimeResult {
+                    n,
+                    prime: false,
+                    smallest_factor: Some(factor),
+                },
+            ),
+            None => structured_success_result(
+                format!("{} is not prime", n),
+                &PrimeResult {
+                    n,
+                    prime: false,
+                    smallest_factor: None,
+                },
+            ),
+        }
+    }
+}
+
+/// Largest shift `bitwise` accepts; shifting a 64-bit value further is an error, not a wrap
+const MAX_SHIFT: u64 = 63;
+
+fn execute_bitwise(arguments: &Option<String>) -> CallToolResult {
+    let parsed = Args::parse(arguments).and_then(|args| {
+        let op: String = args.required("op")?;
+        let a: Bits = args.required("a")?;
+        let b: Option<Bits> = args.optional("b")?;
+        Ok((op, a.0, b.map(|b| b.0)))
+    });
+    let (op, a, b) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    let result = match (op.as_str(), b) {
+        ("not", _) => !a,
+        ("and" | "or" | "xor" | "shl" | "shr", None) => {
+            return error_result(format!(
+                "Missing parameter 'b' at /b (required by '{}')",
+                op
+            ))
+        }
+        ("shl" | "shr", Some(shift)) if shift > MAX_SHIFT => {
+            return error_result(format!(
+                "Error: Shift amount 'b' must be between 0 and {}, got {}",
+                MAX_SHIFT, shift as i64
+            ))
+        }
+        ("and", Some(b)) => a & b,
+        ("or", Some(b)) => a | b,
+        ("xor", Some(b)) => a ^ b,
+        ("shl", Some(shift)) => a << shift,
+        ("shr", Some(shift)) => a >> shift,
+        _ => {
+            return error_result(format!(
+                "Unsupported operation '{}': expected one of and, or, xor, not, shl, shr",
+                op
+            ))
+        }
+    };
+
+    let structured = BitwiseResult {
+        result: result as i64,
+        unsigned: result,
+        hex: format!("{:#x}", result),
+        binary: format!("{:#b}", result),
+    };
+    structured_success_result(
+        format!(
+            "{} ({}, {})",
+            structured.result, structured.hex, structured.binary
+        ),
+        &structured,
+    )
 }
 
-fn parse_power_args(arguments: &Option<String>) -> Result<(f64, f64), String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
+fn execute_complex(arguments: &Option<String>) -> CallToolResult {
+    let parsed = Args::parse(arguments).and_then(|args| {
+        let op: String = args.required("op")?;
+        let a: Complex = args.required("a")?;
+        let b: Complex = args.required("b")?;
+        Ok((op, a, b))
+    });
+    let (op, a, b) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
 
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+    let (re, im) = match op.as_str() {
+        "add" => (a.re + b.re, a.im + b.im),
+        "subtract" => (a.re - b.re, a.im - b.im),
+        "multiply" => (a.re * b.re - a.im * b.im, a.re * b.im + a.im * b.re),
+        "divide" if b.re == 0.0 && b.im == 0.0 => {
+            return error_result("Error: Division by complex zero".to_string())
+        }
+        "divide" => {
+            let denominator = b.re * b.re + b.im * b.im;
+            (
+                (a.re * b.re + a.im * b.im) / denominator,
+                (a.im * b.re - a.re * b.im) / denominator,
+            )
+        }
+        _ => {
+            return error_result(format!(
+                "Unsupported operation '{}': expected one of {}",
+                op,
+                ARITHMETIC_OPS.join(", ")
+            ))
+        }
+    };
 
-    let base = json
-        .get("base")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'base'".to_string())?;
+    let operands = [
+        ("a.re", a.re),
+        ("a.im", a.im),
+        ("b.re", b.re),
+        ("b.im", b.im),
+    ];
+    let (re, im) = match (
+        finite_result(&op, &operands, re),
+        finite_result(&op, &operands, im),
+    ) {
+        (Ok(re), Ok(im)) => (re, im),
+        (Err(msg), _) | (_, Err(msg)) => return error_result(msg),
+    };
 
-    let exponent = json
-        .get("exponent")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'exponent'".to_string())?;
+    let structured = ComplexResult {
+        re,
+        im,
+        magnitude: re.hypot(im),
+        phase: im.atan2(re),
+    };
+    structured_success_result(format_complex(re, im), &structured)
+}
 
-    Ok((base, exponent))
+/// `a+bi` or `a-bi`, in the form the `complex` tool accepts as input
+fn format_complex(re: f64, im: f64) -> String {
+    if im.is_sign_negative() {
+        format!("{}-{}i", re, -im)
+    } else {
+        format!("{}+{}i", re, im)
+    }
 }
 
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
+/// Largest number of rows or columns of a `matrix` operand
+const MAX_MATRIX_DIM: usize = 10;
+
+/// Pivots smaller than this, relative to the largest entry, count as zero
+/// when inverting
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
+type Matrix = Vec<Vec<f64>>;
+
+fn execute_matrix(arguments: &Option<String>) -> CallToolResult {
+    let parsed = Args::parse(arguments).and_then(|args| {
+        let op: String = args.required("op")?;
+        let a: Matrix = args.required("a")?;
+        let b: Option<Matrix> = args.optional("b")?;
+        Ok((op, a, b))
+    });
+    let (op, a, b) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    let result = matrix_shape("a", &a).and_then(|shape_a| match (op.as_str(), &b) {
+        ("multiply", None) => {
+            Err("Missing parameter 'b' at /b (required by 'multiply')".to_string())
+        }
+        ("multiply", Some(b)) => {
+            let shape_b = matrix_shape("b", b)?;
+            matrix_multiply(&a, shape_a, b, shape_b).map(MatrixValue::Matrix)
+        }
+        ("transpose", _) => Ok(MatrixValue::Matrix(transpose(&a, shape_a))),
+        ("determinant", _) => {
+            square_size(&op, shape_a).map(|_| MatrixValue::Number(determinant(&a)))
+        }
+        ("inverse", _) => square_size(&op, shape_a)
+            .and_then(|_| inverse(&a))
+            .map(MatrixValue::Matrix),
+        _ => Err(format!(
+            "Unsupported operation '{}': expected one of multiply, transpose, determinant, inverse",
+            op
+        )),
+    });
+
+    match result {
+        Ok(MatrixValue::Number(value)) if value.is_finite() => structured_success_result(
+            value.to_string(),
+            &MatrixResult {
+                result: value.into(),
+            },
+        ),
+        Ok(MatrixValue::Matrix(matrix))
+            if matrix.iter().flatten().all(|value| value.is_finite()) =>
+        {
+            structured_success_result(
+                format_matrix(&matrix),
+                &MatrixResult {
+                    result: serde_json::json!(matrix),
+                },
+            )
+        }
+        Ok(_) => error_result(format!(
+            "Error: {} overflows to infinity or is not a number (NaN)",
+            op
+        )),
+        Err(msg) => error_result(msg),
     }
 }
 
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
+enum MatrixValue {
+    Number(f64),
+    Matrix(Matrix),
+}
+
+/// Rows and columns of a rectangular matrix of at most `MAX_MATRIX_DIM` in
+/// each direction, which also caps it at 100 elements
+fn matrix_shape(name: &str, matrix: &Matrix) -> Result<(usize, usize), String> {
+    let rows = matrix.len();
+    let cols = matrix.first().map_or(0, Vec::len);
+    if rows == 0 || cols == 0 {
+        return Err(format!("Error: Matrix '{}' is empty", name));
     }
+    if rows > MAX_MATRIX_DIM || cols > MAX_MATRIX_DIM {
+        return Err(format!(
+            "Error: Matrix '{}' is {}x{}, larger than the maximum of {}x{}",
+            name, rows, cols, MAX_MATRIX_DIM, MAX_MATRIX_DIM
+        ));
+    }
+    if let Some((i, row)) = matrix.iter().enumerate().find(|(_, row)| row.len() != cols) {
+        return Err(format!(
+            "Error: Matrix '{}' is not rectangular: row {} has {} columns, but row 0 has {}",
+            name,
+            i,
+            row.len(),
+            cols
+        ));
+    }
+    Ok((rows, cols))
 }
 
-bindings::export!(Math with_types_in bindings);
+fn square_size(op: &str, (rows, cols): (usize, usize)) -> Result<usize, String> {
+    if rows != cols {
+        return Err(format!(
+            "Error: Cannot take the {} of a {}x{} matrix: it must be square",
+            op, rows, cols
+        ));
+    }
+    Ok(rows)
+}
+
+fn matrix_multiply(
+    a: &Matrix,
+    (rows_a, cols_a): (usize, usize),
+    b: &Matrix,
+    (rows_b, cols_b): (usize, usize),
+) -> Result<Matrix, String> {
+    if cols_a != rows_b {
+        return Err(format!(
+            "Error: Cannot multiply {}x{} by {}x{}: a needs as many columns as b has rows",
+            rows_a, cols_a, rows_b, cols_b
+        ));
+    }
+    Ok((0..rows_a)
+        .map(|i| {
+            (0..cols_b)
+                .map(|j| (0..cols_a).map(|k| a[i][k] * b[k][j]).sum())
+                .collect()
+        })
+        .collect())
+}
+
+fn transpose(matrix: &Matrix, (rows, cols): (usize, usize)) -> Matrix {
+    (0..cols)
+        .map(|j| (0..rows).map(|i| matrix[i][j]).collect())
+        .collect()
+}
+
+/// LU decomposition with partial pivoting: the combined L and U factors
+/// (L's unit diagonal implied), the row permutation, and the sign of that
+/// permutation
+fn lu_decompose(matrix: &Matrix) -> (Matrix, Vec<usize>, f64) {
+    let n = matrix.len();
+    let mut lu = matrix.clone();
+    let mut permutation: Vec<usize> = (0..n).collect();
+    let mut sign = 1.0;
+
+    for k in 0..n {
+        // Largest remaining entry in column k as the pivot
+        let pivot = (k..n)
+            .max_by(|&i, &j| lu[i][k].abs().total_cmp(&lu[j][k].abs()))
+            .unwrap_or(k);
+        if pivot != k {
+            lu.swap(pivot, k);
+            permutation.swap(pivot, k);
+            sign = -sign;
+        }
+        if lu[k][k] == 0.0 {
+            continue;
+        }
+        let (upper, lower) = lu.split_at_mut(k + 1);
+        let pivot_row = &upper[k];
+        for row in lower {
+            let factor = row[k] / pivot_row[k];
+            row[k] = factor;
+            for (value, pivot_value) in row[k + 1..].iter_mut().zip(&pivot_row[k + 1..]) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+
+    (lu, permutation, sign)
+}
+
+fn determinant(matrix: &Matrix) -> f64 {
+    let (lu, _, sign) = lu_decompose(matrix);
+    lu_determinant(&lu, sign)
+}
+
+/// Product of U's diagonal; adding 0.0 turns a -0 result into 0
+fn lu_determinant(lu: &Matrix, sign: f64) -> f64 {
+    sign * (0..lu.len()).map(|i| lu[i][i]).product::<f64>() + 0.0
+}
+
+/// Inverse by solving LU x = e_j for each column j of the identity
+fn inverse(matrix: &Matrix) -> Result<Matrix, String> {
+    let n = matrix.len();
+    let (lu, permutation, sign) = lu_decompose(matrix);
+
+    let scale = matrix
+        .iter()
+        .flatten()
+        .fold(0.0_f64, |max, value| max.max(value.abs()));
+    if (0..n).any(|i| lu[i][i].abs() <= SINGULAR_TOLERANCE * scale) {
+        return Err(format!(
+            "Error: Cannot invert a singular matrix (determinant {})",
+            lu_determinant(&lu, sign)
+        ));
+    }
+
+    let columns: Matrix = (0..n)
+        .map(|j| {
+            // Forward substitution with the unit lower triangle, on the permuted e_j
+            let mut x: Vec<f64> = permutation
+                .iter()
+                .map(|&row| if row == j { 1.0 } else { 0.0 })
+                .collect();
+            for i in 0..n {
+                x[i] -= (0..i).map(|k| lu[i][k] * x[k]).sum::<f64>();
+            }
+            // Back substitution with the upper triangle
+            for i in (0..n).rev() {
+                x[i] = (x[i] - (i + 1..n).map(|k| lu[i][k] * x[k]).sum::<f64>()) / lu[i][i];
+            }
+            x
+        })
+        .collect();
+    Ok(transpose(&columns, (n, n)))
+}
+
+/// One bracketed row per line, with columns right-aligned
+fn format_matrix(matrix: &Matrix) -> String {
+    let cells: Vec<Vec<String>> = matrix
+        .iter()
+        .map(|row| row.iter().map(|value| value.to_string()).collect())
+        .collect();
+    let cols = cells.first().map_or(0, Vec::len);
+    let widths: Vec<usize> = (0..cols)
+        .map(|j| cells.iter().map(|row| row[j].len()).max().unwrap_or(0))
+        .collect();
+
+    cells
+        .iter()
+        .map(|row| {
+            let padded: Vec<String> = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+                .collect();
+            format!("[ {} ]", padded.join("  "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn execute_solve(arguments: &Option<String>) -> CallToolResult {
+    let parsed = Args::parse(arguments).and_then(|args| {
+        let Integer(degree) = args.required("degree")?;
+        let a: f64 = args.required("a")?;
+        let b: f64 = args.required("b")?;
+        let c: Option<f64> = args.optional("c")?;
+        match (degree, c) {
+            (1, _) => Ok((0.0, a, b)),
+            (2, Some(c)) => Ok((a, b, c)),
+            (2, None) => Err("Missing parameter 'c' at /c (required by degree 2)".to_string()),
+            _ => Err(format!("Unsupported degree {}: expected 1 or 2", degree)),
+        }
+    });
+    // Every equation is handled as ax^2 + bx + c = 0, with a = 0 for linear ones
+    let (a, b, c) = match parsed {
+        Ok(coefficients) => coefficients,
+        Err(msg) => return error_result(msg),
+    };
+
+    let solved = if a != 0.0 {
+        solve_quadratic(a, b, c)
+    } else if b != 0.0 {
+        let root = -c / b;
+        Ok(SolveResult {
+            degree: 1,
+            discriminant: None,
+            // Adding 0.0 turns -0 into 0
+            roots: vec![Root::Real(root + 0.0)],
+            multiplicities: vec![1],
+        })
+    } else {
+        return error_result(format!(
+            "Error: No equation to solve: every coefficient of x is zero, leaving {} = 0",
+            c
+        ));
+    };
+
+    match solved {
+        Ok(solution) if solution.roots.iter().all(root_is_finite) => {
+            structured_success_result(format_roots(&solution), &solution)
+        }
+        Ok(_) => error_result(format!(
+            "Error: The roots of {}x^2 + {}x + {} = 0 overflow to infinity",
+            a, b, c
+        )),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn solve_quadratic(a: f64, b: f64, c: f64) -> Result<SolveResult, String> {
+    let discriminant = finite_result(
+        "discriminant",
+        &[("a", a), ("b", b), ("c", c)],
+        b * b - 4.0 * a * c,
+    )?;
+
+    let (roots, multiplicities) = if discriminant > 0.0 {
+        // q avoids subtracting nearly equal numbers when b^2 dwarfs 4ac, and
+        // is never zero since the discriminant is positive
+        let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+        let (x1, x2) = (q / a, c / q);
+        (
+            vec![Root::Real(x1.min(x2)), Root::Real(x1.max(x2))],
+            vec![1, 1],
+        )
+    } else if discriminant == 0.0 {
+        (vec![Root::Real(-b / (2.0 * a) + 0.0)], vec![2])
+    } else {
+        let re = -b / (2.0 * a) + 0.0;
+        let im = (-discriminant).sqrt() / (2.0 * a).abs();
+        (
+            vec![Root::Complex { re, im }, Root::Complex { re, im: -im }],
+            vec![1, 1],
+        )
+    };
+
+    Ok(SolveResult {
+        degree: 2,
+        discriminant: Some(discriminant),
+        roots,
+        multiplicities,
+    })
+}
+
+fn root_is_finite(root: &Root) -> bool {
+    match *root {
+        Root::Real(x) => x.is_finite(),
+        Root::Complex { re, im } => re.is_finite() && im.is_finite(),
+    }
+}
+
+/// "x = 2, x = 3", "x = 2 (double root)" or "x = -1+2i, x = -1-2i"
+fn format_roots(solution: &SolveResult) -> String {
+    let roots: Vec<String> = solution
+        .roots
+        .iter()
+        .map(|root| match *root {
+            Root::Real(x) => format!("x = {}", x),
+            Root::Complex { re, im } => format!("x = {}", format_complex(re, im)),
+        })
+        .collect();
+    if solution.multiplicities == [2] {
+        format!("{} (double root)", roots.join(", "))
+    } else {
+        roots.join(", ")
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Deterministic Miller-Rabin: these witnesses are sufficient for all n < 2^64
+fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    for &p in &WITNESSES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let mut d = n - 1;
+    let mut r = 0;
+    while d.is_multiple_of(2) {
+        d /= 2;
+        r += 1;
+    }
+
+    'witness: for &a in &WITNESSES {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            continue;
+        }
+        for _ in 1..r {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Smallest prime factor of a composite n, or None for 0 and 1
+fn smallest_factor(n: u64) -> Option<u64> {
+    if n < 2 {
+        return None;
+    }
+    // Trial division handles small factors cheaply
+    let mut p = 2;
+    while p < 1000 {
+        if p * p > n {
+            return Some(n);
+        }
+        if n.is_multiple_of(p) {
+            return Some(p);
+        }
+        p += 1;
+    }
+    // Remaining factors are all >= 1000: split with Pollard's rho
+    let mut stack = vec![n];
+    let mut smallest = n;
+    while let Some(m) = stack.pop() {
+        if is_prime(m) {
+            smallest = smallest.min(m);
+        } else {
+            let d = pollard_rho(m);
+            stack.push(d);
+            stack.push(m / d);
+        }
+    }
+    Some(smallest)
+}
+
+/// Find a non-trivial divisor of an odd composite n
+fn pollard_rho(n: u64) -> u64 {
+    let mut c = 1;
+    loop {
+        let f = |x: u64| rho_step(x, c, n);
+        let (mut x, mut y, mut d) = (2, 2, 1);
+        while d == 1 {
+            x = f(x);
+            y = f(f(y));
+            d = gcd(x.abs_diff(y) as u128, n as u128) as u64;
+        }
+        if d != n {
+            return d;
+        }
+        c += 1;
+    }
+}
+
+/// x² + c mod n, in u128 since x² mod n + c can exceed u64::MAX when n is
+/// close to it
+fn rho_step(x: u64, c: u64, n: u64) -> u64 {
+    ((mul_mod(x, x, n) as u128 + c as u128) % n as u128) as u64
+}
+
+fn execute_percentage_of(arguments: &Option<String>) -> CallToolResult {
+    match parse_number_args(arguments, ["percent", "value"]) {
+        Ok([percent, value]) => {
+            let operands = [("percent", percent), ("value", value)];
+            let result = match finite_result("percentage_of", &operands, percent / 100.0 * value) {
+                Ok(result) => result,
+                Err(msg) => return error_result(msg),
+            };
+            structured_success_result(
+                result.to_string(),
+                &FormulaResult {
+                    result,
+                    formula: format!("{} / 100 * {}", percent, value),
+                },
+            )
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_percent_change(arguments: &Option<String>) -> CallToolResult {
+    match parse_number_args(arguments, ["from", "to"]) {
+        Ok([from, to]) => {
+            if from == 0.0 {
+                return error_result("Error: Percent change from zero is undefined".to_string());
+            }
+            let change = (to - from) / from.abs() * 100.0;
+            let result =
+                match finite_result("percent_change", &[("from", from), ("to", to)], change) {
+                    Ok(result) => result,
+                    Err(msg) => return error_result(msg),
+                };
+            structured_success_result(
+                format!("{}%", result),
+                &FormulaResult {
+                    result,
+                    formula: format!("({} - {}) / |{}| * 100", to, from, from),
+                },
+            )
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+const DEFAULT_RATIO_PRECISION: u32 = 6;
+const MAX_RATIO_PRECISION: u32 = 12;
+
+fn execute_ratio_simplify(arguments: &Option<String>) -> CallToolResult {
+    let [a, b] = match parse_number_args(arguments, ["a", "b"]) {
+        Ok(values) => values,
+        Err(msg) => return error_result(msg),
+    };
+    let precision = match parse_optional_integer_arg(arguments, "precision") {
+        Ok(None) => DEFAULT_RATIO_PRECISION,
+        Ok(Some(p)) if (0..=MAX_RATIO_PRECISION as i128).contains(&p) => p as u32,
+        Ok(Some(_)) => {
+            return error_result(format!(
+                "Parameter 'precision' must be between 0 and {}",
+                MAX_RATIO_PRECISION
+            ))
+        }
+        Err(msg) => return error_result(msg),
+    };
+
+    if a == 0.0 && b == 0.0 {
+        return error_result("Error: Ratio 0:0 cannot be simplified".to_string());
+    }
+
+    // Scale both terms to integers over a common power-of-ten denominator
+    let scale = 10f64.powi(precision as i32);
+    let (scaled_a, scaled_b) = ((a * scale).round(), (b * scale).round());
+    if scaled_a.abs() >= 1e30 || scaled_b.abs() >= 1e30 {
+        return error_result("Error: Ratio terms are too large at this precision".to_string());
+    }
+    let (int_a, int_b) = (scaled_a as i128, scaled_b as i128);
+
+    let divisor = gcd(int_a.unsigned_abs(), int_b.unsigned_abs()) as i128;
+    let (simple_a, simple_b) = (int_a / divisor, int_b / divisor);
+
+    structured_success_result(
+        format!("{}:{}", simple_a, simple_b),
+        &RatioResult {
+            a: simple_a as f64,
+            b: simple_b as f64,
+            formula: format!(
+                "({} * 10^{}) : ({} * 10^{}), divided by their gcd {}",
+                a, precision, b, precision, divisor
+            ),
+        },
+    )
+}
+
+#[derive(Clone, Copy)]
+enum RoundingMode {
+    HalfUp,
+    HalfEven,
+    Floor,
+    Ceil,
+    Truncate,
+}
+
+impl RoundingMode {
+    fn parse(mode: Option<&str>) -> Result<Self, String> {
+        match mode {
+            None | Some("half_up") => Ok(RoundingMode::HalfUp),
+            Some("half_even") => Ok(RoundingMode::HalfEven),
+            Some("floor") => Ok(RoundingMode::Floor),
+            Some("ceil") => Ok(RoundingMode::Ceil),
+            Some("truncate") => Ok(RoundingMode::Truncate),
+            Some(other) => Err(format!(
+                "Invalid mode '{}': expected one of half_up, half_even, floor, ceil, truncate",
+                other
+            )),
+        }
+    }
+}
+
+/// Round `value` to `digits` decimal places.
+///
+/// Rounding works on the shortest decimal form of `value`, the digits it is
+/// printed with, so inputs like 2.675 (stored as 2.67499999...) are ties as
+/// written while values that are already short enough are left untouched.
+fn round_with_mode(value: f64, digits: i32, mode: RoundingMode) -> f64 {
+    use std::cmp::Ordering;
+
+    if !value.is_finite() {
+        return value;
+    }
+
+    // `{:e}` gives the shortest digits that round-trip: d.ddd...e<exponent>
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let significand: Vec<u8> = mantissa
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .map(|b| b - b'0')
+        .collect();
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+
+    // Significand digits that stay once rounded to `digits` places
+    let kept_len = exponent + 1 + digits;
+    if kept_len >= significand.len() as i32 {
+        return value;
+    }
+    let (kept, rest) = if kept_len > 0 {
+        significand.split_at(kept_len as usize)
+    } else {
+        (&[][..], &significand[..])
+    };
+
+    let kept = kept.iter().fold(0u128, |n, &d| n * 10 + d as u128);
+    let nonzero = rest.iter().any(|&d| d != 0);
+    // A kept length below zero puts zeros ahead of `rest`, below one half
+    let half = if kept_len < 0 || rest[0] < 5 {
+        Ordering::Less
+    } else if rest[0] > 5 || rest[1..].iter().any(|&d| d != 0) {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    };
+    let negative = value < 0.0;
+
+    let round_up = match mode {
+        RoundingMode::Truncate => false,
+        RoundingMode::Floor => negative && nonzero,
+        RoundingMode::Ceil => !negative && nonzero,
+        // Ties away from zero
+        RoundingMode::HalfUp => half != Ordering::Less,
+        RoundingMode::HalfEven => {
+            half == Ordering::Greater || (half == Ordering::Equal && kept % 2 == 1)
+        }
+    };
+    let magnitude = kept + round_up as u128;
+
+    let sign = if negative { "-" } else { "" };
+    format!("{}{}e{}", sign, magnitude, -digits)
+        .parse()
+        .unwrap_or(value)
+}
+
+fn execute_clamp(arguments: &Option<String>) -> CallToolResult {
+    match parse_number_args(arguments, ["value", "min", "max"]) {
+        Ok([_, min, max]) if min > max => error_result(format!(
+            "Error: min ({}) must not be greater than max ({})",
+            min, max
+        )),
+        Ok([value, min, max]) => number_result(value.clamp(min, max)),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_lerp(arguments: &Option<String>) -> CallToolResult {
+    match parse_number_args(arguments, ["a", "b", "t"]) {
+        Ok([a, b, t]) => {
+            // This form returns a and b exactly at t = 0 and t = 1
+            let result = (1.0 - t) * a + t * b;
+            interpolation_result(
+                "lerp",
+                &[("a", a), ("b", b), ("t", t)],
+                result,
+                format!("{} + ({} - {}) * {}", a, b, a, t),
+                !(0.0..=1.0).contains(&t),
+            )
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_map_range(arguments: &Option<String>) -> CallToolResult {
+    match parse_number_args(
+        arguments,
+        ["value", "in_min", "in_max", "out_min", "out_max"],
+    ) {
+        Ok([_, in_min, in_max, ..]) if in_min == in_max => error_result(format!(
+            "Error: The input range [{}, {}] has zero width",
+            in_min, in_max
+        )),
+        Ok([value, in_min, in_max, out_min, out_max]) => {
+            let t = (value - in_min) / (in_max - in_min);
+            let result = out_min + t * (out_max - out_min);
+            interpolation_result(
+                "map_range",
+                &[
+                    ("value", value),
+                    ("in_min", in_min),
+                    ("in_max", in_max),
+                    ("out_min", out_min),
+                    ("out_max", out_max),
+                ],
+                result,
+                format!(
+                    "{} + ({} - {}) / ({} - {}) * ({} - {})",
+                    out_min, value, in_min, in_max, in_min, out_max, out_min
+                ),
+                value < in_min.min(in_max) || value > in_min.max(in_max),
+            )
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Result of `lerp` and `map_range`, with a note in the text when the input
+/// was outside the range and the result is extrapolated
+fn interpolation_result(
+    operation: &str,
+    operands: &[(&str, f64)],
+    result: f64,
+    formula: String,
+    extrapolated: bool,
+) -> CallToolResult {
+    let result = match finite_result(operation, operands, result) {
+        Ok(result) => result,
+        Err(msg) => return error_result(msg),
+    };
+    let text = if extrapolated {
+        format!("{} (extrapolated: the input is outside the range)", result)
+    } else {
+        result.to_string()
+    };
+    structured_success_result(
+        text,
+        &InterpolationResult {
+            result,
+            formula,
+            extrapolated,
+        },
+    )
+}
+
+fn execute_round(arguments: &Option<String>) -> CallToolResult {
+    let value = match parse_single_arg(arguments, "value") {
+        Ok(value) => value,
+        Err(msg) => return error_result(msg),
+    };
+    let digits = match parse_optional_integer_arg(arguments, "digits") {
+        Ok(None) => 0,
+        Ok(Some(d)) if (-15..=15).contains(&d) => d as i32,
+        Ok(Some(_)) => {
+            return error_result("Parameter 'digits' must be between -15 and 15".to_string())
+        }
+        Err(msg) => return error_result(msg),
+    };
+    let mode = match parse_optional_string_arg(arguments, "mode")
+        .and_then(|mode| RoundingMode::parse(mode.as_deref()))
+    {
+        Ok(mode) => mode,
+        Err(msg) => return error_result(msg),
+    };
+
+    let result = round_with_mode(value, digits, mode);
+    let text = format!("{:.*}", digits.max(0) as usize, result);
+    structured_success_result(text, &NumberResult { result })
+}
+
+fn execute_format_number(arguments: &Option<String>) -> CallToolResult {
+    let value = match parse_single_arg(arguments, "value") {
+        Ok(value) => value,
+        Err(msg) => return error_result(msg),
+    };
+    let decimals = match parse_optional_integer_arg(arguments, "decimals") {
+        Ok(None) => 2,
+        Ok(Some(d)) if (0..=15).contains(&d) => d as usize,
+        Ok(Some(_)) => {
+            return error_result("Parameter 'decimals' must be between 0 and 15".to_string())
+        }
+        Err(msg) => return error_result(msg),
+    };
+    let thousands = match parse_optional_string_arg(arguments, "thousands_separator") {
+        Ok(separator) => separator.unwrap_or_else(|| ",".to_string()),
+        Err(msg) => return error_result(msg),
+    };
+    let decimal = match parse_optional_string_arg(arguments, "decimal_separator") {
+        Ok(separator) => separator.unwrap_or_else(|| ".".to_string()),
+        Err(msg) => return error_result(msg),
+    };
+
+    if !value.is_finite() {
+        return error_result(format!("Error: Cannot format non-finite value {}", value));
+    }
+
+    let rounded = round_with_mode(value, decimals as i32, RoundingMode::HalfUp);
+    let fixed = format!("{:.*}", decimals, rounded.abs());
+    let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push_str(&thousands);
+        }
+        grouped.push(digit);
+    }
+
+    // Avoid "-0.00" when a small negative value rounds to zero
+    let sign = if rounded < 0.0 { "-" } else { "" };
+    let text = if fraction.is_empty() {
+        format!("{}{}", sign, grouped)
+    } else {
+        format!("{}{}{}{}", sign, grouped, decimal, fraction)
+    };
+
+    structured_success_result(text, &NumberResult { result: rounded })
+}
+
+/// Highest accepted annual interest rate, in percent
+const MAX_INTEREST_RATE: f64 = 1000.0;
+
+fn validate_principal(principal: f64) -> Result<(), String> {
+    if !principal.is_finite() || principal < 0.0 {
+        return Err("Error: Principal must be a non-negative amount".to_string());
+    }
+    Ok(())
+}
+
+fn validate_rate(rate: f64, arg_name: &str) -> Result<(), String> {
+    if !rate.is_finite() || rate < 0.0 {
+        return Err(format!(
+            "Error: Parameter '{}' must not be negative",
+            arg_name
+        ));
+    }
+    if rate > MAX_INTEREST_RATE {
+        return Err(format!(
+            "Error: Parameter '{}' is {}%, which exceeds the maximum of {}%",
+            arg_name, rate, MAX_INTEREST_RATE
+        ));
+    }
+    Ok(())
+}
+
+fn parse_positive_count(arguments: &Option<String>, arg_name: &str) -> Result<u32, String> {
+    let value = parse_integer_arg(arguments, arg_name)?;
+    u32::try_from(value).ok().filter(|&v| v > 0).ok_or_else(|| {
+        format!(
+            "Error: Parameter '{}' must be a positive whole number",
+            arg_name
+        )
+    })
+}
+
+fn execute_compound_interest(arguments: &Option<String>) -> CallToolResult {
+    let [principal, rate, years] =
+        match parse_number_args(arguments, ["principal", "rate", "years"]) {
+            Ok(values) => values,
+            Err(msg) => return error_result(msg),
+        };
+    let periods_per_year = match parse_positive_count(arguments, "periods_per_year") {
+        Ok(periods) => periods,
+        Err(msg) => return error_result(msg),
+    };
+    if let Err(msg) = validate_principal(principal).and_then(|_| validate_rate(rate, "rate")) {
+        return error_result(msg);
+    }
+    if !years.is_finite() || years < 0.0 {
+        return error_result("Error: Parameter 'years' must not be negative".to_string());
+    }
+
+    let periods = periods_per_year as f64;
+    let operands = [
+        ("principal", principal),
+        ("rate", rate),
+        ("periods_per_year", periods),
+        ("years", years),
+    ];
+    let amount = match finite_result(
+        "compound_interest",
+        &operands,
+        principal * (1.0 + rate / 100.0 / periods).powf(periods * years),
+    ) {
+        Ok(amount) => amount,
+        Err(msg) => return error_result(msg),
+    };
+
+    structured_success_result(
+        format!("{:.2}", amount),
+        &CompoundInterestResult {
+            result: amount,
+            principal,
+            total_interest: amount - principal,
+        },
+    )
+}
+
+fn execute_loan_payment(arguments: &Option<String>) -> CallToolResult {
+    let [principal, annual_rate] = match parse_number_args(arguments, ["principal", "annual_rate"])
+    {
+        Ok(values) => values,
+        Err(msg) => return error_result(msg),
+    };
+    let months = match parse_positive_count(arguments, "months") {
+        Ok(months) => months,
+        Err(msg) => return error_result(msg),
+    };
+    let amortization = match parse_optional_bool_arg(arguments, "amortization") {
+        Ok(flag) => flag.unwrap_or(false),
+        Err(msg) => return error_result(msg),
+    };
+    if let Err(msg) =
+        validate_principal(principal).and_then(|_| validate_rate(annual_rate, "annual_rate"))
+    {
+        return error_result(msg);
+    }
+
+    let monthly_rate = annual_rate / 100.0 / 12.0;
+    let n = months as f64;
+    // An interest-free loan is repaid in equal slices; the annuity formula would divide by zero
+    let payment = if monthly_rate == 0.0 {
+        principal / n
+    } else {
+        principal * monthly_rate / (1.0 - (1.0 + monthly_rate).powf(-n))
+    };
+    let operands = [
+        ("principal", principal),
+        ("annual_rate", annual_rate),
+        ("months", n),
+    ];
+    let payment = match finite_result("loan_payment", &operands, payment) {
+        Ok(payment) => payment,
+        Err(msg) => return error_result(msg),
+    };
+    let total_paid = payment * n;
+
+    let mut structured = LoanPaymentResult {
+        result: payment,
+        total_paid,
+        total_interest: total_paid - principal,
+        amortization: None,
+    };
+
+    if amortization {
+        // Balance still owed before the final payment
+        let growth = (1.0 + monthly_rate).powf(n - 1.0);
+        let last_balance = if monthly_rate == 0.0 {
+            payment
+        } else {
+            principal * growth - payment * (growth - 1.0) / monthly_rate
+        };
+        let first_interest = principal * monthly_rate;
+        let last_interest = last_balance * monthly_rate;
+        structured.amortization = Some(Amortization {
+            first_payment: PaymentSplit {
+                interest: first_interest,
+                principal: payment - first_interest,
+            },
+            last_payment: PaymentSplit {
+                interest: last_interest,
+                principal: payment - last_interest,
+            },
+        });
+    }
+
+    structured_success_result(format!("{:.2}", payment), &structured)
+}
+
+/// Units of a duration breakdown, largest first
+const DURATION_UNITS: [(&str, i64); 4] = [
+    ("days", 86_400),
+    ("hours", 3_600),
+    ("minutes", 60),
+    ("seconds", 1),
+];
+
+fn execute_duration_between(arguments: &Option<String>) -> CallToolResult {
+    let parsed = Args::parse(arguments)
+        .and_then(|args| Ok((timestamp_arg(&args, "start")?, timestamp_arg(&args, "end")?)));
+    let (start, end) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    // Both fit in i64, so the difference always fits in i128 but may not in i64
+    let seconds = match i64::try_from(end as i128 - start as i128) {
+        Ok(seconds) => seconds,
+        Err(_) => {
+            return error_result(format!(
+                "Error: The duration from {} to {} overflows the 64-bit range of seconds",
+                start, end
+            ))
+        }
+    };
+
+    let (duration, human) = duration_breakdown(seconds);
+    structured_success_result(
+        format!("{} seconds ({})", seconds, human),
+        &DurationBetweenResult {
+            result: seconds,
+            duration,
+            human,
+        },
+    )
+}
+
+fn execute_add_duration(arguments: &Option<String>) -> CallToolResult {
+    let parsed = Args::parse(arguments).and_then(|args| {
+        let timestamp = timestamp_arg(&args, "timestamp")?;
+        let duration: serde_json::Value = args.required("duration")?;
+        Ok((timestamp, duration_seconds(&Args::from_value(&duration)?)?))
+    });
+    let (timestamp, seconds) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    let result = match timestamp.checked_add(seconds) {
+        Some(result) => result,
+        None => {
+            return error_result(format!(
+                "Error: Adding {} seconds to {} overflows the 64-bit timestamp range",
+                seconds, timestamp
+            ))
+        }
+    };
+
+    let (added, human) = duration_breakdown(seconds);
+    structured_success_result(
+        result.to_string(),
+        &AddDurationResult {
+            result,
+            added_seconds: seconds,
+            added,
+            human,
+        },
+    )
+}
+
+fn timestamp_arg(args: &Args, name: &str) -> Result<i64, String> {
+    let Integer(value) = args.required(name)?;
+    i64::try_from(value).map_err(|_| {
+        format!(
+            "Error: Parameter '{}' must fit in a 64-bit signed integer",
+            name
+        )
+    })
+}
+
+/// Total seconds of a `{days, hours, minutes, seconds}` object, with checked arithmetic
+fn duration_seconds(duration: &Args) -> Result<i64, String> {
+    let mut total: i64 = 0;
+    for (name, unit_seconds) in DURATION_UNITS {
+        let Integer(value) = duration.optional(name)?.unwrap_or(Integer(0));
+        total = i64::try_from(value)
+            .ok()
+            .and_then(|value| value.checked_mul(unit_seconds))
+            .and_then(|seconds| total.checked_add(seconds))
+            .ok_or_else(|| {
+                format!(
+                    "Error: Duration with {} {} overflows the 64-bit range of seconds",
+                    value, name
+                )
+            })?;
+    }
+    Ok(total)
+}
+
+/// Split signed seconds into units and a readable form such as "3 days, 4 hours"
+fn duration_breakdown(seconds: i64) -> (DurationParts, String) {
+    let sign = if seconds < 0 { -1 } else { 1 };
+    let mut remaining = seconds.unsigned_abs();
+
+    let mut values = [0i64; 4];
+    let mut words = Vec::new();
+    for (value, (name, unit_seconds)) in values.iter_mut().zip(DURATION_UNITS) {
+        let count = remaining / unit_seconds as u64;
+        remaining %= unit_seconds as u64;
+        // At most u64::MAX / 86400 days, so every count fits in i64
+        *value = sign * count as i64;
+        if count > 0 {
+            let unit = if count == 1 {
+                &name[..name.len() - 1]
+            } else {
+                name
+            };
+            words.push(format!("{} {}", count, unit));
+        }
+    }
+
+    let human = match (words.is_empty(), seconds < 0) {
+        (true, _) => "0 seconds".to_string(),
+        (false, false) => words.join(", "),
+        (false, true) => format!("minus {}", words.join(", ")),
+    };
+    let [days, hours, minutes, seconds] = values;
+    (
+        DurationParts {
+            days,
+            hours,
+            minutes,
+            seconds,
+        },
+        human,
+    )
+}
+
+/// Largest number of values `random_number` returns in one call
+const MAX_RANDOM_COUNT: i128 = 1000;
+
+/// Calls made so far by this instance, mixed into each seed so that calls
+/// within the same clock tick still get different streams
+static RANDOM_CALLS: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// SplitMix64 generator: fast and statistically sound, but predictable
+/// from its output, so it is not suitable for anything security related
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn from_seed(seed: u64) -> Self {
+        SplitMix64(seed)
+    }
+
+    fn from_clock() -> Self {
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default();
+        let call = RANDOM_CALLS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let mut seed = SplitMix64(nanos ^ call.rotate_left(32));
+        SplitMix64(seed.next_u64())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform integer in [0, span], rejecting draws from the incomplete
+    /// final block so that no value is favoured (no modulo bias)
+    fn below_or_equal(&mut self, span: u64) -> u64 {
+        if span == u64::MAX {
+            return self.next_u64();
+        }
+        let range = span + 1;
+        let zone = u64::MAX - (u64::MAX - range + 1) % range;
+        loop {
+            let draw = self.next_u64();
+            if draw <= zone {
+                return draw % range;
+            }
+        }
+    }
+}
+
+fn execute_random_number(arguments: &Option<String>) -> CallToolResult {
+    let [min, max] = match parse_number_args(arguments, ["min", "max"]) {
+        Ok(values) => values,
+        Err(msg) => return error_result(msg),
+    };
+    let integer = match parse_optional_bool_arg(arguments, "integer") {
+        Ok(flag) => flag.unwrap_or(false),
+        Err(msg) => return error_result(msg),
+    };
+    let count = match parse_optional_integer_arg(arguments, "count") {
+        Ok(Some(n)) if !(1..=MAX_RANDOM_COUNT).contains(&n) => {
+            return error_result(format!(
+                "Parameter 'count' must be between 1 and {}",
+                MAX_RANDOM_COUNT
+            ))
+        }
+        Ok(count) => count,
+        Err(msg) => return error_result(msg),
+    };
+    let seed = match parse_optional_integer_arg(arguments, "seed") {
+        Ok(Some(n)) => match u64::try_from(n) {
+            Ok(seed) => Some(seed),
+            Err(_) => {
+                return error_result(format!(
+                    "Parameter 'seed' must be between 0 and {}",
+                    u64::MAX
+                ))
+            }
+        },
+        Ok(None) => None,
+        Err(msg) => return error_result(msg),
+    };
+    if !min.is_finite() || !max.is_finite() {
+        return error_result("Error: 'min' and 'max' must be finite numbers".to_string());
+    }
+    if min > max {
+        return error_result(format!(
+            "Error: 'min' ({}) must not be greater than 'max' ({})",
+            min, max
+        ));
+    }
+
+    let mut rng = seed.map_or_else(SplitMix64::from_clock, SplitMix64::from_seed);
+    let values: Vec<serde_json::Value> = if integer {
+        let in_range = |v: f64| v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64;
+        if !in_range(min) || !in_range(max) {
+            return error_result("Error: In integer mode 'min' and 'max' must be whole numbers within the 64-bit range".to_string());
+        }
+        let (min, max) = (min as i64, max as i64);
+        let span = max.abs_diff(min);
+        (0..count.unwrap_or(1))
+            .map(|_| min.wrapping_add_unsigned(rng.below_or_equal(span)).into())
+            .collect()
+    } else {
+        // The width of a range spanning most of f64 overflows
+        if let Err(msg) = finite_result("random_number", &[("min", min), ("max", max)], max - min) {
+            return error_result(msg);
+        }
+        (0..count.unwrap_or(1))
+            .map(|_| (min + (max - min) * rng.next_f64()).into())
+            .collect()
+    };
+
+    let text = values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let result = match count {
+        Some(_) => serde_json::Value::Array(values),
+        None => values.into_iter().next().unwrap_or_default(),
+    };
+    structured_success_result(text, &RandomNumberResult { result })
+}
+
+fn execute_accumulator_add(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_accumulator_id(arguments).and_then(|id| {
+        let value: f64 = Args::parse(arguments)?.required("value")?;
+        Ok((id, value))
+    });
+    let (id, value) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+    if !value.is_finite() {
+        return error_result("Error: 'value' must be a finite number".to_string());
+    }
+
+    match accumulator::add(&id, value) {
+        Ok(total) => accumulator_result(id, total, None),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_accumulator_get(arguments: &Option<String>) -> CallToolResult {
+    match parse_accumulator_id(arguments).and_then(|id| Ok((accumulator::get(&id)?, id))) {
+        Ok((total, id)) => accumulator_result(id, total, None),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_accumulator_reset(arguments: &Option<String>) -> CallToolResult {
+    match parse_accumulator_id(arguments).and_then(|id| Ok((accumulator::reset(&id)?, id))) {
+        Ok((previous, id)) => accumulator_result(id, 0.0, Some(previous)),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn parse_accumulator_id(arguments: &Option<String>) -> Result<String, String> {
+    let id: String = Args::parse(arguments)?.required("id")?;
+    accumulator::check_id(&id)?;
+    Ok(id)
+}
+
+fn accumulator_result(id: String, total: f64, previous: Option<f64>) -> CallToolResult {
+    let text = match previous {
+        Some(previous) => format!("{}: {} (was {})", id, total, previous),
+        None => format!("{}: {}", id, total),
+    };
+    structured_success_result(
+        text,
+        &AccumulatorResult {
+            id,
+            result: total,
+            previous,
+        },
+    )
+}
+
+fn parse_optional_bool_arg(
+    arguments: &Option<String>,
+    arg_name: &str,
+) -> Result<Option<bool>, String> {
+    Args::parse(arguments)?.optional(arg_name)
+}
+
+fn parse_optional_string_arg(
+    arguments: &Option<String>,
+    arg_name: &str,
+) -> Result<Option<String>, String> {
+    Args::parse(arguments)?.optional(arg_name)
+}
+
+/// Parse several required numeric parameters in one pass
+fn parse_number_args<const N: usize>(
+    arguments: &Option<String>,
+    arg_names: [&str; N],
+) -> Result<[f64; N], String> {
+    let args = Args::parse(arguments)?;
+
+    let mut values = [0.0; N];
+    for (value, name) in values.iter_mut().zip(arg_names) {
+        *value = args.required(name)?;
+    }
+
+    Ok(values)
+}
+
+/// Interpret a JSON value as an integer, accepting integral floats such as `4.0`
+fn parse_integer_arg(arguments: &Option<String>, arg_name: &str) -> Result<i128, String> {
+    let Integer(value) = Args::parse(arguments)?.required(arg_name)?;
+    Ok(value)
+}
+
+fn parse_optional_integer_arg(
+    arguments: &Option<String>,
+    arg_name: &str,
+) -> Result<Option<i128>, String> {
+    let value: Option<Integer> = Args::parse(arguments)?.optional(arg_name)?;
+    Ok(value.map(|Integer(i)| i))
+}
+
+fn parse_integers(arguments: &Option<String>, arg_name: &str) -> Result<Vec<i128>, String> {
+    let values: Vec<Integer> = Args::parse(arguments)?.required(arg_name)?;
+
+    if values.is_empty() {
+        return Err(format!(
+            "Parameter '{}' must contain at least one integer",
+            arg_name
+        ));
+    }
+
+    Ok(values.into_iter().map(|Integer(i)| i).collect())
+}
+
+/// Reject NaN and infinite results, which have no JSON representation
+fn finite_result(operation: &str, operands: &[(&str, f64)], result: f64) -> Result<f64, String> {
+    if result.is_finite() {
+        return Ok(result);
+    }
+
+    let operands = operands
+        .iter()
+        .map(|(name, value)| {
+            // Scientific notation keeps huge and tiny operands readable
+            if *value != 0.0 && !(1e-6..1e16).contains(&value.abs()) {
+                format!("{}={:e}", name, value)
+            } else {
+                format!("{}={}", name, value)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let outcome = if result.is_nan() {
+        "is not a number (NaN)"
+    } else {
+        "overflows to infinity"
+    };
+    Err(format!(
+        "Error: {} with {} {}",
+        operation, operands, outcome
+    ))
+}
+
+/// Plain numeric result, as text and as a structured `NumberResult`
+fn number_result(result: f64) -> CallToolResult {
+    structured_success_result(result.to_string(), &NumberResult { result })
+}
+
+/// Only `Output` types can become structured content, keeping results in
+/// step with the schemas advertised for them. They are serialized directly,
+/// not through `serde_json::Value`, which cannot hold `u128` results.
+fn structured_success_result(result: String, structured: &impl Output) -> CallToolResult {
+    match serde_json::to_string(structured) {
+        Ok(json) => result_builder::structured_json_result(result, json),
+        Err(e) => error_result(format!(
+            "Error: Failed to serialize the structured result: {}",
+            e
+        )),
+    }
+}
+
+bindings::export!(Math with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn call(name: &str, arguments: &str) -> CallToolResult {
+        let ctx = bindings::wasmcp::protocol::server_messages::Context {
+            claims: None,
+            session_id: None,
+            data: vec![],
+        };
+        let request = CallToolRequest {
+            name: name.to_string(),
+            arguments: Some(arguments.to_string()),
+        };
+        <Math as Guest>::call_tool(ctx, request, None).expect("math handles this tool")
+    }
+
+    fn text(result: &CallToolResult) -> &str {
+        match &result.content[0] {
+            ContentBlock::Text(TextContent {
+                text: TextData::Text(text),
+                ..
+            }) => text,
+            _ => panic!("expected a text block"),
+        }
+    }
+
+    #[test]
+    fn round_leaves_values_that_are_already_short_enough() {
+        assert_eq!(round_with_mode(1.0, 15, RoundingMode::HalfUp), 1.0);
+        assert_eq!(round_with_mode(1000.0, 12, RoundingMode::HalfUp), 1000.0);
+        assert_eq!(round_with_mode(1e15, 0, RoundingMode::HalfUp), 1e15);
+        assert_eq!(
+            text(&call("round", r#"{"value": 1, "digits": 15}"#)),
+            "1.000000000000000"
+        );
+        assert_eq!(
+            text(&call("format_number", r#"{"value": 6e12, "decimals": 2}"#)),
+            "6,000,000,000,000.00"
+        );
+    }
+
+    #[test]
+    fn round_breaks_ties_by_mode() {
+        assert_eq!(round_with_mode(2.5, 0, RoundingMode::HalfEven), 2.0);
+        assert_eq!(round_with_mode(3.5, 0, RoundingMode::HalfEven), 4.0);
+        assert_eq!(round_with_mode(2.5, 0, RoundingMode::HalfUp), 3.0);
+        assert_eq!(round_with_mode(-2.5, 0, RoundingMode::HalfUp), -3.0);
+        assert_eq!(round_with_mode(0.125, 2, RoundingMode::HalfUp), 0.13);
+        assert_eq!(round_with_mode(0.125, 2, RoundingMode::HalfEven), 0.12);
+        assert_eq!(round_with_mode(2.675, 2, RoundingMode::HalfUp), 2.68);
+        assert_eq!(round_with_mode(1250.0, -2, RoundingMode::HalfEven), 1200.0);
+    }
+
+    #[test]
+    fn round_directed_modes() {
+        assert_eq!(round_with_mode(-1.231, 2, RoundingMode::Floor), -1.24);
+        assert_eq!(round_with_mode(1.231, 2, RoundingMode::Ceil), 1.24);
+        assert_eq!(round_with_mode(-1.239, 2, RoundingMode::Truncate), -1.23);
+        assert_eq!(round_with_mode(0.004, 2, RoundingMode::Ceil), 0.01);
+        assert_eq!(round_with_mode(0.004, 2, RoundingMode::HalfUp), 0.0);
+        assert_eq!(round_with_mode(0.0004, 2, RoundingMode::HalfUp), 0.0);
+    }
+
+    #[test]
+    fn overflowing_results_are_errors() {
+        let result = call("multiply", r#"{"a": 1.7976931348623157e308, "b": 2}"#);
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            text(&result),
+            "Error: multiply with a=1.7976931348623157e308, b=2 overflows to infinity"
+        );
+        assert_eq!(
+            finite_result("multiply", &[("a", f64::MAX), ("b", 2.0)], f64::MAX * 2.0),
+            Err(
+                "Error: multiply with a=1.7976931348623157e308, b=2 overflows to infinity"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            finite_result("add", &[("a", 1.0), ("b", 2.0)], 3.0),
+            Ok(3.0)
+        );
+    }
+
+    #[test]
+    fn zero_over_zero_is_an_error() {
+        let result = call("divide", r#"{"a": 0, "b": 0}"#);
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(text(&result), "Error: Division by zero");
+        assert_eq!(
+            text(&call("divide", r#"{"a": 1, "b": -0.0}"#)),
+            "Error: Division by zero"
+        );
+        assert_eq!(
+            finite_result("divide", &[("a", 0.0), ("b", 0.0)], f64::NAN),
+            Err("Error: divide with a=0, b=0 is not a number (NaN)".to_string())
+        );
+    }
+
+    #[test]
+    fn subnormal_divisors_that_overflow_are_errors() {
+        let result = call("divide", r#"{"a": 1, "b": 1e-320}"#);
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            text(&result),
+            "Error: divide with a=1, b=1e-320 overflows to infinity"
+        );
+        // A subnormal divisor whose quotient still fits is fine
+        let result = call("divide", r#"{"a": 1e-310, "b": 1e-320}"#);
+        assert_ne!(result.is_error, Some(true), "{}", text(&result));
+    }
+
+    #[test]
+    fn nan_inputs_to_the_range_tools_are_rejected() {
+        for (tool, arguments) in [
+            ("clamp", r#"{"value": "NaN", "min": 0, "max": 10}"#),
+            ("clamp", r#"{"value": 5, "min": "NaN", "max": 10}"#),
+            ("clamp", r#"{"value": 5, "min": 0, "max": "nan"}"#),
+            ("lerp", r#"{"a": 0, "b": 10, "t": "NaN"}"#),
+            (
+                "map_range",
+                r#"{"value": "NaN", "in_min": 0, "in_max": 10, "out_min": 0, "out_max": 1}"#,
+            ),
+            (
+                "map_range",
+                r#"{"value": 5, "in_min": 0, "in_max": "NaN", "out_min": 0, "out_max": 1}"#,
+            ),
+        ] {
+            let result = call(tool, arguments);
+            assert_eq!(result.is_error, Some(true), "{} {}", tool, arguments);
+            assert!(
+                text(&result).contains("expected number, got string"),
+                "{} {}: {}",
+                tool,
+                arguments,
+                text(&result)
+            );
+        }
+    }
+
+    #[test]
+    fn clamp_rejects_inverted_bounds() {
+        assert_eq!(
+            text(&call("clamp", r#"{"value": 15, "min": 0, "max": 10}"#)),
+            "10"
+        );
+        assert_eq!(
+            text(&call("clamp", r#"{"value": -3, "min": 0, "max": 10}"#)),
+            "0"
+        );
+        let result = call("clamp", r#"{"value": 5, "min": 10, "max": 0}"#);
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            text(&result),
+            "Error: min (10) must not be greater than max (0)"
+        );
+    }
+
+    #[test]
+    fn lerp_notes_extrapolation() {
+        assert_eq!(
+            text(&call("lerp", r#"{"a": 10, "b": 0, "t": 0.25}"#)),
+            "7.5"
+        );
+        assert_eq!(
+            text(&call("lerp", r#"{"a": 0, "b": 10, "t": 1.5}"#)),
+            "15 (extrapolated: the input is outside the range)"
+        );
+    }
+
+    #[test]
+    fn map_range_handles_inverted_and_zero_width_ranges() {
+        let map = |args: &str| text(&call("map_range", args)).to_string();
+        assert_eq!(
+            map(r#"{"value": 2, "in_min": 10, "in_max": 0, "out_min": 0, "out_max": 100}"#),
+            "80"
+        );
+        assert_eq!(
+            map(r#"{"value": 2, "in_min": 0, "in_max": 10, "out_min": 100, "out_max": 0}"#),
+            "80"
+        );
+        assert_eq!(
+            map(r#"{"value": 12, "in_min": 10, "in_max": 0, "out_min": 0, "out_max": 100}"#),
+            "-20 (extrapolated: the input is outside the range)"
+        );
+
+        let result = call(
+            "map_range",
+            r#"{"value": 5, "in_min": 3, "in_max": 3, "out_min": 0, "out_max": 1}"#,
+        );
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            text(&result),
+            "Error: The input range [3, 3] has zero width"
+        );
+    }
+
+    #[test]
+    fn solve_handles_degenerate_equations() {
+        // A quadratic with a = 0 is solved as the linear equation it is
+        let result = call("solve", r#"{"degree": 2, "a": 0, "b": 2, "c": -4}"#);
+        assert_eq!(
+            result.structured_content.as_deref(),
+            Some(r#"{"degree":1,"roots":[2.0],"multiplicities":[1]}"#)
+        );
+
+        // Every coefficient of x zero: singular, whatever the constant
+        for arguments in [
+            r#"{"degree": 1, "a": 0, "b": 5}"#,
+            r#"{"degree": 2, "a": 0, "b": 0, "c": 5}"#,
+        ] {
+            let result = call("solve", arguments);
+            assert_eq!(result.is_error, Some(true), "{}", arguments);
+            assert_eq!(
+                text(&result),
+                "Error: No equation to solve: every coefficient of x is zero, leaving 5 = 0"
+            );
+        }
+    }
+
+    #[test]
+    fn solve_rejects_coefficients_that_do_not_match_the_degree() {
+        assert_eq!(
+            text(&call("solve", r#"{"degree": 2, "a": 1, "b": 2}"#)),
+            "Missing parameter 'c' at /c (required by degree 2)"
+        );
+        let result = call("solve", r#"{"degree": 3, "a": 1, "b": 2, "c": 3}"#);
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn solve_near_degenerate_quadratics() {
+        // A tiny leading coefficient keeps the small root accurate and sends
+        // the other one far away rather than dividing by zero
+        let result = call("solve", r#"{"degree": 2, "a": 1e-20, "b": 1, "c": -1}"#);
+        let structured: Value =
+            serde_json::from_str(result.structured_content.as_deref().expect("solved")).unwrap();
+        assert_eq!(structured["roots"][1], 1.0);
+        assert_eq!(structured["roots"][0], -1e20);
+
+        // Roots that do not fit in an f64 are reported, not returned as inf
+        let result = call("solve", r#"{"degree": 2, "a": 1e-300, "b": 1e300, "c": 1}"#);
+        assert_eq!(result.is_error, Some(true));
+        assert!(text(&result).contains("overflow"), "{}", text(&result));
+    }
+
+    #[test]
+    fn matrix_inverse_of_singular_and_non_square_matrices() {
+        let result = call("matrix", r#"{"op": "inverse", "a": [[1, 2], [2, 4]]}"#);
+        assert_eq!(result.is_error, Some(true));
+        assert!(
+            text(&result).starts_with("Error: Cannot invert a singular matrix"),
+            "{}",
+            text(&result)
+        );
+
+        let result = call(
+            "matrix",
+            r#"{"op": "inverse", "a": [[1, 2, 3], [4, 5, 6]]}"#,
+        );
+        assert_eq!(
+            text(&result),
+            "Error: Cannot take the inverse of a 2x3 matrix: it must be square"
+        );
+        let result = call(
+            "matrix",
+            r#"{"op": "multiply", "a": [[1, 2]], "b": [[1, 2]]}"#,
+        );
+        assert_eq!(
+            text(&result),
+            "Error: Cannot multiply 1x2 by 1x2: a needs as many columns as b has rows"
+        );
+    }
+
+    #[test]
+    fn matrix_inverse_pivots_around_tiny_entries() {
+        // Without row exchanges the 1e-20 pivot would swamp the result
+        let result = call("matrix", r#"{"op": "inverse", "a": [[1e-20, 1], [1, 1]]}"#);
+        assert_ne!(result.is_error, Some(true), "{}", text(&result));
+        let structured: Value =
+            serde_json::from_str(result.structured_content.as_deref().unwrap()).unwrap();
+        assert_eq!(
+            structured["result"],
+            serde_json::json!([[-1.0, 1.0], [1.0, -1e-20]])
+        );
+
+        // Rows equal to within rounding are singular
+        let result = call(
+            "matrix",
+            r#"{"op": "inverse", "a": [[1, 1], [1, 1.0000000000000002]]}"#,
+        );
+        assert_eq!(result.is_error, Some(true), "{}", text(&result));
+    }
+
+    #[test]
+    fn quoted_numbers_are_accepted() {
+        assert_eq!(text(&call("add", r#"{"a": "1e3", "b": " 2 "}"#)), "1002");
+        let result = call("add", r#"{"a": "", "b": 2}"#);
+        assert_eq!(result.is_error, Some(true));
+        assert!(
+            text(&result).contains("got string \"\""),
+            "{}",
+            text(&result)
+        );
+    }
+
+    #[test]
+    fn empty_arguments_report_the_missing_parameter() {
+        let result = call("add", "{}");
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            text(&result),
+            "Arguments for 'add' do not match its input schema at /: missing required property 'a'"
+        );
+    }
+
+    #[test]
+    fn random_number_is_reproducible_from_its_seed() {
+        let draw = |seed: u64, integer: bool| {
+            let arguments = format!(
+                r#"{{"min": 0, "max": 1000000, "integer": {}, "count": 5, "seed": {}}}"#,
+                integer, seed
+            );
+            let result = call("random_number", &arguments);
+            assert_ne!(result.is_error, Some(true), "{}", text(&result));
+            result.structured_content.expect("structured result")
+        };
+        for integer in [true, false] {
+            assert_eq!(draw(42, integer), draw(42, integer));
+            assert_ne!(draw(42, integer), draw(43, integer));
+            assert_ne!(draw(0, integer), draw(u64::MAX, integer));
+        }
+    }
+
+    #[test]
+    fn integer_results_above_64_bits_keep_structured_content() {
+        let result = call(
+            "lcm",
+            r#"{"numbers": [9223372036854775807, 9223372036854775806]}"#,
+        );
+        let expected = 9223372036854775807u128 * 9223372036854775806;
+        assert!(expected > u64::MAX as u128);
+        assert_eq!(text(&result), expected.to_string());
+        assert_eq!(
+            result.structured_content.as_deref(),
+            Some(format!(r#"{{"result":{}}}"#, expected).as_str())
+        );
+    }
+
+    #[test]
+    fn call_tool_rejects_arguments_that_are_not_an_object() {
+        let result = call("add", "[1, 2]");
+        assert_eq!(result.is_error, Some(true));
+        assert!(
+            text(&result).contains("expected a JSON object, got array of 2 items"),
+            "{}",
+            text(&result)
+        );
+    }
+
+    #[test]
+    fn tools_carry_their_annotations() {
+        let annotations = |name: &str| {
+            let tool = tools()
+                .into_iter()
+                .find(|tool| tool.name == name)
+                .expect("tool exists");
+            let annotations = tool
+                .options
+                .and_then(|options| options.annotations)
+                .expect("annotated");
+            (
+                annotations.read_only_hint,
+                annotations.destructive_hint,
+                annotations.idempotent_hint,
+            )
+        };
+        assert_eq!(annotations("add"), (Some(true), Some(false), Some(true)));
+        assert_eq!(annotations("divide"), (Some(true), Some(false), Some(true)));
+        assert_eq!(
+            annotations("random_number"),
+            (Some(true), Some(false), Some(false))
+        );
+        assert_eq!(
+            annotations("accumulator_reset"),
+            (Some(false), Some(true), Some(true))
+        );
+    }
+
+    #[test]
+    fn smallest_factor_of_semiprimes_near_u64_max() {
+        // The two largest primes below 2^32
+        let (p, q) = (4_294_967_279u64, 4_294_967_291u64);
+        assert_eq!(smallest_factor(p * q), Some(p));
+        assert!([p, q].contains(&pollard_rho(p * q)));
+        assert_eq!(smallest_factor(u64::MAX), Some(3));
+        // The largest prime below 2^64
+        assert_eq!(
+            smallest_factor(18_446_744_073_709_551_557),
+            Some(18_446_744_073_709_551_557)
+        );
+    }
+
+    #[test]
+    fn rho_step_does_not_overflow_near_u64_max() {
+        // The largest prime below 2^64, and a square root of -1 modulo it
+        let n = 18_446_744_073_709_551_557u64;
+        let root = 2_296_021_864_060_584_341u64;
+        assert_eq!(mul_mod(root, root, n), n - 1);
+        // n - 1 + 100 is past u64::MAX
+        assert_eq!(rho_step(root, 100, n), 99);
+    }
+
+    /// Replace each local `{"$ref": "#/$defs/name"}` in `schema` with its
+    /// definition, since [`schema::validate`] does not follow references
+    fn inline_refs(schema: &Value, defs: &Value) -> Value {
+        match schema {
+            Value::Object(map) => match map.get("$ref").and_then(Value::as_str) {
+                Some(reference) => {
+                    let name = reference
+                        .strip_prefix("#/$defs/")
+                        .unwrap_or_else(|| panic!("unsupported $ref {}", reference));
+                    let def = defs
+                        .get(name)
+                        .unwrap_or_else(|| panic!("unresolved $ref {}", reference));
+                    inline_refs(def, defs)
+                }
+                None => Value::Object(
+                    map.iter()
+                        .filter(|(key, _)| key.as_str() != "$defs")
+                        .map(|(key, value)| (key.clone(), inline_refs(value, defs)))
+                        .collect(),
+                ),
+            },
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|item| inline_refs(item, defs)).collect())
+            }
+            other => other.clone(),
+        }
+    }
+
+    #[test]
+    fn structured_results_match_their_output_schemas() {
+        for tool in tools() {
+            let declared = tool
+                .options
+                .as_ref()
+                .and_then(|options| options.output_schema.as_deref())
+                .unwrap_or_else(|| panic!("{} declares no output schema", tool.name));
+            let declared: Value = serde_json::from_str(declared)
+                .unwrap_or_else(|e| panic!("{} output schema is not JSON: {}", tool.name, e));
+            assert_eq!(declared["type"], "object", "{} output schema", tool.name);
+            let properties = declared["properties"]
+                .as_object()
+                .unwrap_or_else(|| panic!("{} output schema has no properties", tool.name));
+            let output_schema = inline_refs(&declared, &declared["$defs"]);
+
+            for example in examples::EXAMPLES
+                .iter()
+                .filter(|example| example.tool == tool.name)
+            {
+                let result = call(&tool.name, example.input);
+                assert_ne!(
+                    result.is_error,
+                    Some(true),
+                    "{} {}: {}",
+                    tool.name,
+                    example.input,
+                    text(&result)
+                );
+                let structured = result
+                    .structured_content
+                    .as_deref()
+                    .unwrap_or_else(|| panic!("{} returned no structured content", tool.name));
+                let structured: Value =
+                    serde_json::from_str(structured).expect("structured content is JSON");
+
+                if let Err(e) = schema::validate(&output_schema, &structured) {
+                    panic!(
+                        "{} {} does not match its output schema: {}",
+                        tool.name, structured, e
+                    );
+                }
+                for key in structured
+                    .as_object()
+                    .expect("structured content is an object")
+                    .keys()
+                {
+                    assert!(
+                        properties.contains_key(key),
+                        "{} returned undeclared '{}'",
+                        tool.name,
+                        key
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn every_tool_has_an_example_that_round_trips() {
+        for tool in tools() {
+            let examples: Vec<_> = examples::EXAMPLES
+                .iter()
+                .filter(|example| example.tool == tool.name)
+                .collect();
+            assert!(!examples.is_empty(), "{} has no example", tool.name);
+
+            // The accumulator examples run in order against the stub store
+            for example in examples {
+                let result = call(&tool.name, example.input);
+                assert_ne!(
+                    result.is_error,
+                    Some(true),
+                    "{} {}: {}",
+                    tool.name,
+                    example.input,
+                    text(&result)
+                );
+                if let Some(output) = example.fixed_output {
+                    assert_eq!(text(&result), output, "{} {}", tool.name, example.input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn listed_tools_carry_their_examples() {
+        let ctx = bindings::wasmcp::protocol::server_messages::Context {
+            claims: None,
+            session_id: None,
+            data: vec![],
+        };
+        let listed = <Math as Guest>::list_tools(ctx, ListToolsRequest { cursor: None }, None)
+            .expect("list_tools");
+        assert_eq!(listed.tools.len(), tools().len());
+        for tool in listed.tools {
+            let meta = tool.options.and_then(|options| options.meta);
+            let meta: Value =
+                serde_json::from_str(meta.as_deref().unwrap_or("null")).expect("meta is JSON");
+            let examples = meta["examples"].as_array();
+            assert!(
+                examples.is_some_and(|examples| !examples.is_empty()),
+                "{} lists no examples",
+                tool.name
+            );
+        }
+    }
+}
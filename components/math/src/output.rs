@@ -0,0 +1,461 @@
+//! Structured results of the calculator tools and their output schemas
+//!
+//! Each result type carries the JSON Schema advertised for it in
+//! `list_tools`, and tools can only build `structured_content` from these
+//! types, so the declared schema and the actual output stay in step.
+
+use serde::Serialize;
+
+/// A structured tool result together with the schema describing it
+pub trait Output: Serialize {
+    const SCHEMA: &'static str;
+}
+
+/// A single numeric result: arithmetic, rounding and formatting tools
+#[derive(Serialize)]
+pub struct NumberResult {
+    pub result: f64,
+}
+
+impl Output for NumberResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "result": {"type": "number"}
+        },
+        "required": ["result"]
+    }"#;
+}
+
+/// A non-negative integer result (gcd, lcm), exact up to 128 bits
+#[derive(Serialize)]
+pub struct IntegerResult {
+    pub result: u128,
+}
+
+impl Output for IntegerResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "result": {"type": "integer", "minimum": 0}
+        },
+        "required": ["result"]
+    }"#;
+}
+
+#[derive(Serialize)]
+pub struct PrimeResult {
+    pub n: u64,
+    pub prime: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smallest_factor: Option<u64>,
+}
+
+impl Output for PrimeResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "n": {"type": "integer", "minimum": 0},
+            "prime": {"type": "boolean"},
+            "smallest_factor": {"type": "integer", "minimum": 2, "description": "Present when n is composite"}
+        },
+        "required": ["n", "prime"]
+    }"#;
+}
+
+/// A numeric result with the formula that produced it
+#[derive(Serialize)]
+pub struct FormulaResult {
+    pub result: f64,
+    pub formula: String,
+}
+
+impl Output for FormulaResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "result": {"type": "number"},
+            "formula": {"type": "string"}
+        },
+        "required": ["result", "formula"]
+    }"#;
+}
+
+/// An interpolated value, flagging results outside the given range
+#[derive(Serialize)]
+pub struct InterpolationResult {
+    pub result: f64,
+    pub formula: String,
+    pub extrapolated: bool,
+}
+
+impl Output for InterpolationResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "result": {"type": "number"},
+            "formula": {"type": "string"},
+            "extrapolated": {"type": "boolean", "description": "True when the input lies outside the interpolated range"}
+        },
+        "required": ["result", "formula", "extrapolated"]
+    }"#;
+}
+
+/// The terms of a simplified ratio a:b
+#[derive(Serialize)]
+pub struct RatioResult {
+    pub a: f64,
+    pub b: f64,
+    pub formula: String,
+}
+
+impl Output for RatioResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "a": {"type": "number"},
+            "b": {"type": "number"},
+            "formula": {"type": "string"}
+        },
+        "required": ["a", "b", "formula"]
+    }"#;
+}
+
+/// A complex result with its polar form
+#[derive(Serialize)]
+pub struct ComplexResult {
+    pub re: f64,
+    pub im: f64,
+    pub magnitude: f64,
+    /// Angle from the positive real axis in radians, in (-pi, pi]
+    pub phase: f64,
+}
+
+impl Output for ComplexResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "re": {"type": "number", "description": "Real part"},
+            "im": {"type": "number", "description": "Imaginary part"},
+            "magnitude": {"type": "number", "minimum": 0},
+            "phase": {"type": "number", "description": "Argument in radians, between -pi and pi"}
+        },
+        "required": ["re", "im", "magnitude", "phase"]
+    }"#;
+}
+
+/// A matrix (as an array of rows) or, for the determinant, a number
+#[derive(Serialize)]
+pub struct MatrixResult {
+    pub result: serde_json::Value,
+}
+
+impl Output for MatrixResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "result": {
+                "oneOf": [
+                    {"type": "number", "description": "Determinant"},
+                    {"type": "array", "items": {"type": "array", "items": {"type": "number"}}, "description": "Matrix as an array of rows"}
+                ]
+            }
+        },
+        "required": ["result"]
+    }"#;
+}
+
+/// Roots of a linear or quadratic equation
+#[derive(Serialize)]
+pub struct SolveResult {
+    /// Degree actually solved: 1 when a quadratic's a is zero
+    pub degree: u8,
+    /// b² - 4ac; absent for linear equations
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discriminant: Option<f64>,
+    pub roots: Vec<Root>,
+    /// Multiplicity of each root, in the same order
+    pub multiplicities: Vec<u8>,
+}
+
+/// A real root, or one of a pair of complex conjugate roots
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum Root {
+    Real(f64),
+    Complex { re: f64, im: f64 },
+}
+
+impl Output for SolveResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "degree": {"type": "integer", "enum": [1, 2], "description": "1 when a quadratic's a is zero"},
+            "discriminant": {"type": "number", "description": "b^2 - 4ac; present for quadratics"},
+            "roots": {
+                "type": "array",
+                "items": {
+                    "oneOf": [
+                        {"type": "number"},
+                        {"type": "object", "properties": {"re": {"type": "number"}, "im": {"type": "number"}}, "required": ["re", "im"]}
+                    ]
+                }
+            },
+            "multiplicities": {"type": "array", "items": {"type": "integer", "minimum": 1}, "description": "Multiplicity of each root, e.g. [2] for a double root"}
+        },
+        "required": ["degree", "roots", "multiplicities"]
+    }"#;
+}
+
+/// The 64-bit result of a bitwise operation in several renderings
+#[derive(Serialize)]
+pub struct BitwiseResult {
+    /// Signed (two's complement) decimal value
+    pub result: i64,
+    pub unsigned: u64,
+    pub hex: String,
+    pub binary: String,
+}
+
+impl Output for BitwiseResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "result": {"type": "integer", "description": "Signed decimal value"},
+            "unsigned": {"type": "integer", "minimum": 0, "description": "Unsigned decimal value"},
+            "hex": {"type": "string", "description": "Hexadecimal, e.g. 0xff"},
+            "binary": {"type": "string", "description": "Binary, e.g. 0b11111111"}
+        },
+        "required": ["result", "unsigned", "hex", "binary"]
+    }"#;
+}
+
+#[derive(Serialize)]
+pub struct CompoundInterestResult {
+    pub result: f64,
+    pub principal: f64,
+    pub total_interest: f64,
+}
+
+impl Output for CompoundInterestResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "result": {"type": "number", "description": "Final balance"},
+            "principal": {"type": "number"},
+            "total_interest": {"type": "number"}
+        },
+        "required": ["result", "principal", "total_interest"]
+    }"#;
+}
+
+#[derive(Serialize)]
+pub struct LoanPaymentResult {
+    pub result: f64,
+    pub total_paid: f64,
+    pub total_interest: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amortization: Option<Amortization>,
+}
+
+#[derive(Serialize)]
+pub struct Amortization {
+    pub first_payment: PaymentSplit,
+    pub last_payment: PaymentSplit,
+}
+
+/// How one payment divides between interest and principal
+#[derive(Serialize)]
+pub struct PaymentSplit {
+    pub interest: f64,
+    pub principal: f64,
+}
+
+impl Output for LoanPaymentResult {
+    const SCHEMA: &'static str = r##"{
+        "type": "object",
+        "properties": {
+            "result": {"type": "number", "description": "Monthly payment"},
+            "total_paid": {"type": "number"},
+            "total_interest": {"type": "number"},
+            "amortization": {
+                "type": "object",
+                "description": "Present when requested",
+                "properties": {
+                    "first_payment": {"$ref": "#/$defs/payment_split"},
+                    "last_payment": {"$ref": "#/$defs/payment_split"}
+                },
+                "required": ["first_payment", "last_payment"]
+            }
+        },
+        "required": ["result", "total_paid", "total_interest"],
+        "$defs": {
+            "payment_split": {
+                "type": "object",
+                "properties": {
+                    "interest": {"type": "number"},
+                    "principal": {"type": "number"}
+                },
+                "required": ["interest", "principal"]
+            }
+        }
+    }"##;
+}
+
+/// A duration split into units, all carrying the duration's sign
+#[derive(Serialize)]
+pub struct DurationParts {
+    pub days: i64,
+    pub hours: i64,
+    pub minutes: i64,
+    pub seconds: i64,
+}
+
+/// Signed seconds from `start` to `end`
+#[derive(Serialize)]
+pub struct DurationBetweenResult {
+    pub result: i64,
+    pub duration: DurationParts,
+    pub human: String,
+}
+
+impl Output for DurationBetweenResult {
+    const SCHEMA: &'static str = r##"{
+        "type": "object",
+        "properties": {
+            "result": {"type": "integer", "description": "Seconds from start to end; negative if end is earlier"},
+            "duration": {"$ref": "#/$defs/duration_parts"},
+            "human": {"type": "string", "description": "e.g. \"3 days, 4 hours\""}
+        },
+        "required": ["result", "duration", "human"],
+        "$defs": {
+            "duration_parts": {
+                "type": "object",
+                "properties": {
+                    "days": {"type": "integer"},
+                    "hours": {"type": "integer"},
+                    "minutes": {"type": "integer"},
+                    "seconds": {"type": "integer"}
+                },
+                "required": ["days", "hours", "minutes", "seconds"]
+            }
+        }
+    }"##;
+}
+
+/// The timestamp reached by adding a duration
+#[derive(Serialize)]
+pub struct AddDurationResult {
+    pub result: i64,
+    pub added_seconds: i64,
+    pub added: DurationParts,
+    pub human: String,
+}
+
+impl Output for AddDurationResult {
+    const SCHEMA: &'static str = r##"{
+        "type": "object",
+        "properties": {
+            "result": {"type": "integer", "description": "Resulting Unix timestamp in seconds"},
+            "added_seconds": {"type": "integer"},
+            "added": {"$ref": "#/$defs/duration_parts", "description": "The added duration, normalized"},
+            "human": {"type": "string", "description": "e.g. \"3 days, 4 hours\""}
+        },
+        "required": ["result", "added_seconds", "added", "human"],
+        "$defs": {
+            "duration_parts": {
+                "type": "object",
+                "properties": {
+                    "days": {"type": "integer"},
+                    "hours": {"type": "integer"},
+                    "minutes": {"type": "integer"},
+                    "seconds": {"type": "integer"}
+                },
+                "required": ["days", "hours", "minutes", "seconds"]
+            }
+        }
+    }"##;
+}
+
+/// One number, or an array of them when a count was requested
+#[derive(Serialize)]
+pub struct RandomNumberResult {
+    pub result: serde_json::Value,
+}
+
+impl Output for RandomNumberResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "result": {
+                "oneOf": [
+                    {"type": "number"},
+                    {"type": "array", "items": {"type": "number"}}
+                ]
+            }
+        },
+        "required": ["result"]
+    }"#;
+}
+
+/// The total of a named accumulator
+#[derive(Serialize)]
+pub struct AccumulatorResult {
+    pub id: String,
+    pub result: f64,
+    /// Total before a reset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<f64>,
+}
+
+impl Output for AccumulatorResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "id": {"type": "string"},
+            "result": {"type": "number", "description": "Current total"},
+            "previous": {"type": "number", "description": "Total before the reset; present for accumulator_reset"}
+        },
+        "required": ["id", "result"]
+    }"#;
+}
+
+#[derive(Serialize)]
+pub struct BatchResult {
+    pub results: Vec<BatchEntry>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+/// Outcome of one batch item: either `result` or `error` is set
+#[derive(Serialize)]
+pub struct BatchEntry {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl Output for BatchResult {
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "properties": {
+            "results": {
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": {
+                        "index": {"type": "integer", "minimum": 0},
+                        "result": {"type": "number"},
+                        "error": {"type": "string"}
+                    },
+                    "required": ["index"]
+                }
+            },
+            "succeeded": {"type": "integer", "minimum": 0},
+            "failed": {"type": "integer", "minimum": 0}
+        },
+        "required": ["results", "succeeded", "failed"]
+    }"#;
+}
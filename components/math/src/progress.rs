@@ -0,0 +1,37 @@
+//! MCP progress notifications
+//!
+//! Long-running tools report progress by writing `notifications/progress`
+//! messages to the client stream passed to `call_tool`. The stream carries the
+//! transport's server-sent event stream, so each message is framed as one
+//! `data:` event. Notifications are best effort: a missing stream or a failed
+//! write never fails the tool call.
+
+use serde_json::json;
+
+use crate::args::ProgressToken;
+use crate::bindings::wasi::io::streams::OutputStream;
+
+/// Send a progress notification for the request identified by `token`.
+/// Does nothing when the client did not provide a stream.
+pub fn notify_progress(
+    stream: Option<&OutputStream>,
+    token: &ProgressToken,
+    progress: u64,
+    total: u64,
+) {
+    let Some(stream) = stream else {
+        return;
+    };
+
+    let notification = json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/progress",
+        "params": {
+            "progressToken": token,
+            "progress": progress,
+            "total": total,
+        },
+    });
+    let event = format!("data: {}\n\n", notification);
+    let _ = stream.blocking_write_and_flush(event.as_bytes());
+}
@@ -0,0 +1,153 @@
+//! Validation of tool arguments against the tools' declared input schemas
+//!
+//! `call_tool` checks the arguments against the `input_schema` advertised in
+//! `list_tools` before dispatching, so a mismatch is reported in terms of the
+//! schema the client was given. Only the keywords the calculator's schemas
+//! use are supported: `type`, `enum`, `minimum`, `maximum`, `properties`,
+//! `required`, `items`, `minItems`, `maxItems` and `oneOf`. Unknown keywords
+//! are ignored; undeclared top-level arguments are rejected separately by
+//! [`crate::args::Args::check_keys`].
+//!
+//! As in [`crate::args`], a `null` property is treated as not given.
+
+use serde_json::Value;
+
+use crate::args::describe;
+
+/// Check `value` against `schema`, naming the offending JSON pointer on failure
+pub fn validate(schema: &Value, value: &Value) -> Result<(), String> {
+    check(schema, value, "")
+}
+
+fn check(schema: &Value, value: &Value, pointer: &str) -> Result<(), String> {
+    // Messages name the root as "/" rather than an empty pointer
+    let at = if pointer.is_empty() { "/" } else { pointer };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            return Err(format!(
+                "{}: expected {}, got {}",
+                at,
+                types.join(" or "),
+                describe(value)
+            ));
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            return Err(format!(
+                "{}: expected one of {}, got {}",
+                at,
+                allowed.join(", "),
+                describe(value)
+            ));
+        }
+    }
+
+    if let Some(n) = value.as_f64() {
+        if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+            if n < min {
+                return Err(format!("{}: {} is less than the minimum of {}", at, n, min));
+            }
+        }
+        if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+            if n > max {
+                return Err(format!(
+                    "{}: {} is greater than the maximum of {}",
+                    at, n, max
+                ));
+            }
+        }
+    }
+
+    if let Value::Array(items) = value {
+        if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+            if (items.len() as u64) < min {
+                return Err(format!(
+                    "{}: expected at least {} items, got {}",
+                    at,
+                    min,
+                    items.len()
+                ));
+            }
+        }
+        if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+            if items.len() as u64 > max {
+                return Err(format!(
+                    "{}: expected at most {} items, got {}",
+                    at,
+                    max,
+                    items.len()
+                ));
+            }
+        }
+        if let Some(item_schema) = schema.get("items") {
+            for (i, item) in items.iter().enumerate() {
+                check(item_schema, item, &format!("{}/{}", pointer, i))?;
+            }
+        }
+    }
+
+    if let Value::Object(map) = value {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if matches!(map.get(name), None | Some(Value::Null)) {
+                    return Err(format!("{}: missing required property '{}'", at, name));
+                }
+            }
+        }
+        if let Some(Value::Object(properties)) = schema.get("properties") {
+            for (name, property_schema) in properties {
+                match map.get(name) {
+                    None | Some(Value::Null) => {}
+                    Some(property) => {
+                        check(property_schema, property, &child_pointer(pointer, name))?
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(Value::Array(options)) = schema.get("oneOf") {
+        let matching = options
+            .iter()
+            .filter(|option| check(option, value, pointer).is_ok())
+            .count();
+        if matching != 1 {
+            return Err(format!(
+                "{}: expected exactly one of {} alternatives to match, got {} matching {}",
+                at,
+                options.len(),
+                matching,
+                describe(value)
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "number" => value.is_number(),
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+/// JSON pointer (RFC 6901) of a property below `pointer`
+fn child_pointer(pointer: &str, name: &str) -> String {
+    format!("{}/{}", pointer, name.replace('~', "~0").replace('/', "~1"))
+}
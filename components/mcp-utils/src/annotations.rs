@@ -0,0 +1,116 @@
+//! Builder for `ToolAnnotations`
+//!
+//! Like the result builders, `ToolAnnotations` is generated separately in
+//! each component, so the builder is a macro expanded inside the component.
+//! The hints themselves are described by the plain [`ToolHints`] struct,
+//! which tools adjust from [`ToolHints::SAFE`]:
+//!
+//! ```ignore
+//! use bindings::wasmcp::protocol::mcp::*;
+//! use mcp_utils::annotations::ToolHints;
+//!
+//! mcp_utils::annotation_builder!();
+//! use annotation_builder::build_annotations;
+//!
+//! let annotations = build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE });
+//! ```
+//!
+//! The expansion is a private `annotation_builder` module, which must be
+//! placed where `ToolAnnotations` is in scope.
+
+/// The MCP behaviour hints of one tool
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ToolHints {
+    /// The tool does not modify its environment
+    pub read_only: bool,
+    /// A modifying tool may destroy data rather than only add to it
+    pub destructive: bool,
+    /// Repeating a call with the same arguments has no further effect
+    pub idempotent: bool,
+    /// The tool reaches entities outside the server, such as the network
+    pub open_world: bool,
+}
+
+impl ToolHints {
+    /// A tool that only computes a result from its arguments
+    pub const SAFE: ToolHints = ToolHints {
+        read_only: true,
+        destructive: false,
+        idempotent: true,
+        open_world: false,
+    };
+}
+
+/// Define the `annotation_builder` module with
+/// `build_annotations(hints) -> ToolAnnotations`, which sets every hint and
+/// leaves the title to `ToolOptions`
+#[macro_export]
+macro_rules! annotation_builder {
+    () => {
+        mod annotation_builder {
+            use super::*;
+
+            pub fn build_annotations(hints: $crate::annotations::ToolHints) -> ToolAnnotations {
+                ToolAnnotations {
+                    title: None,
+                    read_only_hint: Some(hints.read_only),
+                    destructive_hint: Some(hints.destructive),
+                    idempotent_hint: Some(hints.idempotent),
+                    open_world_hint: Some(hints.open_world),
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ToolHints;
+
+    /// Stand-in for the generated binding type
+    #[derive(Debug, PartialEq)]
+    struct ToolAnnotations {
+        title: Option<String>,
+        read_only_hint: Option<bool>,
+        destructive_hint: Option<bool>,
+        idempotent_hint: Option<bool>,
+        open_world_hint: Option<bool>,
+    }
+
+    crate::annotation_builder!();
+    use annotation_builder::build_annotations;
+
+    #[test]
+    fn safe_tools_are_read_only_and_idempotent() {
+        assert_eq!(
+            build_annotations(ToolHints::SAFE),
+            ToolAnnotations {
+                title: None,
+                read_only_hint: Some(true),
+                destructive_hint: Some(false),
+                idempotent_hint: Some(true),
+                open_world_hint: Some(false),
+            }
+        );
+    }
+
+    #[test]
+    fn every_hint_is_set_from_its_field() {
+        let hints = ToolHints {
+            read_only: false,
+            destructive: true,
+            idempotent: false,
+            open_world: true,
+        };
+        assert_eq!(
+            build_annotations(hints),
+            ToolAnnotations {
+                title: None,
+                read_only_hint: Some(false),
+                destructive_hint: Some(true),
+                idempotent_hint: Some(false),
+                open_world_hint: Some(true),
+            }
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Argument parsing for `call_tool`
+//!
+//! Tool arguments arrive as a JSON object string. Every helper parses it and
+//! reports failures in the same words across components:
+//!
+//! ```text
+//! Missing arguments
+//! Invalid JSON arguments: expected value at line 1 column 1
+//! Missing or invalid parameter 'text'
+//! Invalid parameter 'mode': invalid type: integer `1`, expected a string
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Parse the raw arguments into JSON
+pub fn parse_json(arguments: &Option<String>) -> Result<Value, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))
+}
+
+/// A required parameter of any deserializable type
+pub fn parse_arg<T: DeserializeOwned>(arguments: &Option<String>, arg_name: &str) -> Result<T, String> {
+    let json = parse_json(arguments)?;
+
+    json.get(arg_name)
+        .and_then(|value| T::deserialize(value).ok())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", arg_name))
+}
+
+/// An optional parameter; absent and `null` are both treated as not given
+pub fn parse_optional_arg<T: DeserializeOwned>(
+    arguments: &Option<String>,
+    arg_name: &str,
+) -> Result<Option<T>, String> {
+    let json = parse_json(arguments)?;
+
+    match json.get(arg_name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => T::deserialize(value)
+            .map(Some)
+            .map_err(|e| format!("Invalid parameter '{}': {}", arg_name, e)),
+    }
+}
+
+/// The `text` parameter taken by most string tools
+pub fn parse_text_arg(arguments: &Option<String>) -> Result<String, String> {
+    parse_arg(arguments, "text")
+}
+
+/// Two required numeric parameters, such as the `a` and `b` of a binary operation
+pub fn parse_two_number_args(arguments: &Option<String>, first: &str, second: &str) -> Result<(f64, f64), String> {
+    Ok((parse_arg(arguments, first)?, parse_arg(arguments, second)?))
+}
+
+/// Two required string parameters, such as the `message` and `key` of a MAC
+pub fn parse_two_string_args(arguments: &Option<String>, first: &str, second: &str) -> Result<(String, String), String> {
+    Ok((parse_arg(arguments, first)?, parse_arg(arguments, second)?))
+}
@@ -0,0 +1,18 @@
+//! Helpers shared by the tool components
+//!
+//! A plain library crate: it has no WIT world of its own and is linked into
+//! each component that depends on it by path. Argument parsing lives in
+//! [`args`], `list_tools` paging in [`pagination`] and the `TOOL_FILTER`
+//! selection in [`tool_filter`]. Result builders and the annotation builder
+//! are expanded into each component by [`result_builders!`] (see
+//! [`result_builder`]) and [`annotation_builder!`] (see [`annotations`]).
+
+pub mod annotations;
+pub mod args;
+pub mod pagination;
+pub mod result_builder;
+pub mod tool_filter;
+
+/// Used by the code [`result_builders!`] expands to
+#[doc(hidden)]
+pub use serde_json;
@@ -0,0 +1,161 @@
+//! Cursor-based pagination of `list_tools` results
+//!
+//! A cursor is the offset of the next page, base64-encoded so that clients
+//! treat it as opaque. A plain base-10 offset is accepted as well, since
+//! that is what cursors were before they were encoded. A cursor that fails
+//! to decode, or that points at or past the end of the list (for example
+//! after the tool set shrank), is treated as stale and the first page is
+//! returned again.
+//!
+//! In a composed server every component receives the same cursor but pages
+//! its own list, so a cursor issued by one component means nothing to the
+//! others. [`DEFAULT_PAGE_SIZE`] is therefore well above the number of tools
+//! any component has, so that each one fits in a single page and no cursor
+//! is issued. Paging past the first page therefore only happens with a
+//! smaller page size, as in this module's tests.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+
+/// Page size that keeps every component's tools on one page
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+
+/// Prefix inside the encoded cursor, so that unrelated base64 strings are rejected
+const CURSOR_PREFIX: &str = "offset:";
+
+/// Return the page of `items` starting at `cursor`, and the cursor of the next
+/// page if more items remain. An invalid or stale cursor yields the first page.
+pub fn paginate<T>(
+    items: Vec<T>,
+    cursor: Option<&str>,
+    page_size: usize,
+) -> (Vec<T>, Option<String>) {
+    let offset = cursor
+        .and_then(decode_cursor)
+        .filter(|&offset| offset < items.len())
+        .unwrap_or(0);
+    let end = offset.saturating_add(page_size.max(1)).min(items.len());
+    let next_cursor = (end < items.len()).then(|| encode_cursor(end));
+
+    let page = items.into_iter().skip(offset).take(end - offset).collect();
+    (page, next_cursor)
+}
+
+fn encode_cursor(offset: usize) -> String {
+    STANDARD.encode(format!("{}{}", CURSOR_PREFIX, offset))
+}
+
+fn decode_cursor(cursor: &str) -> Option<usize> {
+    if let Ok(offset) = cursor.parse() {
+        return Some(offset);
+    }
+    let bytes = STANDARD.decode(cursor).ok()?;
+    let text = String::from_utf8(bytes).ok()?;
+    text.strip_prefix(CURSOR_PREFIX)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tool counts of the components in the multi-tools composition
+    const COMPOSED_SIZES: [usize; 7] = [32, 3, 26, 25, 4, 5, 3];
+
+    #[test]
+    fn pages_follow_opaque_cursors_to_the_end() {
+        let items: Vec<usize> = (0..25).collect();
+        let mut cursor = None;
+        let mut seen = Vec::new();
+
+        loop {
+            let (page, next) = paginate(items.clone(), cursor.as_deref(), 10);
+            assert!(page.len() <= 10);
+            seen.extend(page);
+            match next {
+                Some(next) => {
+                    assert!(
+                        next.parse::<usize>().is_err(),
+                        "cursor {} is a bare offset",
+                        next
+                    );
+                    cursor = Some(next);
+                }
+                None => break,
+            }
+        }
+        assert_eq!(seen, items);
+    }
+
+    #[test]
+    fn decimal_offsets_are_accepted_as_cursors() {
+        let items: Vec<usize> = (0..5).collect();
+        assert_eq!(
+            paginate(items.clone(), Some("2"), 2),
+            (vec![2, 3], Some(encode_cursor(4)))
+        );
+        assert_eq!(
+            paginate(items, Some(&encode_cursor(2)), 2),
+            (vec![2, 3], Some(encode_cursor(4)))
+        );
+    }
+
+    #[test]
+    fn invalid_and_stale_cursors_return_the_first_page() {
+        let items: Vec<usize> = (0..5).collect();
+        let first_page = (vec![0, 1], Some(encode_cursor(2)));
+        assert_eq!(paginate(items.clone(), Some("not a cursor"), 2), first_page);
+        assert_eq!(paginate(items.clone(), Some("b2Zmc2V0Og=="), 2), first_page);
+        assert_eq!(paginate(items.clone(), Some("-1"), 2), first_page);
+        assert_eq!(paginate(items.clone(), Some("5"), 2), first_page);
+        assert_eq!(
+            paginate(items.clone(), Some(&encode_cursor(5)), 2),
+            first_page
+        );
+        assert_eq!(
+            paginate(items.clone(), Some(&encode_cursor(50)), 2),
+            first_page
+        );
+        assert_eq!(paginate(items, Some(&encode_cursor(4)), 2), (vec![4], None));
+    }
+
+    #[test]
+    fn composed_list_has_no_duplicates() {
+        // Every component sees the same request, as in a composed chain, and
+        // the merged page is the concatenation of their pages
+        let components: Vec<Vec<String>> = COMPOSED_SIZES
+            .iter()
+            .enumerate()
+            .map(|(c, &size)| {
+                (0..size)
+                    .map(|t| format!("component{}_tool{}", c, t))
+                    .collect()
+            })
+            .collect();
+
+        let mut cursor: Option<String> = None;
+        let mut listed = Vec::new();
+        for _ in 0..COMPOSED_SIZES.iter().sum::<usize>() {
+            let mut next = None;
+            for tools in &components {
+                let (page, component_next) =
+                    paginate(tools.clone(), cursor.as_deref(), DEFAULT_PAGE_SIZE);
+                listed.extend(page);
+                next = next.or(component_next);
+            }
+            cursor = next;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        let mut unique = listed.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(
+            unique.len(),
+            listed.len(),
+            "duplicate tools in the composed list"
+        );
+        assert_eq!(listed.len(), COMPOSED_SIZES.iter().sum::<usize>());
+    }
+}
@@ -0,0 +1,91 @@
+//! Builders for `call_tool` results
+//!
+//! `CallToolResult` and its content types are generated separately in each
+//! component by `wit_bindgen::generate!`, so a library cannot name them. The
+//! builders are therefore written once here as a macro and expanded inside
+//! each component, where they pick up that component's binding types:
+//!
+//! ```ignore
+//! use bindings::wasmcp::protocol::mcp::*;
+//!
+//! mcp_utils::result_builders!();
+//! use result_builder::{error_result, success_result};
+//! ```
+//!
+//! The expansion is a private `result_builder` module, which must be placed
+//! where `CallToolResult`, `ContentBlock`, `TextContent`, `TextData`,
+//! `ErrorCode` and `Error` are in scope. Builders a component does not use
+//! are not reported as dead code.
+
+/// Define the `result_builder` module with the result builders:
+///
+/// - `success_result(text)`: plain text result
+/// - `error_result(message)`: text result flagged with `is_error`
+/// - `structured_success_result(text, value)`: text plus `structured_content`
+/// - `structured_json_result(text, json)`: the same with `structured_content`
+///   already serialized
+/// - `json_result(value)`: `value` as both the text and `structured_content`
+/// - `number_result(n)`: `n` as text and as `{"result": n}`
+/// - `invalid_params(message)`: an `invalid-params` request error, for
+///   `list_tools` failures such as a bad cursor
+#[macro_export]
+macro_rules! result_builders {
+    () => {
+        #[allow(dead_code)]
+        mod result_builder {
+            use super::*;
+
+            pub fn success_result(result: String) -> CallToolResult {
+                text_result(result, None, None)
+            }
+
+            pub fn error_result(message: String) -> CallToolResult {
+                text_result(message, Some(true), None)
+            }
+
+            pub fn structured_success_result(result: String, structured: $crate::serde_json::Value) -> CallToolResult {
+                text_result(result, None, Some(structured.to_string()))
+            }
+
+            /// For structured content a `serde_json::Value` cannot hold, such
+            /// as integers above 64 bits, serialized straight from the type
+            pub fn structured_json_result(result: String, structured: String) -> CallToolResult {
+                text_result(result, None, Some(structured))
+            }
+
+            /// The text is the compact JSON, so clients without structured
+            /// content support still see the whole result
+            pub fn json_result(value: $crate::serde_json::Value) -> CallToolResult {
+                let json = value.to_string();
+                text_result(json.clone(), None, Some(json))
+            }
+
+            /// The text stays a bare number so that callers further up a
+            /// middleware chain can keep parsing it
+            pub fn number_result(result: f64) -> CallToolResult {
+                structured_success_result(result.to_string(), $crate::serde_json::json!({ "result": result }))
+            }
+
+            pub fn invalid_params(message: String) -> ErrorCode {
+                ErrorCode::InvalidParams(Error {
+                    id: None,
+                    code: -32602,
+                    message,
+                    data: None,
+                })
+            }
+
+            fn text_result(text: String, is_error: Option<bool>, structured_content: Option<String>) -> CallToolResult {
+                CallToolResult {
+                    content: vec![ContentBlock::Text(TextContent {
+                        text: TextData::Text(text),
+                        options: None,
+                    })],
+                    is_error,
+                    meta: None,
+                    structured_content,
+                }
+            }
+        }
+    };
+}
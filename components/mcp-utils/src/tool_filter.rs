@@ -0,0 +1,54 @@
+//! Selection of the tools a component exposes
+//!
+//! The `TOOL_FILTER` environment variable trims the tool surface without code
+//! changes. It is a comma-separated list of tool names: plain names expose
+//! only the listed tools, and names prefixed with `-` hide a tool, for example
+//! `TOOL_FILTER="-base64_decode"`. Hidden tools are left out of `list_tools`
+//! and reported as unknown by `call_tool`, so the composed server behaves as
+//! if they did not exist. Without the variable every tool is exposed.
+//!
+//! The variable is shared by every component in the composition, so names of
+//! other components' tools may appear in it.
+
+const TOOL_FILTER_ENV: &str = "TOOL_FILTER";
+
+/// Whether `tool` should be listed and callable
+pub fn is_exposed(tool: &str) -> bool {
+    match std::env::var(TOOL_FILTER_ENV) {
+        Ok(spec) => allows(&spec, tool),
+        Err(_) => true,
+    }
+}
+
+fn allows(spec: &str, tool: &str) -> bool {
+    let entries: Vec<&str> = spec.split(',').map(str::trim).filter(|e| !e.is_empty()).collect();
+
+    let hidden = entries.iter().any(|e| e.strip_prefix('-') == Some(tool));
+    let allowlist: Vec<&str> = entries.iter().copied().filter(|e| !e.starts_with('-')).collect();
+
+    !hidden && (allowlist.is_empty() || allowlist.contains(&tool))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn without_entries_every_tool_is_exposed() {
+        assert!(allows("", "mean"));
+        assert!(allows(" , ", "mean"));
+    }
+
+    #[test]
+    fn hidden_tools_are_not_exposed() {
+        assert!(!allows("-base64_decode", "base64_decode"));
+        assert!(allows("-base64_decode", "base64_encode"));
+    }
+
+    #[test]
+    fn an_allowlist_exposes_only_the_listed_tools() {
+        assert!(allows("mean, sum", "sum"));
+        assert!(!allows("mean, sum", "count"));
+        assert!(!allows("mean,-mean", "mean"));
+    }
+}
@@ -37,6 +37,14 @@ use bindings::wasi::io::streams::OutputStream;
 use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasmcp::protocol::server_messages::Context;
 use bindings::wasmcp::server::handler as downstream; // Downstream handler chain
+use mcp_utils::annotations::ToolHints;
+use mcp_utils::args::parse_two_number_args;
+
+mcp_utils::result_builders!();
+use result_builder::{error_result, number_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
 
 struct PythagoreanMiddleware;
 
@@ -98,11 +106,11 @@ fn handle_tools_list(
         .to_string(),
         options: Some(ToolOptions {
             meta: None,
-            annotations: None,
+            annotations: Some(build_annotations(ToolHints::SAFE)),
             description: Some(
                 "Calculate the hypotenuse of a right triangle using the Pythagorean theorem (c = √(a² + b²))".to_string(),
             ),
-            output_schema: None,
+            output_schema: Some(NUMBER_OUTPUT_SCHEMA.to_string()),
             title: Some("Pythagorean Theorem".to_string()),
         }),
     };
@@ -142,7 +150,7 @@ fn handle_pythagorean_call(
     client_stream: Option<&OutputStream>,
 ) -> Result<ServerResponse, ErrorCode> {
     // Parse arguments
-    let (a, b) = match parse_pythagorean_args(&request.arguments) {
+    let (a, b) = match parse_two_number_args(&request.arguments, "a", "b") {
         Ok(values) => values,
         Err(msg) => {
             return Ok(ServerResponse::ToolsCall(error_result(msg)));
@@ -183,9 +191,7 @@ fn handle_pythagorean_call(
     match call_downstream_tool(ctx, &sqrt_req, &id, client_stream) {
         Ok(hypotenuse) => {
             // Return the hypotenuse as the result
-            Ok(ServerResponse::ToolsCall(success_result(
-                hypotenuse.to_string(),
-            )))
+            Ok(ServerResponse::ToolsCall(number_result(hypotenuse)))
         }
         Err(msg) => Ok(ServerResponse::ToolsCall(error_result(msg))),
     }
@@ -220,27 +226,6 @@ fn call_downstream_tool(
 }
 
 /// Parse pythagorean arguments (a, b)
-fn parse_pythagorean_args(arguments: &Option<String>) -> Result<(f64, f64), String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
-
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
-
-    let a = json
-        .get("a")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'a'".to_string())?;
-
-    let b = json
-        .get("b")
-        .and_then(|v| v.as_f64())
-        .ok_or_else(|| "Missing or invalid parameter 'b'".to_string())?;
-
-    Ok((a, b))
-}
-
 /// Extract a numeric value from a CallToolResult
 fn extract_number_from_result(result: &CallToolResult) -> Result<f64, String> {
     // Check if it's an error result
@@ -264,28 +249,13 @@ fn extract_number_from_result(result: &CallToolResult) -> Result<f64, String> {
     }
 }
 
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
-    }
-}
-
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
-    }
-}
+/// Shape of the `structured_content` returned alongside the text result
+const NUMBER_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "result": {"type": "number"}
+    },
+    "required": ["result"]
+}"#;
 
 bindings::export!(PythagoreanMiddleware with_types_in bindings);
@@ -0,0 +1,320 @@
+//! regex-tools Tools Capability Provider
+//!
+//! A tools capability that provides regular expression operations, using the
+//! `regex` crate's syntax: matching runs in linear time, so there is no
+//! look-around and no backreferences.
+//!
+//! Which tools are exposed is configured by [`tool_filter`], and `list_tools`
+//! returns them in pages (see [`pagination`]).
+
+mod bindings {
+    wit_bindgen::generate!({
+        world: "regex-tools",
+        generate_all,
+    });
+}
+
+use bindings::exports::wasmcp::protocol::tools::Guest;
+use bindings::wasmcp::protocol::mcp::*;
+use bindings::wasi::io::streams::OutputStream;
+use mcp_utils::annotations::ToolHints;
+use mcp_utils::args::{parse_arg, parse_optional_arg, parse_text_arg};
+use mcp_utils::{pagination, tool_filter};
+
+mcp_utils::result_builders!();
+use result_builder::{error_result, structured_success_result, success_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
+
+struct RegexTools;
+
+impl Guest for RegexTools {
+    fn list_tools(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: ListToolsRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Result<ListToolsResult, ErrorCode> {
+        let exposed: Vec<Tool> = vec![
+            Tool {
+                name: "regex_match".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to search"},
+                        "pattern": {"type": "string", "description": "Regular expression (Rust regex syntax; no look-around or backreferences)"},
+                        "flags": {"type": "string", "description": "Any of i (case-insensitive), m (^ and $ match at line breaks), s (. matches newlines), x (ignore whitespace in the pattern)"}
+                    },
+                    "required": ["text", "pattern"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Check whether a regular expression matches anywhere in the text, returning \"true\" or \"false\". Anchor the pattern with ^ and $ to require a whole-text match".to_string()),
+                    output_schema: None,
+                    title: Some("Regex Match".to_string()),
+                }),
+            },
+            Tool {
+                name: "regex_find".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to search"},
+                        "pattern": {"type": "string", "description": "Regular expression (Rust regex syntax; no look-around or backreferences)"},
+                        "flags": {"type": "string", "description": "Any of i (case-insensitive), m (^ and $ match at line breaks), s (. matches newlines), x (ignore whitespace in the pattern)"}
+                    },
+                    "required": ["text", "pattern"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Return the first match of a regular expression in the text, or an empty string when there is none".to_string()),
+                    output_schema: None,
+                    title: Some("Regex Find".to_string()),
+                }),
+            },
+            Tool {
+                name: "regex_replace".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to search"},
+                        "pattern": {"type": "string", "description": "Regular expression (Rust regex syntax; no look-around or backreferences)"},
+                        "replacement": {"type": "string", "description": "Replacement text; $1 or ${name} insert capture groups, $$ inserts a literal $"},
+                        "flags": {"type": "string", "description": "Any of i (case-insensitive), m (^ and $ match at line breaks), s (. matches newlines), x (ignore whitespace in the pattern)"}
+                    },
+                    "required": ["text", "pattern", "replacement"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Replace every match of a regular expression. The replacement can refer to capture groups as $1 or ${name}; write ${1}x rather than $1x when a group number is followed by letters or digits".to_string()),
+                    output_schema: None,
+                    title: Some("Regex Replace".to_string()),
+                }),
+            },
+            Tool {
+                name: "regex_find_all".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to search"},
+                        "pattern": {"type": "string", "description": "Regular expression (Rust regex syntax; no look-around or backreferences)"},
+                        "flags": {"type": "string", "description": "Any of i (case-insensitive), m (^ and $ match at line breaks), s (. matches newlines), x (ignore whitespace in the pattern)"}
+                    },
+                    "required": ["text", "pattern"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some(format!(
+                        "Return every non-overlapping match of a regular expression as a JSON array of strings, up to {} matches",
+                        MAX_MATCHES
+                    )),
+                    output_schema: Some(REGEX_FIND_ALL_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Regex Find All".to_string()),
+                }),
+            },
+            Tool {
+                name: "regex_capture_groups".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to search"},
+                        "pattern": {"type": "string", "description": "Regular expression (Rust regex syntax; no look-around or backreferences)"},
+                        "flags": {"type": "string", "description": "Any of i (case-insensitive), m (^ and $ match at line breaks), s (. matches newlines), x (ignore whitespace in the pattern)"}
+                    },
+                    "required": ["text", "pattern"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some(format!(
+                        "Return the capture groups of every match as a JSON array of arrays: the full match first, then each group by index, with null for a group that did not participate. Named groups keep their index; the structured result lists the names. Up to {} matches",
+                        MAX_MATCHES
+                    )),
+                    output_schema: Some(REGEX_CAPTURE_GROUPS_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Regex Capture Groups".to_string()),
+                }),
+            },
+        ]
+        .into_iter()
+        .filter(|tool| tool_filter::is_exposed(&tool.name))
+        .collect();
+        let (tools, next_cursor) =
+            pagination::paginate(exposed, request.cursor.as_deref(), TOOLS_PAGE_SIZE);
+
+        Ok(ListToolsResult {
+            tools,
+            next_cursor,
+            meta: None,
+        })
+    }
+
+    fn call_tool(
+        _ctx: bindings::wasmcp::protocol::server_messages::Context,
+        request: CallToolRequest,
+        _client_stream: Option<&OutputStream>,
+    ) -> Option<CallToolResult> {
+        if !tool_filter::is_exposed(&request.name) {
+            return None;
+        }
+
+        match request.name.as_str() {
+            "regex_match" => Some(execute_regex_match(&request.arguments)),
+            "regex_find" => Some(execute_regex_find(&request.arguments)),
+            "regex_replace" => Some(execute_regex_replace(&request.arguments)),
+            "regex_find_all" => Some(execute_regex_find_all(&request.arguments)),
+            "regex_capture_groups" => Some(execute_regex_capture_groups(&request.arguments)),
+            _ => None, // We don't handle this tool
+        }
+    }
+}
+
+/// Largest number of tools returned by one `list_tools` call
+const TOOLS_PAGE_SIZE: usize = pagination::DEFAULT_PAGE_SIZE;
+
+/// Upper bound on the compiled size of a pattern, in bytes
+const MAX_REGEX_SIZE: usize = 1 << 20;
+
+/// Most matches `regex_find_all` and `regex_capture_groups` return
+const MAX_MATCHES: usize = 10_000;
+
+fn execute_regex_match(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_and_regex(arguments) {
+        Ok((text, regex)) => success_result(regex.is_match(&text).to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_regex_find(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_and_regex(arguments) {
+        Ok((text, regex)) => success_result(regex.find(&text).map(|m| m.as_str()).unwrap_or_default().to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_regex_replace(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_text_and_regex(arguments)
+        .and_then(|(text, regex)| Ok((text, regex, parse_arg::<String>(arguments, "replacement")?)));
+
+    match parsed {
+        Ok((text, regex, replacement)) => success_result(regex.replace_all(&text, replacement.as_str()).into_owned()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Shape of the `structured_content` returned by `regex_find_all`
+const REGEX_FIND_ALL_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "matches": {"type": "array", "items": {"type": "string"}},
+        "count": {"type": "integer", "minimum": 0},
+        "truncated": {"type": "boolean", "description": "True when more matches exist than were returned"}
+    },
+    "required": ["matches", "count", "truncated"]
+}"#;
+
+fn execute_regex_find_all(arguments: &Option<String>) -> CallToolResult {
+    let (text, regex) = match parse_text_and_regex(arguments) {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    let mut found = regex.find_iter(&text);
+    let matches: Vec<&str> = found.by_ref().take(MAX_MATCHES).map(|m| m.as_str()).collect();
+    let truncated = found.next().is_some();
+
+    structured_success_result(
+        serde_json::json!(matches).to_string(),
+        serde_json::json!({
+            "count": matches.len(),
+            "matches": matches,
+            "truncated": truncated,
+        }),
+    )
+}
+
+/// Shape of the `structured_content` returned by `regex_capture_groups`
+const REGEX_CAPTURE_GROUPS_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "matches": {
+            "type": "array",
+            "items": {"type": "array", "items": {"type": ["string", "null"]}},
+            "description": "Per match, the full match followed by groups 1..n; null for groups that did not participate"
+        },
+        "names": {
+            "type": "array",
+            "items": {"type": ["string", "null"]},
+            "description": "Name of the group at each index, null for unnamed groups and the full match"
+        },
+        "count": {"type": "integer", "minimum": 0},
+        "truncated": {"type": "boolean", "description": "True when more matches exist than were returned"}
+    },
+    "required": ["matches", "names", "count", "truncated"]
+}"#;
+
+fn execute_regex_capture_groups(arguments: &Option<String>) -> CallToolResult {
+    let (text, regex) = match parse_text_and_regex(arguments) {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    let mut captures = regex.captures_iter(&text);
+    let matches: Vec<Vec<Option<&str>>> = captures
+        .by_ref()
+        .take(MAX_MATCHES)
+        .map(|caps| caps.iter().map(|group| group.map(|m| m.as_str())).collect())
+        .collect();
+    let truncated = captures.next().is_some();
+    let names: Vec<Option<&str>> = regex.capture_names().collect();
+
+    structured_success_result(
+        serde_json::json!(matches).to_string(),
+        serde_json::json!({
+            "count": matches.len(),
+            "matches": matches,
+            "names": names,
+            "truncated": truncated,
+        }),
+    )
+}
+
+/// The `text` to search and the compiled `pattern` with its `flags`
+fn parse_text_and_regex(arguments: &Option<String>) -> Result<(String, regex::Regex), String> {
+    let text = parse_text_arg(arguments)?;
+    let pattern: String = parse_arg(arguments, "pattern")?;
+    let flags = parse_optional_arg::<String>(arguments, "flags")?.unwrap_or_default();
+    Ok((text, build_regex(&pattern, &flags)?))
+}
+
+/// Compile `pattern` with single-letter `flags`, reporting syntax errors
+/// and oversized patterns as messages
+fn build_regex(pattern: &str, flags: &str) -> Result<regex::Regex, String> {
+    let mut builder = regex::RegexBuilder::new(pattern);
+    builder.size_limit(MAX_REGEX_SIZE);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            other => {
+                return Err(format!(
+                    "Invalid parameter 'flags': unknown flag '{}', expected any of i, m, s, x",
+                    other
+                ))
+            }
+        };
+    }
+    builder.build().map_err(|e| format!("Invalid regex pattern: {}", e))
+}
+
+bindings::export!(RegexTools with_types_in bindings);
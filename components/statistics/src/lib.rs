@@ -4,6 +4,9 @@
 //! - mean: Calculate average
 //! - sum: Calculate total
 //! - count: Count elements
+//!
+//! Which tools are exposed is configured by [`tool_filter`], and `list_tools`
+//! returns them in pages (see [`pagination`]).
 
 mod bindings {
     wit_bindgen::generate!({
@@ -15,85 +18,100 @@ mod bindings {
 use bindings::exports::wasmcp::protocol::tools::Guest;
 use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasi::io::streams::OutputStream;
+use mcp_utils::annotations::ToolHints;
+use mcp_utils::{pagination, tool_filter};
+
+mcp_utils::result_builders!();
+use result_builder::{error_result, number_result, structured_success_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
 
 struct Statistics;
 
 impl Guest for Statistics {
     fn list_tools(
         _ctx: bindings::wasmcp::protocol::server_messages::Context,
-        _request: ListToolsRequest,
+        request: ListToolsRequest,
         _client_stream: Option<&OutputStream>,
     ) -> Result<ListToolsResult, ErrorCode> {
+        let exposed: Vec<Tool> = vec![
+            Tool {
+                name: "mean".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "numbers": {
+                            "type": "array",
+                            "items": {"type": "number"},
+                            "description": "Array of numbers"
+                        }
+                    },
+                    "required": ["numbers"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Calculate the mean (average) of an array of numbers".to_string()),
+                    output_schema: Some(NUMBER_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Mean (Average)".to_string()),
+                }),
+            },
+            Tool {
+                name: "sum".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "numbers": {
+                            "type": "array",
+                            "items": {"type": "number"},
+                            "description": "Array of numbers"
+                        }
+                    },
+                    "required": ["numbers"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Calculate the sum of an array of numbers".to_string()),
+                    output_schema: Some(NUMBER_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Sum".to_string()),
+                }),
+            },
+            Tool {
+                name: "count".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "numbers": {
+                            "type": "array",
+                            "items": {"type": "number"},
+                            "description": "Array of numbers"
+                        }
+                    },
+                    "required": ["numbers"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Count the number of elements in an array".to_string()),
+                    output_schema: Some(NUMBER_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Count".to_string()),
+                }),
+            },
+        ]
+        .into_iter()
+        .filter(|tool| tool_filter::is_exposed(&tool.name))
+        .collect();
+        let (tools, next_cursor) =
+            pagination::paginate(exposed, request.cursor.as_deref(), TOOLS_PAGE_SIZE);
+
         Ok(ListToolsResult {
-            tools: vec![
-                Tool {
-                    name: "mean".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "numbers": {
-                                "type": "array",
-                                "items": {"type": "number"},
-                                "description": "Array of numbers"
-                            }
-                        },
-                        "required": ["numbers"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Calculate the mean (average) of an array of numbers".to_string()),
-                        output_schema: None,
-                        title: Some("Mean (Average)".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "sum".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "numbers": {
-                                "type": "array",
-                                "items": {"type": "number"},
-                                "description": "Array of numbers"
-                            }
-                        },
-                        "required": ["numbers"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Calculate the sum of an array of numbers".to_string()),
-                        output_schema: None,
-                        title: Some("Sum".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "count".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "numbers": {
-                                "type": "array",
-                                "items": {"type": "number"},
-                                "description": "Array of numbers"
-                            }
-                        },
-                        "required": ["numbers"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Count the number of elements in an array".to_string()),
-                        output_schema: None,
-                        title: Some("Count".to_string()),
-                    }),
-                },
-            ],
-            next_cursor: None,
+            tools,
+            next_cursor,
             meta: None,
         })
     }
@@ -103,6 +121,10 @@ impl Guest for Statistics {
         request: CallToolRequest,
         _client_stream: Option<&OutputStream>,
     ) -> Option<CallToolResult> {
+        if !tool_filter::is_exposed(&request.name) {
+            return None;
+        }
+
         match request.name.as_str() {
             "mean" => Some(execute_mean(&request.arguments)),
             "sum" => Some(execute_sum(&request.arguments)),
@@ -112,6 +134,18 @@ impl Guest for Statistics {
     }
 }
 
+/// Largest number of tools returned by one `list_tools` call
+const TOOLS_PAGE_SIZE: usize = pagination::DEFAULT_PAGE_SIZE;
+
+/// Shape of the `structured_content` every tool returns
+const NUMBER_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "result": {"type": "number"}
+    },
+    "required": ["result"]
+}"#;
+
 fn execute_mean(arguments: &Option<String>) -> CallToolResult {
     match parse_numbers(arguments) {
         Ok(numbers) => {
@@ -120,7 +154,7 @@ fn execute_mean(arguments: &Option<String>) -> CallToolResult {
             }
             let sum: f64 = numbers.iter().sum();
             let mean = sum / numbers.len() as f64;
-            success_result(mean.to_string())
+            number_result(mean)
         }
         Err(msg) => error_result(msg),
     }
@@ -130,7 +164,7 @@ fn execute_sum(arguments: &Option<String>) -> CallToolResult {
     match parse_numbers(arguments) {
         Ok(numbers) => {
             let sum: f64 = numbers.iter().sum();
-            success_result(sum.to_string())
+            number_result(sum)
         }
         Err(msg) => error_result(msg),
     }
@@ -138,9 +172,10 @@ fn execute_sum(arguments: &Option<String>) -> CallToolResult {
 
 fn execute_count(arguments: &Option<String>) -> CallToolResult {
     match parse_numbers(arguments) {
-        Ok(numbers) => {
-            success_result(numbers.len().to_string())
-        }
+        Ok(numbers) => structured_success_result(
+            numbers.len().to_string(),
+            serde_json::json!({ "result": numbers.len() }),
+        ),
         Err(msg) => error_result(msg),
     }
 }
@@ -169,28 +204,41 @@ fn parse_numbers(arguments: &Option<String>) -> Result<Vec<f64>, String> {
     numbers
 }
 
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
+bindings::export!(Statistics with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctx() -> bindings::wasmcp::protocol::server_messages::Context {
+        bindings::wasmcp::protocol::server_messages::Context {
+            claims: None,
+            session_id: None,
+            data: vec![],
+        }
     }
-}
 
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
+    #[test]
+    fn filtered_out_tool_is_not_listed_or_callable() {
+        // The only test in this crate that touches TOOL_FILTER
+        std::env::set_var("TOOL_FILTER", "-count");
+
+        let listed =
+            <Statistics as Guest>::list_tools(ctx(), ListToolsRequest { cursor: None }, None)
+                .unwrap_or_else(|_| panic!("list_tools failed"));
+        let names: Vec<&str> = listed.tools.iter().map(|tool| tool.name.as_str()).collect();
+        assert_eq!(names, ["mean", "sum"]);
+
+        let call = |name: &str| {
+            let request = CallToolRequest {
+                name: name.to_string(),
+                arguments: Some(r#"{"numbers": [1, 2]}"#.to_string()),
+            };
+            <Statistics as Guest>::call_tool(ctx(), request, None)
+        };
+        assert!(call("count").is_none());
+        assert!(call("sum").is_some());
+
+        std::env::remove_var("TOOL_FILTER");
     }
 }
-
-bindings::export!(Statistics with_types_in bindings);
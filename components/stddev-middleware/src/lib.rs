@@ -22,6 +22,13 @@ use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasmcp::protocol::server_messages::Context;
 use bindings::wasmcp::server::handler as downstream;
 use bindings::wasi::io::streams::OutputStream;
+use mcp_utils::annotations::ToolHints;
+
+mcp_utils::result_builders!();
+use result_builder::{error_result, number_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
 
 struct StdDevMiddleware;
 
@@ -96,12 +103,12 @@ fn handle_tools_list(
         .to_string(),
         options: Some(ToolOptions {
             meta: None,
-            annotations: None,
+            annotations: Some(build_annotations(ToolHints::SAFE)),
             description: Some(
                 "Calculate the standard deviation (σ) of an array of numbers: √(variance)"
                     .to_string(),
             ),
-            output_schema: None,
+            output_schema: Some(NUMBER_OUTPUT_SCHEMA.to_string()),
             title: Some("Standard Deviation".to_string()),
         }),
     });
@@ -123,9 +130,9 @@ fn handle_tools_list(
         .to_string(),
         options: Some(ToolOptions {
             meta: None,
-            annotations: None,
+            annotations: Some(build_annotations(ToolHints::SAFE)),
             description: Some("Alias for standard_deviation".to_string()),
-            output_schema: None,
+            output_schema: Some(NUMBER_OUTPUT_SCHEMA.to_string()),
             title: Some("StdDev (alias)".to_string()),
         }),
     });
@@ -155,9 +162,7 @@ fn handle_stddev_call(
         Err(e) => return Ok(ServerResponse::ToolsCall(error_result(e))),
     };
 
-    Ok(ServerResponse::ToolsCall(success_result(
-        stddev.to_string(),
-    )))
+    Ok(ServerResponse::ToolsCall(number_result(stddev)))
 }
 
 fn call_variance_tool(
@@ -226,28 +231,13 @@ fn extract_number_from_result(result: &CallToolResult) -> Result<f64, String> {
     Err("No text content found in result".to_string())
 }
 
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
-    }
-}
-
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
-    }
-}
+/// Shape of the `structured_content` returned alongside the text result
+const NUMBER_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "result": {"type": "number"}
+    },
+    "required": ["result"]
+}"#;
 
 bindings::export!(StdDevMiddleware with_types_in bindings);
@@ -1,6 +1,9 @@
 //! string-utils Tools Capability Provider
 //!
 //! A tools capability that provides string manipulation operations.
+//!
+//! Which tools are exposed is configured by [`tool_filter`], and `list_tools`
+//! returns them in pages (see [`pagination`]).
 
 mod bindings {
     wit_bindgen::generate!({
@@ -12,91 +15,536 @@ mod bindings {
 use bindings::exports::wasmcp::protocol::tools::Guest;
 use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasi::io::streams::OutputStream;
+use mcp_utils::annotations::ToolHints;
+use mcp_utils::args::{parse_arg, parse_optional_arg, parse_text_arg};
+use mcp_utils::{pagination, tool_filter};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use unicode_segmentation::UnicodeSegmentation;
+
+mcp_utils::result_builders!();
+use result_builder::{error_result, json_result, structured_success_result, success_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
 
 struct StringUtils;
 
 impl Guest for StringUtils {
     fn list_tools(
         _ctx: bindings::wasmcp::protocol::server_messages::Context,
-        _request: ListToolsRequest,
+        request: ListToolsRequest,
         _client_stream: Option<&OutputStream>,
     ) -> Result<ListToolsResult, ErrorCode> {
+        let exposed: Vec<Tool> = vec![
+            Tool {
+                name: "uppercase".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to convert to uppercase"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Convert text to uppercase".to_string()),
+                    output_schema: None,
+                    title: Some("Uppercase".to_string()),
+                }),
+            },
+            Tool {
+                name: "lowercase".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to convert to lowercase"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Convert text to lowercase".to_string()),
+                    output_schema: None,
+                    title: Some("Lowercase".to_string()),
+                }),
+            },
+            Tool {
+                name: "reverse".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to reverse"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some(
+                        "Reverse a string by user-perceived character, keeping emoji and accented letters intact"
+                            .to_string(),
+                    ),
+                    output_schema: None,
+                    title: Some("Reverse".to_string()),
+                }),
+            },
+            Tool {
+                name: "trim".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to trim"},
+                        "mode": {"type": "string", "enum": ["both", "start", "end"], "description": "Which ends to trim (default both)"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Strip leading and/or trailing whitespace".to_string()),
+                    output_schema: None,
+                    title: Some("Trim".to_string()),
+                }),
+            },
+            Tool {
+                name: "word_count".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to count words in"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Count words in text, with character and line counts in the structured result. Lines are split on every '\\n', so empty text has 1 line and a trailing newline adds an empty last line; lines_count and text_stats count neither".to_string()),
+                    output_schema: Some(WORD_COUNT_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Word Count".to_string()),
+                }),
+            },
+            Tool {
+                name: "text_stats".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to measure"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some(
+                        "Count characters, bytes, words, lines and sentences, and the average word length, in one call"
+                            .to_string(),
+                    ),
+                    output_schema: Some(TEXT_STATS_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Text Statistics".to_string()),
+                }),
+            },
+            Tool {
+                name: "lines_count".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to count lines in"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Count lines in text, accepting \\n, \\r\\n and \\r line endings. A trailing line ending does not start a new line, and empty text has 0 lines".to_string()),
+                    output_schema: None,
+                    title: Some("Lines Count".to_string()),
+                }),
+            },
+            Tool {
+                name: "repeat".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to repeat"},
+                        "count": {"type": "integer", "minimum": 0, "maximum": 1000, "description": "Number of repetitions"}
+                    },
+                    "required": ["text", "count"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Repeat text a number of times (at most 1000 repetitions and 1 MiB of output)".to_string()),
+                    output_schema: None,
+                    title: Some("Repeat".to_string()),
+                }),
+            },
+            Tool {
+                name: "fill".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "pattern": {"type": "string", "minLength": 1, "description": "Pattern to repeat"},
+                        "length": {"type": "integer", "minimum": 0, "description": "Exact length of the output in characters (Unicode scalar values)"}
+                    },
+                    "required": ["pattern", "length"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Repeat a pattern until the output is exactly length characters, cutting the last repetition short if needed (at most 1 MiB of output)".to_string()),
+                    output_schema: None,
+                    title: Some("Fill".to_string()),
+                }),
+            },
+            Tool {
+                name: "title_case".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to convert to title case"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Uppercase the first letter of each whitespace-separated word and lowercase the rest, e.g. \"hELLO (wORLD)\" -> \"Hello (World)\"".to_string()),
+                    output_schema: None,
+                    title: Some("Title Case".to_string()),
+                }),
+            },
+            Tool {
+                name: "capitalize".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text whose first character to uppercase"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Uppercase the first character of the text, leaving the rest unchanged. Text starting with a non-letter is returned as is".to_string()),
+                    output_schema: None,
+                    title: Some("Capitalize".to_string()),
+                }),
+            },
+            Tool {
+                name: "camel_case".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to convert to camelCase"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Convert text to lowerCamelCase, treating whitespace, hyphens, and underscores as word separators".to_string()),
+                    output_schema: None,
+                    title: Some("Camel Case".to_string()),
+                }),
+            },
+            Tool {
+                name: "palindrome_check".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to check"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Check whether text is a palindrome, returning \"true\" or \"false\". Whitespace and punctuation are removed and letters lowercased before comparing, so \"A man, a plan, a canal: Panama\" is a palindrome".to_string()),
+                    output_schema: None,
+                    title: Some("Palindrome Check".to_string()),
+                }),
+            },
+            Tool {
+                name: "json_flatten".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "JSON object or array to flatten"},
+                        "separator": {"type": "string", "description": "String placed between path segments (default \".\")"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Flatten nested JSON into a single object keyed by path, e.g. {\"a\":{\"b\":[1,2]}} -> {\"a.b.0\":1,\"a.b.1\":2}. Array elements use their index as the path segment".to_string()),
+                    output_schema: None,
+                    title: Some("JSON Flatten".to_string()),
+                }),
+            },
+            Tool {
+                name: "json_unflatten".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Flat JSON object keyed by path"},
+                        "separator": {"type": "string", "description": "String placed between path segments (default \".\")"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Rebuild nested JSON from a flat object keyed by path, reversing json_flatten. Objects whose keys are exactly 0, 1, 2, ... become arrays".to_string()),
+                    output_schema: None,
+                    title: Some("JSON Unflatten".to_string()),
+                }),
+            },
+            Tool {
+                name: "json_sort_keys".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "JSON document to canonicalize"},
+                        "compact": {"type": "boolean", "description": "Emit minified JSON without whitespace (default false, pretty-printed)"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Re-serialize JSON with the keys of every object, at every level, sorted lexicographically by code point. Array order is preserved, so equal documents give identical output, e.g. for hashing with sha256".to_string()),
+                    output_schema: None,
+                    title: Some("JSON Sort Keys".to_string()),
+                }),
+            },
+            Tool {
+                name: "json_equal".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "a": {"type": "string", "description": "First JSON document"},
+                        "b": {"type": "string", "description": "Second JSON document"}
+                    },
+                    "required": ["a", "b"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Compare two JSON documents semantically: object key order is ignored and numbers compare by value, so 1 equals 1.0. When they differ, 'path' is the JSON Pointer of the first difference, visiting object keys in sorted order".to_string()),
+                    output_schema: Some(JSON_EQUAL_OUTPUT_SCHEMA.to_string()),
+                    title: Some("JSON Equal".to_string()),
+                }),
+            },
+            Tool {
+                name: "contains".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to search"},
+                        "pattern": {"type": "string", "description": "Substring to look for"},
+                        "case_sensitive": {"type": "boolean", "description": "Match case exactly (default true)"}
+                    },
+                    "required": ["text", "pattern"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Check whether text contains a substring, returning \"true\" or \"false\"".to_string()),
+                    output_schema: None,
+                    title: Some("Contains".to_string()),
+                }),
+            },
+            Tool {
+                name: "starts_with".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to search"},
+                        "pattern": {"type": "string", "description": "Substring to look for"},
+                        "case_sensitive": {"type": "boolean", "description": "Match case exactly (default true)"}
+                    },
+                    "required": ["text", "pattern"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Check whether text starts with a prefix, returning \"true\" or \"false\"".to_string()),
+                    output_schema: None,
+                    title: Some("Starts With".to_string()),
+                }),
+            },
+            Tool {
+                name: "ends_with".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to search"},
+                        "pattern": {"type": "string", "description": "Substring to look for"},
+                        "case_sensitive": {"type": "boolean", "description": "Match case exactly (default true)"}
+                    },
+                    "required": ["text", "pattern"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Check whether text ends with a suffix, returning \"true\" or \"false\"".to_string()),
+                    output_schema: None,
+                    title: Some("Ends With".to_string()),
+                }),
+            },
+            Tool {
+                name: "replace".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to modify"},
+                        "from": {"type": "string", "minLength": 1, "description": "Substring to replace (case-sensitive, must not be empty)"},
+                        "to": {"type": "string", "description": "Replacement text"},
+                        "count": {"type": "integer", "minimum": 0, "description": "Maximum number of replacements, starting from the left (default: all)"}
+                    },
+                    "required": ["text", "from", "to"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Replace occurrences of a substring with another string".to_string()),
+                    output_schema: None,
+                    title: Some("Replace".to_string()),
+                }),
+            },
+            Tool {
+                name: "regex_extract".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to search"},
+                        "pattern": {"type": "string", "description": "Regular expression (Rust regex syntax; no look-around or backreferences)"},
+                        "flags": {"type": "string", "description": "Any of i (case-insensitive), m (^ and $ match at line breaks), s (. matches newlines), x (ignore whitespace in the pattern)"}
+                    },
+                    "required": ["text", "pattern"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some(format!(
+                        "Find all matches of a regular expression, with their positions and capture groups, up to {} matches",
+                        MAX_REGEX_MATCHES
+                    )),
+                    output_schema: Some(REGEX_EXTRACT_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Regex Extract".to_string()),
+                }),
+            },
+            Tool {
+                name: "acronym".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Phrase to abbreviate"},
+                        "min_word_len": {"type": "integer", "minimum": 0, "description": "Skip words with fewer letters than this (default 0, keep all)"},
+                        "skip_stopwords": {"type": "boolean", "description": "Skip minor words such as \"of\", \"the\" and \"and\" (default false)"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Build an uppercase acronym from the first letter of each word, e.g. \"Portable Document Format\" -> \"PDF\". Words are separated by whitespace and hyphens; punctuation around a word is ignored and an apostrophe inside one does not split it (\"don't panic\" -> \"DP\")".to_string()),
+                    output_schema: None,
+                    title: Some("Acronym".to_string()),
+                }),
+            },
+            Tool {
+                name: "compare_versions".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "a": {"type": "string", "description": "Semantic version, e.g. 1.2.3-alpha.1"},
+                        "b": {"type": "string", "description": "Semantic version to compare against"}
+                    },
+                    "required": ["a", "b"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Compare two semantic versions by SemVer 2.0 precedence, returning -1 if a < b, 0 if equal and 1 if a > b. Pre-releases sort before their release (1.0.0-alpha < 1.0.0) and build metadata is ignored".to_string()),
+                    output_schema: None,
+                    title: Some("Compare Versions".to_string()),
+                }),
+            },
+            Tool {
+                name: "sha256".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to hash; its UTF-8 bytes are digested"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Compute the SHA-256 digest of the text's UTF-8 bytes as 64 lowercase hex characters".to_string()),
+                    output_schema: None,
+                    title: Some("SHA-256".to_string()),
+                }),
+            },
+            Tool {
+                name: "md5".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to hash; its UTF-8 bytes are digested"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Compute the MD5 digest of the text's UTF-8 bytes as 32 lowercase hex characters. MD5 is broken for security purposes; use it only for checksums and legacy compatibility".to_string()),
+                    output_schema: None,
+                    title: Some("MD5".to_string()),
+                }),
+            },
+        ]
+        .into_iter()
+        .filter(|tool| tool_filter::is_exposed(&tool.name))
+        .collect();
+        let (tools, next_cursor) =
+            pagination::paginate(exposed, request.cursor.as_deref(), TOOLS_PAGE_SIZE);
+
         Ok(ListToolsResult {
-            tools: vec![
-                Tool {
-                    name: "uppercase".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "text": {"type": "string", "description": "Text to convert to uppercase"}
-                        },
-                        "required": ["text"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Convert text to uppercase".to_string()),
-                        output_schema: None,
-                        title: Some("Uppercase".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "lowercase".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "text": {"type": "string", "description": "Text to convert to lowercase"}
-                        },
-                        "required": ["text"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Convert text to lowercase".to_string()),
-                        output_schema: None,
-                        title: Some("Lowercase".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "reverse".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "text": {"type": "string", "description": "Text to reverse"}
-                        },
-                        "required": ["text"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Reverse a string".to_string()),
-                        output_schema: None,
-                        title: Some("Reverse".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "word_count".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "text": {"type": "string", "description": "Text to count words in"}
-                        },
-                        "required": ["text"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Count words in text".to_string()),
-                        output_schema: None,
-                        title: Some("Word Count".to_string()),
-                    }),
-                },
-            ],
-            next_cursor: None,
+            tools,
+            next_cursor,
             meta: None,
         })
     }
@@ -106,16 +554,45 @@ impl Guest for StringUtils {
         request: CallToolRequest,
         _client_stream: Option<&OutputStream>,
     ) -> Option<CallToolResult> {
+        if !tool_filter::is_exposed(&request.name) {
+            return None;
+        }
+
         match request.name.as_str() {
             "uppercase" => Some(execute_uppercase(&request.arguments)),
             "lowercase" => Some(execute_lowercase(&request.arguments)),
             "reverse" => Some(execute_reverse(&request.arguments)),
+            "trim" => Some(execute_trim(&request.arguments)),
             "word_count" => Some(execute_word_count(&request.arguments)),
+            "text_stats" => Some(execute_text_stats(&request.arguments)),
+            "lines_count" => Some(execute_lines_count(&request.arguments)),
+            "repeat" => Some(execute_repeat(&request.arguments)),
+            "fill" => Some(execute_fill(&request.arguments)),
+            "title_case" => Some(execute_title_case(&request.arguments)),
+            "capitalize" => Some(execute_capitalize(&request.arguments)),
+            "camel_case" => Some(execute_camel_case(&request.arguments)),
+            "palindrome_check" => Some(execute_palindrome_check(&request.arguments)),
+            "json_flatten" => Some(execute_json_flatten(&request.arguments)),
+            "json_unflatten" => Some(execute_json_unflatten(&request.arguments)),
+            "json_sort_keys" => Some(execute_json_sort_keys(&request.arguments)),
+            "json_equal" => Some(execute_json_equal(&request.arguments)),
+            "contains" => Some(execute_match(&request.arguments, |text, pattern| text.contains(pattern))),
+            "starts_with" => Some(execute_match(&request.arguments, |text, pattern| text.starts_with(pattern))),
+            "ends_with" => Some(execute_match(&request.arguments, |text, pattern| text.ends_with(pattern))),
+            "replace" => Some(execute_replace(&request.arguments)),
+            "regex_extract" => Some(execute_regex_extract(&request.arguments)),
+            "acronym" => Some(execute_acronym(&request.arguments)),
+            "compare_versions" => Some(execute_compare_versions(&request.arguments)),
+            "sha256" => Some(execute_digest::<Sha256>(&request.arguments)),
+            "md5" => Some(execute_digest::<Md5>(&request.arguments)),
             _ => None, // We don't handle this tool
         }
     }
 }
 
+/// Largest number of tools returned by one `list_tools` call
+const TOOLS_PAGE_SIZE: usize = pagination::DEFAULT_PAGE_SIZE;
+
 fn execute_uppercase(arguments: &Option<String>) -> CallToolResult {
     match parse_text_arg(arguments) {
         Ok(text) => success_result(text.to_uppercase()),
@@ -130,24 +607,750 @@ fn execute_lowercase(arguments: &Option<String>) -> CallToolResult {
     }
 }
 
+/// Reverses extended grapheme clusters rather than chars, so combining marks
+/// and emoji modifier or flag sequences stay attached to their base
 fn execute_reverse(arguments: &Option<String>) -> CallToolResult {
     match parse_text_arg(arguments) {
-        Ok(text) => success_result(text.chars().rev().collect()),
+        Ok(text) => success_result(text.graphemes(true).rev().collect()),
         Err(msg) => error_result(msg),
     }
 }
 
+fn execute_trim(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_text_arg(arguments).and_then(|text| {
+        let mode = parse_optional_arg::<String>(arguments, "mode")?;
+        Ok((text, mode))
+    });
+
+    match parsed {
+        Ok((text, mode)) => match mode.as_deref().unwrap_or("both") {
+            "both" => success_result(text.trim().to_string()),
+            "start" => success_result(text.trim_start().to_string()),
+            "end" => success_result(text.trim_end().to_string()),
+            other => error_result(format!(
+                "Invalid parameter 'mode': expected one of both, start, end, got '{}'",
+                other
+            )),
+        },
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Shape of the `structured_content` returned by `word_count`
+const WORD_COUNT_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "words": {"type": "integer", "minimum": 0, "description": "Whitespace-separated words"},
+        "characters": {"type": "integer", "minimum": 0, "description": "Unicode scalar values"},
+        "lines": {"type": "integer", "minimum": 1, "description": "Pieces between '\\n' characters: empty text is one line and a trailing newline starts another, unlike lines_count"}
+    },
+    "required": ["words", "characters", "lines"]
+}"#;
+
 fn execute_word_count(arguments: &Option<String>) -> CallToolResult {
     match parse_text_arg(arguments) {
         Ok(text) => {
-            let count = text.split_whitespace().count();
-            success_result(format!("{} words", count))
+            let words = count_words(&text);
+            structured_success_result(
+                format!("{} words", words),
+                serde_json::json!({
+                    "words": words,
+                    "characters": text.chars().count(),
+                    "lines": text.split('\n').count(),
+                }),
+            )
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn count_words(text: &str) -> usize {
+    text.split_whitespace().count()
+}
+
+/// Shape of the `structured_content` returned by `text_stats`
+const TEXT_STATS_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "characters": {"type": "integer", "minimum": 0, "description": "Unicode scalar values"},
+        "bytes": {"type": "integer", "minimum": 0, "description": "UTF-8 bytes"},
+        "words": {"type": "integer", "minimum": 0, "description": "Whitespace-separated words"},
+        "lines": {"type": "integer", "minimum": 0, "description": "Lines as counted by lines_count"},
+        "sentences": {"type": "integer", "minimum": 0, "description": "Runs of text ended by '.', '!' or '?', or by the end of the text"},
+        "average_word_length": {"type": "number", "minimum": 0, "description": "Mean characters per word, not counting punctuation around it; 0 without words"}
+    },
+    "required": ["characters", "bytes", "words", "lines", "sentences", "average_word_length"]
+}"#;
+
+fn execute_text_stats(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => {
+            let characters = text.chars().count();
+            let words = count_words(&text);
+            let lines = count_lines(&text);
+            let sentences = count_sentences(&text);
+            let average_word_length = average_word_length(&text);
+            structured_success_result(
+                format!(
+                    "{} characters, {} bytes, {} words, {} lines, {} sentences, average word length {:.2}",
+                    characters,
+                    text.len(),
+                    words,
+                    lines,
+                    sentences,
+                    average_word_length
+                ),
+                serde_json::json!({
+                    "characters": characters,
+                    "bytes": text.len(),
+                    "words": words,
+                    "lines": lines,
+                    "sentences": sentences,
+                    "average_word_length": average_word_length,
+                }),
+            )
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Stretches between sentence terminators that contain a letter or digit.
+/// Abbreviations such as "e.g." are counted as sentence ends.
+fn count_sentences(text: &str) -> usize {
+    text.split(['.', '!', '?'])
+        .filter(|sentence| sentence.chars().any(char::is_alphanumeric))
+        .count()
+}
+
+fn average_word_length(text: &str) -> f64 {
+    let lengths: Vec<usize> = text
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()).chars().count())
+        .filter(|&length| length > 0)
+        .collect();
+    if lengths.is_empty() {
+        return 0.0;
+    }
+    lengths.iter().sum::<usize>() as f64 / lengths.len() as f64
+}
+
+fn execute_lines_count(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => success_result(count_lines(&text).to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn count_lines(text: &str) -> usize {
+    let mut chars = text.chars().peekable();
+    let mut breaks = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => breaks += 1,
+            '\r' => {
+                breaks += 1;
+                chars.next_if_eq(&'\n');
+            }
+            _ => {}
+        }
+    }
+
+    // Text after the last line ending forms a final line of its own
+    let unterminated = !text.is_empty() && !text.ends_with(['\n', '\r']);
+    breaks + usize::from(unterminated)
+}
+
+const MAX_REPEAT_COUNT: u64 = 1000;
+/// Largest output of `repeat` and `fill`, 1 MiB
+const MAX_REPEAT_BYTES: usize = 1024 * 1024;
+
+fn execute_repeat(arguments: &Option<String>) -> CallToolResult {
+    let text = match parse_text_arg(arguments) {
+        Ok(text) => text,
+        Err(msg) => return error_result(msg),
+    };
+    let count = match parse_count_arg(arguments, "count") {
+        Ok(count) => count,
+        Err(msg) => return error_result(msg),
+    };
+
+    if count > MAX_REPEAT_COUNT {
+        return error_result(format!(
+            "Error: count {} exceeds the maximum of {} repetitions",
+            count, MAX_REPEAT_COUNT
+        ));
+    }
+
+    // Check the projected size before allocating the output
+    let total = repeated_len(text.len(), count);
+    if total > MAX_REPEAT_BYTES {
+        return error_result(format!(
+            "Error: output would be {} bytes, exceeding the maximum of {} bytes",
+            total, MAX_REPEAT_BYTES
+        ));
+    }
+
+    success_result(text.repeat(count as usize))
+}
+
+/// Size of `len` bytes repeated `count` times, saturating since a
+/// multi-megabyte text times the count can overflow a 32-bit usize
+fn repeated_len(len: usize, count: u64) -> usize {
+    len.saturating_mul(usize::try_from(count).unwrap_or(usize::MAX))
+}
+
+fn execute_fill(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_optional_arg::<String>(arguments, "pattern").and_then(|pattern| {
+        let pattern = pattern.ok_or_else(|| "Missing or invalid parameter 'pattern'".to_string())?;
+        Ok((pattern, parse_count_arg(arguments, "length")?))
+    });
+    let (pattern, length) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    let pattern_chars = pattern.chars().count();
+    if pattern_chars == 0 {
+        return error_result("Parameter 'pattern' must not be empty".to_string());
+    }
+
+    // Check the projected size before allocating the output; a length too
+    // large for usize is over the limit anyway
+    let length = usize::try_from(length).unwrap_or(usize::MAX);
+    let partial: usize = pattern.chars().take(length % pattern_chars).map(char::len_utf8).sum();
+    let total = (length / pattern_chars).saturating_mul(pattern.len()).saturating_add(partial);
+    if total > MAX_REPEAT_BYTES {
+        return error_result(format!(
+            "Error: output would be {} bytes, exceeding the maximum of {} bytes",
+            total, MAX_REPEAT_BYTES
+        ));
+    }
+
+    // Cutting by char never splits a multibyte character
+    success_result(pattern.chars().cycle().take(length).collect())
+}
+
+/// Uses the full Unicode case mappings, which may change the length: "ß"
+/// uppercases to "SS"
+fn execute_title_case(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => {
+            let mut result = String::with_capacity(text.len());
+            // Leading punctuation or digits do not count as the first letter
+            let mut awaiting_first_letter = true;
+            for c in text.chars() {
+                if c.is_whitespace() {
+                    result.push(c);
+                    awaiting_first_letter = true;
+                } else if awaiting_first_letter && c.is_alphabetic() {
+                    result.extend(c.to_uppercase());
+                    awaiting_first_letter = false;
+                } else {
+                    result.extend(c.to_lowercase());
+                }
+            }
+            success_result(result)
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_capitalize(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => {
+            let mut chars = text.chars();
+            match chars.next() {
+                Some(first) => success_result(first.to_uppercase().chain(chars).collect()),
+                None => success_result(text),
+            }
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_camel_case(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => {
+            let words = text
+                .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+                .filter(|word| !word.is_empty());
+
+            let mut result = String::with_capacity(text.len());
+            for (i, word) in words.enumerate() {
+                let mut chars = word.chars();
+                if let Some(first) = chars.next() {
+                    if i == 0 {
+                        result.extend(first.to_lowercase());
+                    } else {
+                        result.extend(first.to_uppercase());
+                    }
+                    result.push_str(&chars.as_str().to_lowercase());
+                }
+            }
+            success_result(result)
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_palindrome_check(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => {
+            let normalized: Vec<char> = text
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect();
+            let is_palindrome = normalized.iter().eq(normalized.iter().rev());
+            success_result(is_palindrome.to_string())
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_match<F>(arguments: &Option<String>, matches: F) -> CallToolResult
+where
+    F: Fn(&str, &str) -> bool,
+{
+    let parsed = parse_text_arg(arguments).and_then(|text| {
+        let pattern = parse_optional_arg::<String>(arguments, "pattern")?
+            .ok_or_else(|| "Missing or invalid parameter 'pattern'".to_string())?;
+        let case_sensitive = parse_optional_arg::<bool>(arguments, "case_sensitive")?.unwrap_or(true);
+        Ok((text, pattern, case_sensitive))
+    });
+
+    match parsed {
+        Ok((text, pattern, true)) => success_result(matches(&text, &pattern).to_string()),
+        Ok((text, pattern, false)) => {
+            success_result(matches(&text.to_lowercase(), &pattern.to_lowercase()).to_string())
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_replace(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_text_arg(arguments).and_then(|text| {
+        let from = parse_optional_arg::<String>(arguments, "from")?
+            .ok_or_else(|| "Missing or invalid parameter 'from'".to_string())?;
+        let to = parse_optional_arg::<String>(arguments, "to")?
+            .ok_or_else(|| "Missing or invalid parameter 'to'".to_string())?;
+        Ok((text, from, to))
+    });
+    let (text, from, to) = match parsed {
+        Ok(args) => args,
+        Err(msg) => return error_result(msg),
+    };
+    if from.is_empty() {
+        return error_result("Parameter 'from' must not be empty".to_string());
+    }
+
+    let count = match parse_optional_count_arg(arguments, "count") {
+        Ok(count) => count.map_or(usize::MAX, |c| c.try_into().unwrap_or(usize::MAX)),
+        Err(msg) => return error_result(msg),
+    };
+
+    success_result(text.replacen(&from, &to, count))
+}
+
+/// Matches returned by `regex_extract` before the result is truncated
+const MAX_REGEX_MATCHES: usize = 1000;
+
+/// Upper bound on the compiled size of a `regex_extract` pattern, in bytes
+const MAX_REGEX_SIZE: usize = 1 << 20;
+
+/// Shape of the `structured_content` returned by `regex_extract`
+const REGEX_EXTRACT_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "matches": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "properties": {
+                    "match": {"type": "string"},
+                    "start": {"type": "integer", "minimum": 0, "description": "Byte offset of the match"},
+                    "end": {"type": "integer", "minimum": 0, "description": "Byte offset just past the match"},
+                    "groups": {"type": "array", "items": {"type": ["string", "null"]}, "description": "Capture groups 1..n; null for groups that did not participate"},
+                    "named": {"type": "object", "additionalProperties": {"type": ["string", "null"]}, "description": "Named capture groups, when the pattern has any"}
+                },
+                "required": ["match", "start", "end", "groups"]
+            }
+        },
+        "count": {"type": "integer", "minimum": 0},
+        "truncated": {"type": "boolean", "description": "True when more matches exist than were returned"}
+    },
+    "required": ["matches", "count", "truncated"]
+}"#;
+
+fn execute_regex_extract(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_text_arg(arguments).and_then(|text| {
+        let pattern = parse_optional_arg::<String>(arguments, "pattern")?
+            .ok_or_else(|| "Missing or invalid parameter 'pattern'".to_string())?;
+        let flags = parse_optional_arg::<String>(arguments, "flags")?.unwrap_or_default();
+        Ok((text, build_regex(&pattern, &flags)?))
+    });
+    let (text, regex) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    let names: Vec<(usize, &str)> = regex
+        .capture_names()
+        .enumerate()
+        .filter_map(|(i, name)| Some((i, name?)))
+        .collect();
+    let mut captures = regex.captures_iter(&text);
+    let matches: Vec<serde_json::Value> = captures
+        .by_ref()
+        .take(MAX_REGEX_MATCHES)
+        .map(|caps| {
+            let whole = caps.get(0).expect("group 0 is always the whole match");
+            let group = |i: usize| caps.get(i).map(|m| m.as_str());
+            let mut entry = serde_json::json!({
+                "match": whole.as_str(),
+                "start": whole.start(),
+                "end": whole.end(),
+                "groups": (1..caps.len()).map(group).collect::<Vec<_>>(),
+            });
+            if !names.is_empty() {
+                entry["named"] = names
+                    .iter()
+                    .map(|&(i, name)| (name.to_string(), serde_json::json!(group(i))))
+                    .collect::<serde_json::Map<_, _>>()
+                    .into();
+            }
+            entry
+        })
+        .collect();
+    let truncated = captures.next().is_some();
+
+    let texts: Vec<&str> = matches.iter().filter_map(|m| m["match"].as_str()).collect();
+    structured_success_result(
+        serde_json::json!(texts).to_string(),
+        serde_json::json!({
+            "count": matches.len(),
+            "matches": matches,
+            "truncated": truncated,
+        }),
+    )
+}
+
+/// Compile `pattern` with single-letter `flags`, reporting syntax errors
+/// and oversized patterns as messages
+fn build_regex(pattern: &str, flags: &str) -> Result<regex::Regex, String> {
+    let mut builder = regex::RegexBuilder::new(pattern);
+    builder.size_limit(MAX_REGEX_SIZE);
+    for flag in flags.chars() {
+        match flag {
+            'i' => builder.case_insensitive(true),
+            'm' => builder.multi_line(true),
+            's' => builder.dot_matches_new_line(true),
+            'x' => builder.ignore_whitespace(true),
+            other => {
+                return Err(format!(
+                    "Invalid parameter 'flags': unknown flag '{}', expected any of i, m, s, x",
+                    other
+                ))
+            }
+        };
+    }
+    builder.build().map_err(|e| format!("Invalid regex pattern: {}", e))
+}
+
+/// Minor words `acronym` leaves out when asked to skip stopwords
+const ACRONYM_STOPWORDS: &[&str] = &[
+    "a", "an", "and", "as", "at", "by", "for", "from", "in", "into", "of", "on", "or", "the", "to",
+    "with",
+];
+
+fn execute_acronym(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_text_arg(arguments).and_then(|text| {
+        let min_word_len = parse_optional_count_arg(arguments, "min_word_len")?.unwrap_or(0);
+        let skip_stopwords = parse_optional_arg::<bool>(arguments, "skip_stopwords")?.unwrap_or(false);
+        Ok((text, min_word_len, skip_stopwords))
+    });
+
+    match parsed {
+        Ok((text, min_word_len, skip_stopwords)) => {
+            success_result(acronym(&text, min_word_len, skip_stopwords))
+        }
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// Words are split on whitespace and hyphens only, so an apostrophe inside a
+/// word ("don't") does not start a new one; punctuation around a word is dropped
+fn acronym(text: &str, min_word_len: u64, skip_stopwords: bool) -> String {
+    text.split(|c: char| c.is_whitespace() || c == '-')
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .filter(|word| word.chars().filter(|c| c.is_alphanumeric()).count() as u64 >= min_word_len)
+        .filter(|word| !(skip_stopwords && ACRONYM_STOPWORDS.contains(&word.to_lowercase().as_str())))
+        .filter_map(|word| word.chars().next())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+fn execute_compare_versions(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_version_arg(arguments, "a")
+        .and_then(|a| Ok((a, parse_version_arg(arguments, "b")?)));
+
+    match parsed {
+        // Precedence ignores build metadata, unlike `Ord` for `Version`
+        Ok((a, b)) => success_result((a.cmp_precedence(&b) as i8).to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn parse_version_arg(arguments: &Option<String>, arg_name: &str) -> Result<semver::Version, String> {
+    let text = parse_optional_arg::<String>(arguments, arg_name)?
+        .ok_or_else(|| format!("Missing or invalid parameter '{}'", arg_name))?;
+    semver::Version::parse(&text).map_err(|e| {
+        format!("Invalid parameter '{}': '{}' is not a semantic version ({})", arg_name, text, e)
+    })
+}
+
+/// Lowercase hex digest of the text's UTF-8 bytes
+fn execute_digest<D: Digest>(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => success_result(
+            D::digest(text.as_bytes())
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect(),
+        ),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_json_flatten(arguments: &Option<String>) -> CallToolResult {
+    let (value, separator) = match parse_json_text_args(arguments) {
+        Ok(args) => args,
+        Err(msg) => return error_result(msg),
+    };
+    if !value.is_object() && !value.is_array() {
+        return error_result("Parameter 'text' must be a JSON object or array".to_string());
+    }
+
+    let mut flat = serde_json::Map::new();
+    flatten_into(&mut flat, None, value, &separator);
+    success_result(serde_json::Value::Object(flat).to_string())
+}
+
+fn flatten_into(
+    flat: &mut serde_json::Map<String, serde_json::Value>,
+    prefix: Option<&str>,
+    value: serde_json::Value,
+    separator: &str,
+) {
+    let path = |segment: &str| match prefix {
+        Some(prefix) => format!("{}{}{}", prefix, separator, segment),
+        None => segment.to_string(),
+    };
+
+    match value {
+        serde_json::Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                flatten_into(flat, Some(&path(&key)), child, separator);
+            }
+        }
+        serde_json::Value::Array(items) if !items.is_empty() => {
+            for (index, child) in items.into_iter().enumerate() {
+                flatten_into(flat, Some(&path(&index.to_string())), child, separator);
+            }
+        }
+        // Scalars and empty containers are leaves. Only an empty top-level
+        // container has no prefix; it has no paths, so it flattens to `{}`
+        leaf => {
+            if let Some(prefix) = prefix {
+                flat.insert(prefix.to_string(), leaf);
+            }
+        }
+    }
+}
+
+/// Intermediate tree for unflattening, keeping leaf values apart from
+/// the branches built from path segments
+enum PathNode {
+    Leaf(serde_json::Value),
+    Branch(std::collections::BTreeMap<String, PathNode>),
+}
+
+impl PathNode {
+    fn into_value(self) -> serde_json::Value {
+        match self {
+            PathNode::Leaf(value) => value,
+            PathNode::Branch(children) => {
+                // Keys 0..n in order mean the branch came from an array. Only
+                // the root can be empty, and an empty document flattens from {}
+                let is_array = !children.is_empty()
+                    && children
+                        .keys()
+                        .map(|key| key.parse::<usize>().ok().filter(|i| i.to_string() == *key))
+                        .collect::<Option<Vec<_>>>()
+                        .is_some_and(|mut indices| {
+                            indices.sort_unstable();
+                            indices.into_iter().enumerate().all(|(i, index)| i == index)
+                        });
+
+                if is_array {
+                    let mut items: Vec<_> = children
+                        .into_iter()
+                        .map(|(key, child)| (key.parse::<usize>().unwrap_or_default(), child))
+                        .collect();
+                    items.sort_unstable_by_key(|(index, _)| *index);
+                    serde_json::Value::Array(items.into_iter().map(|(_, child)| child.into_value()).collect())
+                } else {
+                    serde_json::Value::Object(
+                        children
+                            .into_iter()
+                            .map(|(key, child)| (key, child.into_value()))
+                            .collect(),
+                    )
+                }
+            }
+        }
+    }
+}
+
+fn execute_json_unflatten(arguments: &Option<String>) -> CallToolResult {
+    let (value, separator) = match parse_json_text_args(arguments) {
+        Ok(args) => args,
+        Err(msg) => return error_result(msg),
+    };
+    let flat = match value {
+        serde_json::Value::Object(flat) => flat,
+        _ => return error_result("Parameter 'text' must be a flat JSON object".to_string()),
+    };
+
+    match unflatten(flat, &separator) {
+        Ok(value) => success_result(value.to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn unflatten(
+    flat: serde_json::Map<String, serde_json::Value>,
+    separator: &str,
+) -> Result<serde_json::Value, String> {
+    let mut root = std::collections::BTreeMap::new();
+
+    for (key, value) in flat {
+        let conflict = || format!("Key '{}' conflicts with another key's path", key);
+        let mut segments: Vec<&str> = key.split(separator).collect();
+        let last = segments.pop().unwrap_or_default();
+
+        let mut node = &mut root;
+        for segment in segments {
+            let child = node
+                .entry(segment.to_string())
+                .or_insert_with(|| PathNode::Branch(std::collections::BTreeMap::new()));
+            node = match child {
+                PathNode::Branch(children) => children,
+                PathNode::Leaf(_) => return Err(conflict()),
+            };
+        }
+        if node.insert(last.to_string(), PathNode::Leaf(value)).is_some() {
+            return Err(conflict());
         }
+    }
+
+    Ok(PathNode::Branch(root).into_value())
+}
+
+/// `serde_json::Map` is a `BTreeMap` (the `preserve_order` feature is off),
+/// so parsing already orders the keys of every object and serializing writes
+/// them back in that order
+fn execute_json_sort_keys(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_text_arg(arguments).and_then(|text| {
+        let compact = parse_optional_arg::<bool>(arguments, "compact")?.unwrap_or(false);
+        let value: serde_json::Value =
+            serde_json::from_str(&text).map_err(|e| format!("Parameter 'text' is not valid JSON: {}", e))?;
+        Ok((value, compact))
+    });
+
+    match parsed {
+        Ok((value, true)) => success_result(value.to_string()),
+        Ok((value, false)) => match serde_json::to_string_pretty(&value) {
+            Ok(pretty) => success_result(pretty),
+            Err(e) => error_result(format!("Failed to serialize JSON: {}", e)),
+        },
+        Err(msg) => error_result(msg),
+    }
+}
+
+const JSON_EQUAL_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "equal": {"type": "boolean"},
+        "path": {"type": "string", "description": "JSON Pointer of the first difference; only present when not equal"}
+    },
+    "required": ["equal"]
+}"#;
+
+fn execute_json_equal(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_json_arg(arguments, "a").and_then(|a| Ok((a, parse_json_arg(arguments, "b")?)));
+
+    match parsed {
+        Ok((a, b)) => match first_difference(&a, &b) {
+            None => json_result(serde_json::json!({ "equal": true })),
+            Some(path) => json_result(serde_json::json!({ "equal": false, "path": path })),
+        },
         Err(msg) => error_result(msg),
     }
 }
 
-fn parse_text_arg(arguments: &Option<String>) -> Result<String, String> {
+fn parse_json_arg(arguments: &Option<String>, arg_name: &str) -> Result<serde_json::Value, String> {
+    let text: String = parse_arg(arguments, arg_name)?;
+    serde_json::from_str(&text).map_err(|e| format!("Parameter '{}' is not valid JSON: {}", arg_name, e))
+}
+
+/// JSON Pointer of the first place `a` and `b` differ, `None` if they are
+/// equal. Object keys are visited in sorted order, which is the map order.
+fn first_difference(a: &serde_json::Value, b: &serde_json::Value) -> Option<String> {
+    use serde_json::Value;
+
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => {
+            // Integers compare exactly, so large values are not merged by f64 rounding
+            let equal = if x.is_f64() || y.is_f64() { x.as_f64() == y.as_f64() } else { x == y };
+            (!equal).then(String::new)
+        }
+        (Value::Array(x), Value::Array(y)) => (0..x.len().max(y.len())).find_map(|i| {
+            // An index past the end of the shorter array is itself the difference
+            let rest = match (x.get(i), y.get(i)) {
+                (Some(x), Some(y)) => first_difference(x, y)?,
+                _ => String::new(),
+            };
+            Some(format!("/{}{}", i, rest))
+        }),
+        (Value::Object(x), Value::Object(y)) => {
+            let mut keys: Vec<&String> = x.keys().chain(y.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            keys.into_iter().find_map(|key| {
+                let rest = match (x.get(key), y.get(key)) {
+                    (Some(x), Some(y)) => first_difference(x, y)?,
+                    _ => String::new(),
+                };
+                Some(format!("/{}{}", key.replace('~', "~0").replace('/', "~1"), rest))
+            })
+        }
+        _ => (a != b).then(String::new),
+    }
+}
+
+fn parse_json_text_args(arguments: &Option<String>) -> Result<(serde_json::Value, String), String> {
+    let text = parse_text_arg(arguments)?;
+    let separator = parse_optional_arg::<String>(arguments, "separator")?.unwrap_or_else(|| ".".to_string());
+    if separator.is_empty() {
+        return Err("Parameter 'separator' must not be empty".to_string());
+    }
+    let value = serde_json::from_str(&text).map_err(|e| format!("Parameter 'text' is not valid JSON: {}", e))?;
+    Ok((value, separator))
+}
+
+fn parse_count_arg(arguments: &Option<String>, arg_name: &str) -> Result<u64, String> {
     let args_str = arguments
         .as_ref()
         .ok_or_else(|| "Missing arguments".to_string())?;
@@ -155,36 +1358,440 @@ fn parse_text_arg(arguments: &Option<String>) -> Result<String, String> {
     let json: serde_json::Value =
         serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
 
-    let text = json
-        .get("text")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing or invalid parameter 'text'".to_string())?;
+    let count = json
+        .get(arg_name)
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| format!("Missing or invalid parameter '{}' (expected a non-negative integer)", arg_name))?;
 
-    Ok(text.to_string())
+    Ok(count)
 }
 
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
-    }
-}
+fn parse_optional_count_arg(arguments: &Option<String>, arg_name: &str) -> Result<Option<u64>, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
 
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
+    match json.get(arg_name) {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(value) => value.as_u64().map(Some).ok_or_else(|| {
+            format!("Invalid parameter '{}' (expected a non-negative integer)", arg_name)
+        }),
     }
 }
 
 bindings::export!(StringUtils with_types_in bindings);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(result: &CallToolResult) -> &str {
+        match &result.content[0] {
+            ContentBlock::Text(TextContent { text: TextData::Text(text), .. }) => text,
+            _ => panic!("expected a text block"),
+        }
+    }
+
+    /// Text of a successful call to `execute` with `arguments`
+    fn run(execute: fn(&Option<String>) -> CallToolResult, arguments: serde_json::Value) -> String {
+        let result = execute(&Some(arguments.to_string()));
+        assert_ne!(result.is_error, Some(true), "{}: {}", arguments, text(&result));
+        text(&result).to_string()
+    }
+
+    fn repeat(text: &str, count: u64) -> CallToolResult {
+        execute_repeat(&Some(serde_json::json!({ "text": text, "count": count }).to_string()))
+    }
+
+    #[test]
+    fn repeat_allows_output_up_to_one_mebibyte() {
+        let chunk = "x".repeat(MAX_REPEAT_BYTES / 1000);
+        let result = repeat(&chunk, 1000);
+        assert_ne!(result.is_error, Some(true));
+        assert!(text(&result).len() <= MAX_REPEAT_BYTES);
+
+        let result = repeat("ab", 3);
+        assert_eq!(text(&result), "ababab");
+    }
+
+    #[test]
+    fn repeat_rejects_output_over_one_mebibyte() {
+        let chunk = "x".repeat(MAX_REPEAT_BYTES / 1000 + 1);
+        let result = repeat(&chunk, 1000);
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            text(&result),
+            "Error: output would be 1049000 bytes, exceeding the maximum of 1048576 bytes"
+        );
+    }
+
+    #[test]
+    fn repeat_rejects_too_many_repetitions() {
+        let result = repeat("x", MAX_REPEAT_COUNT + 1);
+        assert_eq!(result.is_error, Some(true));
+        assert!(text(&result).contains("exceeds the maximum of 1000 repetitions"), "{}", text(&result));
+    }
+
+    #[test]
+    fn repeated_len_saturates() {
+        assert_eq!(repeated_len(3, 4), 12);
+        assert_eq!(repeated_len(usize::MAX, 2), usize::MAX);
+        assert_eq!(repeated_len(2, u64::MAX), usize::MAX);
+        assert_eq!(repeated_len(0, u64::MAX), 0);
+    }
+
+    fn json_tool(execute: fn(&Option<String>) -> CallToolResult, arguments: serde_json::Value) -> serde_json::Value {
+        let result = execute(&Some(arguments.to_string()));
+        assert_ne!(result.is_error, Some(true), "{}", text(&result));
+        serde_json::from_str(text(&result)).expect("JSON output")
+    }
+
+    #[test]
+    fn flatten_of_an_empty_document_is_empty() {
+        for empty in ["{}", "[]"] {
+            let flat = json_tool(execute_json_flatten, serde_json::json!({ "text": empty }));
+            assert_eq!(flat, serde_json::json!({}), "{}", empty);
+        }
+
+        // {} round-trips; an empty top-level array comes back as {}
+        let unflat = json_tool(execute_json_unflatten, serde_json::json!({ "text": "{}" }));
+        assert_eq!(unflat, serde_json::json!({}));
+    }
+
+    #[test]
+    fn flatten_and_unflatten_round_trip_with_arrays() {
+        let nested = serde_json::json!({
+            "a": {"b": [1, {"c": true}], "d": null},
+            "e": "x",
+            "empty": {"list": [], "map": {}},
+        });
+        let flat = json_tool(execute_json_flatten, serde_json::json!({ "text": nested.to_string() }));
+        assert_eq!(
+            flat,
+            serde_json::json!({
+                "a.b.0": 1,
+                "a.b.1.c": true,
+                "a.d": null,
+                "e": "x",
+                "empty.list": [],
+                "empty.map": {},
+            })
+        );
+
+        let unflat = json_tool(execute_json_unflatten, serde_json::json!({ "text": flat.to_string() }));
+        assert_eq!(unflat, nested);
+    }
+
+    #[test]
+    fn flatten_and_unflatten_honor_the_separator() {
+        let nested = serde_json::json!({"a.b": {"c": [7]}});
+        let flat = json_tool(execute_json_flatten, serde_json::json!({ "text": nested.to_string(), "separator": "/" }));
+        assert_eq!(flat, serde_json::json!({"a.b/c/0": 7}));
+
+        let unflat = json_tool(
+            execute_json_unflatten,
+            serde_json::json!({ "text": flat.to_string(), "separator": "/" }),
+        );
+        assert_eq!(unflat, nested);
+    }
+
+    #[test]
+    fn digests_match_known_vectors() {
+        let sha256 = |text: &str| run(execute_digest::<Sha256>, serde_json::json!({ "text": text }));
+        let md5 = |text: &str| run(execute_digest::<Md5>, serde_json::json!({ "text": text }));
+
+        assert_eq!(sha256(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(md5(""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(sha256("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(md5("abc"), "900150983cd24fb0d6963f7d28e17f72");
+        // Non-ASCII text is hashed as its UTF-8 bytes
+        assert_eq!(sha256("日本語"), "77710aedc74ecfa33685e33a6c7df5cc83004da1bdcef7fb280f5c2b2e97e0a5");
+        assert_eq!(md5("日本語"), "00110af8b4393ef3f72c50be5b332bec");
+    }
+
+    #[test]
+    fn compare_versions_follows_semver_precedence() {
+        let compare = |a: &str, b: &str| run(execute_compare_versions, serde_json::json!({ "a": a, "b": b }));
+
+        assert_eq!(compare("1.0.0-alpha", "1.0.0"), "-1");
+        assert_eq!(compare("1.0.0", "1.0.0-alpha"), "1");
+        assert_eq!(compare("1.2.3", "1.2.3"), "0");
+        assert_eq!(compare("1.10.0", "1.9.0"), "1");
+        // Numeric identifiers compare as numbers and sort before alphanumeric ones
+        assert_eq!(compare("1.0.0-alpha.2", "1.0.0-alpha.10"), "-1");
+        assert_eq!(compare("1.0.0-1", "1.0.0-alpha"), "-1");
+        assert_eq!(compare("1.0.0-alpha", "1.0.0-alpha.1"), "-1");
+        // Build metadata does not affect precedence
+        assert_eq!(compare("1.0.0+build.1", "1.0.0+build.2"), "0");
+        assert_eq!(compare("1.0.0-rc.1+sha.abc", "1.0.0-rc.1"), "0");
+    }
+
+    #[test]
+    fn compare_versions_rejects_invalid_semver() {
+        for (a, b, bad) in [("1.0", "1.0.0", "a"), ("1.0.0", "v1.0.0", "b"), ("1.0.0", "01.0.0", "b")] {
+            let result = execute_compare_versions(&Some(serde_json::json!({ "a": a, "b": b }).to_string()));
+            assert_eq!(result.is_error, Some(true), "{} {}", a, b);
+            assert!(
+                text(&result).starts_with(&format!("Invalid parameter '{}'", bad)),
+                "{}",
+                text(&result)
+            );
+            assert!(text(&result).contains("is not a semantic version"), "{}", text(&result));
+        }
+    }
+
+    #[test]
+    fn text_stats_of_a_multi_line_sample() {
+        let sample = "Héllo, world! This is a test.\nSecond line here?\nNo end";
+        let result = execute_text_stats(&Some(serde_json::json!({ "text": sample }).to_string()));
+        assert_ne!(result.is_error, Some(true), "{}", text(&result));
+        let mut structured: serde_json::Value =
+            serde_json::from_str(result.structured_content.as_deref().expect("structured result")).unwrap();
+
+        // Héllo world This is a test Second line here No end: 40 letters in 11 words
+        let average = structured["average_word_length"].take().as_f64().expect("average_word_length");
+        assert!((average - 40.0 / 11.0).abs() < 1e-12, "{}", average);
+        assert_eq!(
+            structured,
+            serde_json::json!({
+                "characters": 54,
+                "bytes": 55,
+                "words": 11,
+                "lines": 3,
+                "sentences": 4,
+                "average_word_length": null,
+            })
+        );
+        assert_eq!(
+            text(&result),
+            "54 characters, 55 bytes, 11 words, 3 lines, 4 sentences, average word length 3.64"
+        );
+    }
+
+    fn word_count(sample: &str) -> serde_json::Value {
+        let result = execute_word_count(&Some(serde_json::json!({ "text": sample }).to_string()));
+        assert_ne!(result.is_error, Some(true), "{}", text(&result));
+        serde_json::from_str(result.structured_content.as_deref().expect("structured result")).unwrap()
+    }
+
+    #[test]
+    fn word_count_of_empty_text() {
+        assert_eq!(word_count(""), serde_json::json!({"words": 0, "characters": 0, "lines": 1}));
+        assert_eq!(word_count(" \t "), serde_json::json!({"words": 0, "characters": 3, "lines": 1}));
+        assert_eq!(count_lines(""), 0);
+    }
+
+    #[test]
+    fn word_count_splits_lines_on_every_newline() {
+        assert_eq!(word_count("one two"), serde_json::json!({"words": 2, "characters": 7, "lines": 1}));
+        // A trailing newline starts an empty last line, which lines_count does not count
+        assert_eq!(word_count("one two\n"), serde_json::json!({"words": 2, "characters": 8, "lines": 2}));
+        assert_eq!(count_lines("one two\n"), 1);
+        assert_eq!(word_count("one\n\ntwo\n\n"), serde_json::json!({"words": 2, "characters": 10, "lines": 5}));
+        assert_eq!(count_lines("one\n\ntwo\n\n"), 4);
+        // A lone '\r' is not a line break here
+        assert_eq!(word_count("one\rtwo")["lines"], 1);
+    }
+
+    #[test]
+    fn reverse_keeps_grapheme_clusters_together() {
+        let reverse = |text: &str| run(execute_reverse, serde_json::json!({ "text": text }));
+        let by_chars = |text: &str| text.chars().rev().collect::<String>();
+
+        for (text, reversed) in [
+            // Precomposed é, and a thumbs up with a skin tone modifier
+            ("é👍🏽", "👍🏽é"),
+            // e followed by a combining acute accent
+            ("ae\u{301}", "e\u{301}a"),
+            // Regional indicator pairs: the French and German flags
+            ("🇫🇷🇩🇪", "🇩🇪🇫🇷"),
+            ("abc", "cba"),
+            ("", ""),
+        ] {
+            assert_eq!(reverse(text), reversed, "{:?}", text);
+        }
+
+        // Reversing chars would detach the modifier, the accent and the flag halves
+        assert_eq!(by_chars("é👍🏽"), "🏽👍é");
+        assert_eq!(by_chars("ae\u{301}"), "\u{301}ea");
+        assert_eq!(by_chars("🇫🇷🇩🇪"), "🇪🇩🇷🇫");
+        assert_ne!(reverse("🇫🇷🇩🇪"), by_chars("🇫🇷🇩🇪"));
+    }
+
+    fn fill(pattern: &str, length: u64) -> CallToolResult {
+        execute_fill(&Some(serde_json::json!({ "pattern": pattern, "length": length }).to_string()))
+    }
+
+    #[test]
+    fn fill_repeats_multibyte_patterns_by_character() {
+        assert_eq!(text(&fill("ab", 5)), "ababa");
+        assert_eq!(text(&fill("é–", 3)), "é–é");
+        // Cutting mid-pattern never splits a multibyte character
+        assert_eq!(text(&fill("a👍🏽", 5)), "a👍🏽a👍");
+        assert_eq!(text(&fill("🎉", 2)), "🎉🎉");
+        assert_eq!(text(&fill("xyz", 0)), "");
+        assert_ne!(fill("xyz", 0).is_error, Some(true));
+    }
+
+    #[test]
+    fn fill_rejects_output_over_one_mebibyte() {
+        assert_eq!(text(&fill("x", MAX_REPEAT_BYTES as u64)).len(), MAX_REPEAT_BYTES);
+
+        // Four bytes per character, so a quarter of the limit plus one is over it
+        let result = fill("🎉", (MAX_REPEAT_BYTES / 4 + 1) as u64);
+        assert_eq!(result.is_error, Some(true));
+        assert_eq!(
+            text(&result),
+            format!(
+                "Error: output would be {} bytes, exceeding the maximum of {} bytes",
+                MAX_REPEAT_BYTES + 4,
+                MAX_REPEAT_BYTES
+            )
+        );
+        assert_eq!(fill("ab", u64::MAX).is_error, Some(true));
+    }
+
+    #[test]
+    fn acronym_splits_on_whitespace_and_hyphens() {
+        assert_eq!(acronym("Portable Document Format", 0, false), "PDF");
+        assert_eq!(acronym("don't panic", 0, false), "DP");
+        assert_eq!(acronym("  (Read-only) memory, please!  ", 0, false), "ROMP");
+        assert_eq!(acronym("über straße", 0, false), "ÜS");
+        assert_eq!(acronym("... --- ...", 0, false), "");
+    }
+
+    #[test]
+    fn acronym_skips_stopwords_and_short_words() {
+        let phrase = "The Federal Bureau of Investigation";
+        assert_eq!(acronym(phrase, 0, false), "TFBOI");
+        assert_eq!(acronym(phrase, 0, true), "FBI");
+        assert_eq!(acronym("Bank Of America", 0, true), "BA");
+
+        // Letters are counted without the punctuation around or inside a word
+        assert_eq!(acronym("as soon as possible", 3, false), "SP");
+        assert_eq!(acronym("it's a go", 3, false), "I");
+        assert_eq!(acronym("a b c", 2, false), "");
+        assert_eq!(
+            run(
+                execute_acronym,
+                serde_json::json!({"text": "Graphics Processing Unit of a PC", "min_word_len": 2, "skip_stopwords": true})
+            ),
+            "GPUP"
+        );
+    }
+
+    #[test]
+    fn title_case_starts_each_word_at_its_first_letter() {
+        let title_case = |text: &str| run(execute_title_case, serde_json::json!({ "text": text }));
+        assert_eq!(title_case("hello WORLD"), "Hello World");
+        assert_eq!(title_case("1st place \"quoted\" words"), "1St Place \"Quoted\" Words");
+        assert_eq!(title_case("(the) end"), "(The) End");
+        assert_eq!(title_case("ßtraße groß"), "SStraße Groß");
+        assert_eq!(title_case("ÉCOLE  normale"), "École  Normale");
+        assert_eq!(title_case(""), "");
+    }
+
+    #[test]
+    fn capitalize_changes_only_the_first_character() {
+        let capitalize = |text: &str| run(execute_capitalize, serde_json::json!({ "text": text }));
+        assert_eq!(capitalize("hello WORLD"), "Hello WORLD");
+        assert_eq!(capitalize("1st place"), "1st place");
+        assert_eq!(capitalize(" hello"), " hello");
+        assert_eq!(capitalize("ßtraße"), "SStraße");
+        assert_eq!(capitalize("élan"), "Élan");
+        assert_eq!(capitalize(""), "");
+    }
+
+    fn sort_keys(text: &str, compact: bool) -> String {
+        run(execute_json_sort_keys, serde_json::json!({ "text": text, "compact": compact }))
+    }
+
+    #[test]
+    fn json_sort_keys_sorts_nested_objects() {
+        let text = r#"{"b": {"z": 1, "a": {"d": true, "c": null}}, "a": [3, 1, 2], "B": "upper"}"#;
+        assert_eq!(
+            sort_keys(text, true),
+            r#"{"B":"upper","a":[3,1,2],"b":{"a":{"c":null,"d":true},"z":1}}"#
+        );
+        assert_eq!(
+            sort_keys(text, false),
+            "{\n  \"B\": \"upper\",\n  \"a\": [\n    3,\n    1,\n    2\n  ],\n  \"b\": {\n    \"a\": {\n      \"c\": null,\n      \"d\": true\n    },\n    \"z\": 1\n  }\n}"
+        );
+    }
+
+    #[test]
+    fn json_sort_keys_sorts_objects_inside_arrays() {
+        let text = r#"[{"y": 2, "x": 1}, {"b": [{"d": 4, "c": 3}], "a": 0}]"#;
+        assert_eq!(sort_keys(text, true), r#"[{"x":1,"y":2},{"a":0,"b":[{"c":3,"d":4}]}]"#);
+        assert_eq!(
+            sort_keys(text, false),
+            "[\n  {\n    \"x\": 1,\n    \"y\": 2\n  },\n  {\n    \"a\": 0,\n    \"b\": [\n      {\n        \"c\": 3,\n        \"d\": 4\n      }\n    ]\n  }\n]"
+        );
+    }
+
+    #[test]
+    fn json_sort_keys_is_deterministic_across_key_orders() {
+        let reordered = r#"[{"x": 1, "y": 2}, {"a": 0, "b": [{"c": 3, "d": 4}]}]"#;
+        let original = r#"[{"y": 2, "x": 1}, {"b": [{"d": 4, "c": 3}], "a": 0}]"#;
+        for compact in [true, false] {
+            assert_eq!(sort_keys(original, compact), sort_keys(reordered, compact));
+            // Sorting sorted output changes nothing
+            assert_eq!(sort_keys(&sort_keys(original, compact), compact), sort_keys(original, compact));
+        }
+    }
+
+    fn json_equal(a: &str, b: &str) -> serde_json::Value {
+        serde_json::from_str(&run(execute_json_equal, serde_json::json!({ "a": a, "b": b }))).unwrap()
+    }
+
+    #[test]
+    fn json_equal_ignores_key_order_and_number_representation() {
+        assert_eq!(
+            json_equal(
+                r#"{"a": 1, "b": {"c": [1, 2], "d": null}}"#,
+                r#"{"b": {"d": null, "c": [1, 2]}, "a": 1}"#
+            ),
+            serde_json::json!({"equal": true})
+        );
+        assert_eq!(json_equal("1", "1.0"), serde_json::json!({"equal": true}));
+        assert_eq!(json_equal(r#"{"n": [1, -2]}"#, r#"{"n": [1.0, -2.0]}"#), serde_json::json!({"equal": true}));
+        // Integers beyond f64 precision still compare exactly
+        assert_eq!(
+            json_equal("9007199254740993", "9007199254740992"),
+            serde_json::json!({"equal": false, "path": ""})
+        );
+    }
+
+    #[test]
+    fn json_equal_reports_the_first_differing_path() {
+        assert_eq!(
+            json_equal(r#"{"a": {"b": [1, 2, 3]}}"#, r#"{"a": {"b": [1, 5, 3]}}"#),
+            serde_json::json!({"equal": false, "path": "/a/b/1"})
+        );
+        assert_eq!(
+            json_equal(r#"{"a": 1, "b": 2}"#, r#"{"a": 1}"#),
+            serde_json::json!({"equal": false, "path": "/b"})
+        );
+        assert_eq!(json_equal(r#""1""#, "1"), serde_json::json!({"equal": false, "path": ""}));
+    }
+
+    #[test]
+    fn json_equal_escapes_pointer_segments() {
+        assert_eq!(
+            json_equal(r#"{"a/b": {"c~d": 1}}"#, r#"{"a/b": {"c~d": 2}}"#),
+            serde_json::json!({"equal": false, "path": "/a~1b/c~0d"})
+        );
+    }
+
+    #[test]
+    fn json_equal_reports_array_length_mismatches() {
+        assert_eq!(
+            json_equal(r#"{"list": [1, 2]}"#, r#"{"list": [1, 2, 3]}"#),
+            serde_json::json!({"equal": false, "path": "/list/2"})
+        );
+        assert_eq!(json_equal("[]", "[0]"), serde_json::json!({"equal": false, "path": "/0"}));
+    }
+}
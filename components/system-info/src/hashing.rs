@@ -0,0 +1,72 @@
+//! Digest algorithms offered by the `hash` tool
+//!
+//! SHA-256 and SHA-512 are the choices for integrity checks against an
+//! adversary. SHA-1 and MD5 have practical collision attacks and are kept
+//! only for interoperating with legacy checksums. CRC32 (IEEE 802.3, as used
+//! by zip and PNG) detects accidental corruption and nothing more.
+
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+#[derive(Clone, Copy)]
+pub enum Algorithm {
+    Sha256,
+    Sha512,
+    Sha1,
+    Md5,
+    Crc32,
+}
+
+impl Algorithm {
+    pub const NAMES: [&'static str; 5] = ["sha256", "sha512", "sha1", "md5", "crc32"];
+
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "sha256" => Ok(Algorithm::Sha256),
+            "sha512" => Ok(Algorithm::Sha512),
+            "sha1" => Ok(Algorithm::Sha1),
+            "md5" => Ok(Algorithm::Md5),
+            "crc32" => Ok(Algorithm::Crc32),
+            other => Err(format!(
+                "Invalid algorithm '{}': expected one of {}",
+                other,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha512 => "sha512",
+            Algorithm::Sha1 => "sha1",
+            Algorithm::Md5 => "md5",
+            Algorithm::Crc32 => "crc32",
+        }
+    }
+
+    /// Digest of `bytes`; a CRC32 is its 4 big-endian bytes, as usually printed
+    pub fn digest(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Algorithm::Sha256 => Sha256::digest(bytes).to_vec(),
+            Algorithm::Sha512 => Sha512::digest(bytes).to_vec(),
+            Algorithm::Sha1 => Sha1::digest(bytes).to_vec(),
+            Algorithm::Md5 => Md5::digest(bytes).to_vec(),
+            Algorithm::Crc32 => crc32(bytes).to_be_bytes().to_vec(),
+        }
+    }
+}
+
+/// Bitwise CRC-32 with the reflected IEEE polynomial; inputs are small, so
+/// the 1 KiB lookup table is not worth it
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
@@ -1,6 +1,18 @@
 //! system-info Tools Capability Provider
 //!
 //! A tools capability that provides system utility operations.
+//!
+//! Individual tools can be rate limited per instance; see [`rate_limit`].
+//! Which tools are exposed at all is configured by [`tool_filter`], and
+//! `list_tools` returns them in pages (see [`pagination`]).
+//!
+//! The random tools draw from the host's secure random source (wasi:random).
+//! Given a `seed` they switch to a seeded ChaCha20 generator instead, so tests
+//! get reproducible output; seeded output is predictable to anyone who knows
+//! the seed and must not be used for secrets.
+//!
+//! `uuid_v7` and `ulid` share their layout and same-millisecond ordering
+//! through [`time_ordered`].
 
 mod bindings {
     wit_bindgen::generate!({
@@ -9,91 +21,534 @@ mod bindings {
     });
 }
 
+mod hashing;
+mod radix;
+mod rate_limit;
+mod time_ordered;
+
 use bindings::exports::wasmcp::protocol::tools::Guest;
 use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasi::io::streams::OutputStream;
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use mcp_utils::annotations::ToolHints;
+use mcp_utils::args::{parse_arg, parse_optional_arg, parse_text_arg, parse_two_string_args};
+use mcp_utils::{pagination, tool_filter};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use sha2::{Digest, Sha256, Sha512};
 use std::time::{SystemTime, UNIX_EPOCH};
 
+mcp_utils::result_builders!();
+use result_builder::{error_result, json_result, structured_success_result, success_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
+
 struct SystemInfo;
 
 impl Guest for SystemInfo {
     fn list_tools(
         _ctx: bindings::wasmcp::protocol::server_messages::Context,
-        _request: ListToolsRequest,
+        request: ListToolsRequest,
         _client_stream: Option<&OutputStream>,
     ) -> Result<ListToolsResult, ErrorCode> {
-        Ok(ListToolsResult {
-            tools: vec![
-                Tool {
-                    name: "timestamp".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {},
-                        "required": []
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Get current Unix timestamp".to_string()),
-                        output_schema: None,
-                        title: Some("Timestamp".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "random_uuid".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {},
-                        "required": []
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Generate a random UUID v4".to_string()),
-                        output_schema: None,
-                        title: Some("Random UUID".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "base64_encode".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "text": {"type": "string", "description": "Text to encode to base64"}
+        let exposed: Vec<Tool> = vec![
+            Tool {
+                name: "timestamp".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                    description: Some("Get current Unix timestamp".to_string()),
+                    output_schema: None,
+                    title: Some("Timestamp".to_string()),
+                }),
+            },
+            Tool {
+                name: "timestamp_millis".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                    description: Some("Get current Unix timestamp in milliseconds".to_string()),
+                    output_schema: None,
+                    title: Some("Timestamp (Milliseconds)".to_string()),
+                }),
+            },
+            Tool {
+                name: "timestamp_nanos".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                    description: Some("Get current Unix timestamp in nanoseconds".to_string()),
+                    output_schema: None,
+                    title: Some("Timestamp (Nanoseconds)".to_string()),
+                }),
+            },
+            Tool {
+                name: "timestamp_iso".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                    description: Some("Get the current UTC time as an RFC 3339 / ISO 8601 string (e.g. 2024-01-02T03:04:05Z)".to_string()),
+                    output_schema: None,
+                    title: Some("ISO Timestamp".to_string()),
+                }),
+            },
+            Tool {
+                name: "iso8601_timestamp".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                    description: Some("Get the current UTC time as an ISO 8601 string (e.g. 2025-01-15T10:30:00Z). Same output as timestamp_iso".to_string()),
+                    output_schema: None,
+                    title: Some("ISO 8601 Timestamp".to_string()),
+                }),
+            },
+            Tool {
+                name: "random_uuid".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "seed": {"type": "integer", "minimum": 0, "maximum": 18446744073709551615, "description": "Seed for reproducible output in tests. Seeded output is NOT cryptographically secure"}
+                    },
+                    "required": []
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                    description: Some("Generate a random UUID v4 from the host's cryptographically secure random source, or reproducibly from 'seed' (not secure). If the host returns too few random bytes, falls back to a clock-seeded generator and says so in the result meta".to_string()),
+                    output_schema: None,
+                    title: Some("Random UUID".to_string()),
+                }),
+            },
+            Tool {
+                name: "uuid_v7".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "seed": {"type": "integer", "minimum": 0, "maximum": 18446744073709551615, "description": "Seed for reproducible random bits in tests (the timestamp still comes from the clock). Seeded output is NOT cryptographically secure"}
+                    },
+                    "required": []
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                    description: Some("Generate a time-ordered UUID v7: the current Unix time in milliseconds followed by random bits, so values sort by creation time (useful as database keys). Ids made in the same millisecond by this instance still increase. The result also gives the embedded timestamp. 'seed' makes the random bits reproducible (not secure, and without the same-millisecond ordering)".to_string()),
+                    output_schema: Some(TIME_ORDERED_OUTPUT_SCHEMA.to_string()),
+                    title: Some("UUID v7".to_string()),
+                }),
+            },
+            Tool {
+                name: "ulid".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "seed": {"type": "integer", "minimum": 0, "maximum": 18446744073709551615, "description": "Seed for reproducible random bits in tests (the timestamp still comes from the clock). Seeded output is NOT cryptographically secure"}
+                    },
+                    "required": []
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                    description: Some("Generate a ULID: 26 Crockford base32 characters encoding the current Unix time in milliseconds and 80 random bits, so values sort by creation time both as text and as bytes. Ids made in the same millisecond by this instance still increase. The result also gives the embedded timestamp. 'seed' makes the random bits reproducible (not secure, and without the same-millisecond ordering)".to_string()),
+                    output_schema: Some(TIME_ORDERED_OUTPUT_SCHEMA.to_string()),
+                    title: Some("ULID".to_string()),
+                }),
+            },
+            Tool {
+                name: "base64_encode".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to encode to base64"},
+                        "alphabet": {
+                            "type": "string",
+                            "enum": ["standard", "url"],
+                            "description": "standard uses + and /, url uses - and _ (default: standard)"
                         },
-                        "required": ["text"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Encode string to base64".to_string()),
-                        output_schema: None,
-                        title: Some("Base64 Encode".to_string()),
-                    }),
-                },
-                Tool {
-                    name: "base64_decode".to_string(),
-                    input_schema: r#"{
-                        "type": "object",
-                        "properties": {
-                            "text": {"type": "string", "description": "Base64 text to decode"}
-                        },
-                        "required": ["text"]
-                    }"#
-                    .to_string(),
-                    options: Some(ToolOptions {
-                        meta: None,
-                        annotations: None,
-                        description: Some("Decode base64 to string".to_string()),
-                        output_schema: None,
-                        title: Some("Base64 Decode".to_string()),
-                    }),
-                },
-            ],
-            next_cursor: None,
+                        "padding": {"type": "boolean", "description": "Pad the output with = to a multiple of 4 characters (default: true)"},
+                        "variant": {
+                            "type": "string",
+                            "enum": ["standard", "url_safe", "url_safe_no_pad"],
+                            "description": "Older shorthand for alphabet and padding; cannot be combined with them"
+                        }
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Encode string to base64. Defaults to the standard alphabet with padding; JWTs and most web APIs want alphabet url without padding".to_string()),
+                    output_schema: None,
+                    title: Some("Base64 Encode".to_string()),
+                }),
+            },
+            Tool {
+                name: "base64_decode".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Base64 text to decode, in the standard or URL-safe alphabet, padded or not"},
+                        "output": {
+                            "type": "string",
+                            "enum": ["text", "hex", "base64url"],
+                            "description": "How to return the decoded bytes: text (must be UTF-8), lowercase hex, or unpadded URL-safe base64. Default: text when the bytes are UTF-8, otherwise hex"
+                        }
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Decode base64 written with either alphabet (+/ or -_), with or without padding. UTF-8 data is returned as text; binary data, such as an image, is returned as hex unless another output is chosen. The structured result gives the output form and the length in bytes".to_string()),
+                    output_schema: Some(BASE64_DECODE_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Base64 Decode".to_string()),
+                }),
+            },
+            Tool {
+                name: "hex_encode".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to encode to hex"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Encode the UTF-8 bytes of a string as lowercase hex".to_string()),
+                    output_schema: None,
+                    title: Some("Hex Encode".to_string()),
+                }),
+            },
+            Tool {
+                name: "hex_decode".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Hex text to decode (upper or lower case)"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Decode hex to string; the bytes must be valid UTF-8".to_string()),
+                    output_schema: None,
+                    title: Some("Hex Decode".to_string()),
+                }),
+            },
+            Tool {
+                name: "hash_sha256".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to hash; its UTF-8 bytes are digested"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Compute the SHA-256 digest of the text's UTF-8 bytes as 64 lowercase hex characters. SHA-256 is a collision-resistant cryptographic hash, suitable for fingerprints and integrity checks; it is not a password hash".to_string()),
+                    output_schema: None,
+                    title: Some("SHA-256 Hash".to_string()),
+                }),
+            },
+            Tool {
+                name: "hash_sha512".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to hash; its UTF-8 bytes are digested"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Compute the SHA-512 digest of the text's UTF-8 bytes as 128 lowercase hex characters. SHA-512 is a collision-resistant cryptographic hash with a longer output than SHA-256; it is not a password hash".to_string()),
+                    output_schema: None,
+                    title: Some("SHA-512 Hash".to_string()),
+                }),
+            },
+            Tool {
+                name: "hash_md5".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to hash; its UTF-8 bytes are digested"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Compute the MD5 digest of the text's UTF-8 bytes as 32 lowercase hex characters. MD5 is NOT cryptographically secure, since collisions are cheap to produce; use it only for non-adversarial checksums and legacy compatibility".to_string()),
+                    output_schema: None,
+                    title: Some("MD5 Hash".to_string()),
+                }),
+            },
+            Tool {
+                name: "hmac_sha256".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "message": {"type": "string", "description": "Message to authenticate; its UTF-8 bytes are signed"},
+                        "key": {"type": "string", "description": "Secret key, used as its UTF-8 bytes (not decoded from hex or base64)"}
+                    },
+                    "required": ["message", "key"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Compute the HMAC-SHA256 of a message as 64 lowercase hex characters. The key is taken as a UTF-8 string, so binary keys must be given in a form that is valid text. The key is sent in the tool arguments and may be logged by clients or servers along the way".to_string()),
+                    output_schema: None,
+                    title: Some("HMAC-SHA256".to_string()),
+                }),
+            },
+            Tool {
+                name: "hmac_sign".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "message": {"type": "string", "description": "Message to sign; its UTF-8 bytes are signed"},
+                        "key": {"type": "string", "description": "Secret key, interpreted according to 'key_encoding'"},
+                        "key_encoding": {"type": "string", "enum": ["utf8", "hex", "base64"], "description": "How 'key' is written (default: utf8); use hex or base64 for binary keys"},
+                        "encoding": {"type": "string", "enum": ["hex", "base64"], "description": "Encoding of the returned signature (default: hex)"}
+                    },
+                    "required": ["message", "key"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Sign a message with HMAC-SHA256, returning the 32-byte signature as lowercase hex or standard base64. Binary keys can be given as hex or base64. The key is sent in the tool arguments and may be logged by clients or servers along the way".to_string()),
+                    output_schema: None,
+                    title: Some("HMAC Sign".to_string()),
+                }),
+            },
+            Tool {
+                name: "hmac_verify".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "message": {"type": "string", "description": "Message whose signature is checked; its UTF-8 bytes are signed"},
+                        "key": {"type": "string", "description": "Secret key, interpreted according to 'key_encoding'"},
+                        "key_encoding": {"type": "string", "enum": ["utf8", "hex", "base64"], "description": "How 'key' is written (default: utf8); use hex or base64 for binary keys"},
+                        "signature": {"type": "string", "description": "HMAC-SHA256 signature to check"},
+                        "encoding": {"type": "string", "enum": ["hex", "base64"], "description": "How 'signature' is written"}
+                    },
+                    "required": ["message", "key", "signature", "encoding"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Check an HMAC-SHA256 signature in constant time. A mismatch is a normal result, {\"valid\": false}, not an error; only malformed arguments, such as a signature that is not valid hex or base64, are errors".to_string()),
+                    output_schema: Some(HMAC_VERIFY_OUTPUT_SCHEMA.to_string()),
+                    title: Some("HMAC Verify".to_string()),
+                }),
+            },
+            Tool {
+                name: "hash".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to hash; its UTF-8 bytes are digested. Mutually exclusive with 'base64'"},
+                        "base64": {"type": "string", "description": "Binary input as base64, standard or URL-safe. Mutually exclusive with 'text'"},
+                        "algorithm": {
+                            "type": "string",
+                            "enum": ["sha256", "sha512", "sha1", "md5", "crc32"],
+                            "description": "Digest algorithm (default: sha256)"
+                        }
+                    },
+                    "required": []
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Compute a digest of text or base64-encoded bytes as lowercase hex. sha256 and sha512 are secure cryptographic hashes. sha1 and md5 are legacy, not for security: collisions can be crafted. crc32 only detects accidental corruption. The structured result repeats the algorithm and gives the digest length in bytes".to_string()),
+                    output_schema: Some(HASH_OUTPUT_SCHEMA.to_string()),
+                    title: Some("Hash".to_string()),
+                }),
+            },
+            Tool {
+                name: "url_encode".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text to percent-encode"},
+                        "mode": {"type": "string", "enum": ["form", "component"], "description": "form (default): application/x-www-form-urlencoded, spaces become '+'. component: RFC 3986, only A-Z a-z 0-9 - . _ ~ are left as is and spaces become %20"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some(
+                        "Percent-encode a string for a query string (form mode, spaces become '+') or for any URL component such as a path segment (component mode, spaces become %20)"
+                            .to_string(),
+                    ),
+                    output_schema: None,
+                    title: Some("URL Encode".to_string()),
+                }),
+            },
+            Tool {
+                name: "url_decode".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Percent-encoded text to decode"},
+                        "mode": {"type": "string", "enum": ["form", "component"], "description": "form (default): '+' becomes a space. component: '+' is kept as is"}
+                    },
+                    "required": ["text"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some(
+                        "Decode percent-encoded text. A '%' not followed by two hex digits is an error, and the decoded bytes must be valid UTF-8"
+                            .to_string(),
+                    ),
+                    output_schema: None,
+                    title: Some("URL Decode".to_string()),
+                }),
+            },
+            Tool {
+                name: "random_bytes".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "length": {"type": "integer", "minimum": 1, "maximum": 1024, "description": "Number of random bytes to generate"},
+                        "seed": {"type": "integer", "minimum": 0, "maximum": 18446744073709551615, "description": "Seed for reproducible output in tests. Seeded output is NOT cryptographically secure"}
+                    },
+                    "required": ["length"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints { idempotent: false, ..ToolHints::SAFE })),
+                    description: Some("Generate cryptographically secure random bytes from the host (wasi:random), returned hex-encoded. 'seed' gives reproducible bytes for tests instead (not secure)".to_string()),
+                    output_schema: None,
+                    title: Some("Random Bytes".to_string()),
+                }),
+            },
+            Tool {
+                name: "base_encode".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "data": {"type": "string", "description": "Bytes to encode, as standard base64"},
+                        "alphabet": {"type": "string", "minLength": 2, "description": "2 to 255 distinct symbols; the radix is the number of symbols and the first symbol is zero"}
+                    },
+                    "required": ["data", "alphabet"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Encode bytes in an arbitrary radix, treating them as one big integer written with a custom alphabet (e.g. the base58 alphabet). Leading zero bytes become leading zero symbols".to_string()),
+                    output_schema: None,
+                    title: Some("Base Encode".to_string()),
+                }),
+            },
+            Tool {
+                name: "base_decode".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "text": {"type": "string", "description": "Text produced by base_encode"},
+                        "alphabet": {"type": "string", "minLength": 2, "description": "The alphabet the text was encoded with"}
+                    },
+                    "required": ["text", "alphabet"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some("Decode text written with a custom alphabet by base_encode, returning the bytes as standard base64".to_string()),
+                    output_schema: None,
+                    title: Some("Base Decode".to_string()),
+                }),
+            },
+            Tool {
+                name: "env_var".to_string(),
+                input_schema: r#"{
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string", "description": "Name of the environment variable"}
+                    },
+                    "required": ["name"]
+                }"#
+                .to_string(),
+                options: Some(ToolOptions {
+                    meta: None,
+                    annotations: Some(build_annotations(ToolHints::SAFE)),
+                    description: Some(format!(
+                        "Read an environment variable of the component's runtime. Only these variables may be read: {}",
+                        ENV_VAR_ALLOWLIST.join(", ")
+                    )),
+                    output_schema: None,
+                    title: Some("Environment Variable".to_string()),
+                }),
+            },
+        ]
+        .into_iter()
+        .filter(|tool| tool_filter::is_exposed(&tool.name))
+        .collect();
+        let (tools, next_cursor) =
+            pagination::paginate(exposed, request.cursor.as_deref(), TOOLS_PAGE_SIZE);
+
+        Ok(ListToolsResult {
+            tools,
+            next_cursor,
             meta: None,
         })
     }
@@ -103,16 +558,51 @@ impl Guest for SystemInfo {
         request: CallToolRequest,
         _client_stream: Option<&OutputStream>,
     ) -> Option<CallToolResult> {
-        match request.name.as_str() {
-            "timestamp" => Some(execute_timestamp()),
-            "random_uuid" => Some(execute_random_uuid()),
-            "base64_encode" => Some(execute_base64_encode(&request.arguments)),
-            "base64_decode" => Some(execute_base64_decode(&request.arguments)),
-            _ => None, // We don't handle this tool
+        if !tool_filter::is_exposed(&request.name) {
+            return None;
         }
+
+        let execute: fn(&Option<String>) -> CallToolResult = match request.name.as_str() {
+            "timestamp" => |_| execute_timestamp(),
+            "timestamp_millis" => |_| execute_timestamp_millis(),
+            "timestamp_nanos" => |_| execute_timestamp_nanos(),
+            // iso8601_timestamp is an alias kept for discoverability
+            "timestamp_iso" | "iso8601_timestamp" => |_| execute_timestamp_iso(),
+            "random_uuid" => execute_random_uuid,
+            "uuid_v7" => execute_uuid_v7,
+            "ulid" => execute_ulid,
+            "random_bytes" => execute_random_bytes,
+            "base64_encode" => execute_base64_encode,
+            "base64_decode" => execute_base64_decode,
+            "hex_encode" => execute_hex_encode,
+            "hex_decode" => execute_hex_decode,
+            "hash_sha256" => execute_hash::<Sha256>,
+            "hash_sha512" => execute_hash::<Sha512>,
+            "hash_md5" => execute_hash::<Md5>,
+            "hmac_sha256" => execute_hmac_sha256,
+            "hmac_sign" => execute_hmac_sign,
+            "hmac_verify" => execute_hmac_verify,
+            "hash" => execute_hash_with_algorithm,
+            "url_encode" => execute_url_encode,
+            "url_decode" => execute_url_decode,
+            "base_encode" => execute_base_encode,
+            "base_decode" => execute_base_decode,
+            "env_var" => execute_env_var,
+            _ => return None, // We don't handle this tool
+        };
+
+        // Only tools handled here are checked, so limits never shadow other components
+        if let Err(msg) = rate_limit::check(&request.name) {
+            return Some(error_result(msg));
+        }
+
+        Some(execute(&request.arguments))
     }
 }
 
+/// Largest number of tools returned by one `list_tools` call
+const TOOLS_PAGE_SIZE: usize = pagination::DEFAULT_PAGE_SIZE;
+
 fn execute_timestamp() -> CallToolResult {
     match SystemTime::now().duration_since(UNIX_EPOCH) {
         Ok(duration) => {
@@ -123,113 +613,1038 @@ fn execute_timestamp() -> CallToolResult {
     }
 }
 
-fn execute_random_uuid() -> CallToolResult {
-    // Simple UUID v4 generation
-    // In production, you might want to use the uuid crate
-    let uuid = format!(
-        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
-        random_u32(),
-        random_u16(),
-        (random_u16() & 0x0fff) | 0x4000, // Version 4
-        (random_u16() & 0x3fff) | 0x8000, // Variant 10
-        random_u64() & 0xffffffffffff
-    );
-    success_result(uuid)
+fn execute_timestamp_millis() -> CallToolResult {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => {
+            let timestamp = duration.as_millis();
+            success_result(timestamp.to_string())
+        }
+        Err(e) => error_result(format!("Failed to get timestamp: {}", e)),
+    }
+}
+
+fn execute_timestamp_nanos() -> CallToolResult {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => {
+            let timestamp = duration.as_nanos();
+            success_result(timestamp.to_string())
+        }
+        Err(e) => error_result(format!("Failed to get timestamp: {}", e)),
+    }
+}
+
+fn execute_timestamp_iso() -> CallToolResult {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => success_result(format_rfc3339(duration.as_secs())),
+        Err(e) => error_result(format!("Failed to get timestamp: {}", e)),
+    }
+}
+
+/// Format Unix seconds as an RFC 3339 UTC timestamp, e.g. `2024-01-02T03:04:05Z`
+fn format_rfc3339(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60
+    )
+}
+
+/// Format Unix milliseconds as an RFC 3339 UTC timestamp, e.g. `2024-01-02T03:04:05.678Z`
+fn format_rfc3339_millis(unix_millis: u64) -> String {
+    let seconds = format_rfc3339(unix_millis / 1000);
+    format!("{}.{:03}Z", seconds.trim_end_matches('Z'), unix_millis % 1000)
+}
+
+/// Convert days since 1970-01-01 to a proleptic Gregorian (year, month, day).
+///
+/// Howard Hinnant's `civil_from_days`: shifts the year to start in March so the
+/// leap day falls at the end, then works in 400-year eras of 146097 days.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+fn execute_random_uuid(arguments: &Option<String>) -> CallToolResult {
+    let mut source = match parse_random_source(arguments) {
+        Ok(source) => source,
+        Err(msg) => return error_result(msg),
+    };
+    let (uuid, entropy) = random_uuid(&mut source);
+    let mut result = success_result(uuid);
+    if let Entropy::TimeSeeded = entropy {
+        result.meta = Some(r#"{"entropy":"time-seeded fallback"}"#.to_string());
+    }
+    result
+}
+
+/// Where the bytes of a generated value came from
+enum Entropy {
+    Source,
+    /// The source returned the wrong number of bytes, so the clock-seeded
+    /// [`fallback_bytes`] were used instead
+    TimeSeeded,
+}
+
+/// A version 4 UUID from 16 bytes of `source`
+fn random_uuid(source: &mut RandomSource) -> (String, Entropy) {
+    match source.bytes(16).try_into() {
+        Ok(bytes) => (uuid_v4(bytes), Entropy::Source),
+        Err(_) => (uuid_v4(fallback_bytes()), Entropy::TimeSeeded),
+    }
+}
+
+fn uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40; // Version 4
+    bytes[8] = (bytes[8] & 0x3f) | 0x80; // Variant 10
+    format_uuid(&bytes)
+}
+
+/// Bytes from a generator seeded once from the clock. Predictable, so only
+/// used when the host's wasi:random misbehaves; drawing from one stream keeps
+/// values generated in the same nanosecond distinct.
+fn fallback_bytes() -> [u8; 16] {
+    static FALLBACK: std::sync::Mutex<Option<ChaCha20Rng>> = std::sync::Mutex::new(None);
+
+    let mut guard = FALLBACK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let rng = guard.get_or_insert_with(|| {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+        ChaCha20Rng::seed_from_u64(nanos as u64)
+    });
+    let mut bytes = [0; 16];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn execute_uuid_v7(arguments: &Option<String>) -> CallToolResult {
+    execute_time_ordered(arguments, &time_ordered::UUID_V7, time_ordered::uuid_v7, format_uuid)
+}
+
+fn execute_ulid(arguments: &Option<String>) -> CallToolResult {
+    execute_time_ordered(arguments, &time_ordered::ULID, time_ordered::ulid, time_ordered::ulid_text)
+}
+
+const TIME_ORDERED_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "id": {"type": "string"},
+        "timestamp_ms": {"type": "integer", "minimum": 0, "description": "Unix time in milliseconds decoded from the id"},
+        "timestamp": {"type": "string", "description": "The same time in ISO 8601 UTC with milliseconds"}
+    },
+    "required": ["id", "timestamp_ms", "timestamp"]
+}"#;
+
+/// Generate a UUID v7 or ULID, reporting the timestamp decoded back out of it
+fn execute_time_ordered(
+    arguments: &Option<String>,
+    generator: &time_ordered::Generator,
+    layout: fn(u64, u128) -> [u8; 16],
+    text: fn(&[u8; 16]) -> String,
+) -> CallToolResult {
+    let mut source = match parse_random_source(arguments) {
+        Ok(source) => source,
+        Err(msg) => return error_result(msg),
+    };
+    let now_millis = match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_millis() as u64,
+        Err(e) => return error_result(format!("Failed to get timestamp: {}", e)),
+    };
+    let Ok(random) = source.bytes(16).try_into().map(u128::from_be_bytes) else {
+        return error_result("Host returned the wrong number of random bytes".to_string());
+    };
+
+    let next = match source {
+        RandomSource::Secure => generator.next(now_millis, random),
+        RandomSource::Seeded(_) => generator.unordered(now_millis, random),
+    };
+    let bytes = match next {
+        Ok((millis, random)) => layout(millis, random),
+        Err(msg) => return error_result(msg),
+    };
+
+    let id = text(&bytes);
+    let millis = time_ordered::timestamp_millis(&bytes);
+    structured_success_result(
+        id.clone(),
+        serde_json::json!({
+            "id": id,
+            "timestamp_ms": millis,
+            "timestamp": format_rfc3339_millis(millis),
+        }),
+    )
+}
+
+/// Hyphenated lowercase form, e.g. 123e4567-e89b-42d3-a456-426614174000
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let hex = to_hex(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Largest number of bytes `random_bytes` returns in one call
+const MAX_RANDOM_BYTES: i64 = 1024;
+
+fn execute_random_bytes(arguments: &Option<String>) -> CallToolResult {
+    let (length, mut source) = match parse_length_arg(arguments)
+        .and_then(|length| Ok((length, parse_random_source(arguments)?)))
+    {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    success_result(to_hex(&source.bytes(length)))
+}
+
+fn parse_length_arg(arguments: &Option<String>) -> Result<u64, String> {
+    let args_str = arguments
+        .as_ref()
+        .ok_or_else(|| "Missing arguments".to_string())?;
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    let length = json
+        .get("length")
+        .and_then(|v| v.as_i64())
+        .ok_or_else(|| "Missing or invalid parameter 'length' (expected an integer)".to_string())?;
+
+    if !(1..=MAX_RANDOM_BYTES).contains(&length) {
+        return Err(format!(
+            "Parameter 'length' must be between 1 and {}, got {}",
+            MAX_RANDOM_BYTES, length
+        ));
+    }
+    Ok(length as u64)
+}
+
+/// Where the random tools get their bytes
+enum RandomSource {
+    /// The host's cryptographically secure wasi:random implementation
+    Secure,
+    /// Reproducible output for tests; predictable from the seed
+    Seeded(Box<ChaCha20Rng>),
+}
+
+impl RandomSource {
+    fn bytes(&mut self, len: u64) -> Vec<u8> {
+        match self {
+            RandomSource::Secure => bindings::wasi::random::random::get_random_bytes(len),
+            RandomSource::Seeded(rng) => {
+                let mut bytes = vec![0; len as usize];
+                rng.fill_bytes(&mut bytes);
+                bytes
+            }
+        }
+    }
+}
+
+/// The optional `seed` parameter; tools without required parameters may be called without arguments
+fn parse_random_source(arguments: &Option<String>) -> Result<RandomSource, String> {
+    let Some(args_str) = arguments else {
+        return Ok(RandomSource::Secure);
+    };
+
+    let json: serde_json::Value =
+        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+
+    match json.get("seed") {
+        None | Some(serde_json::Value::Null) => Ok(RandomSource::Secure),
+        Some(value) => value
+            .as_u64()
+            .map(|seed| RandomSource::Seeded(Box::new(ChaCha20Rng::seed_from_u64(seed))))
+            .ok_or_else(|| {
+                format!("Invalid parameter 'seed': expected an integer between 0 and {}", u64::MAX)
+            }),
+    }
 }
 
 fn execute_base64_encode(arguments: &Option<String>) -> CallToolResult {
+    let engine = match base64_engine(arguments) {
+        Ok(engine) => engine,
+        Err(msg) => return error_result(msg),
+    };
+
     match parse_text_arg(arguments) {
         Ok(text) => {
-            use base64::{Engine as _, engine::general_purpose::STANDARD};
-            let encoded = STANDARD.encode(text.as_bytes());
+            use base64::Engine as _;
+            let encoded = engine.encode(text.as_bytes());
             success_result(encoded)
         }
         Err(msg) => error_result(msg),
     }
 }
 
+/// Shape of the `structured_content` returned by `base64_decode`
+const BASE64_DECODE_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "output": {"type": "string", "enum": ["text", "hex", "base64url"], "description": "How 'data' is written"},
+        "data": {"type": "string", "description": "The decoded bytes"},
+        "length": {"type": "integer", "minimum": 0, "description": "Number of decoded bytes"}
+    },
+    "required": ["output", "data", "length"]
+}"#;
+
 fn execute_base64_decode(arguments: &Option<String>) -> CallToolResult {
-    match parse_text_arg(arguments) {
-        Ok(text) => {
-            use base64::{Engine as _, engine::general_purpose::STANDARD};
-            match STANDARD.decode(&text) {
-                Ok(decoded_bytes) => {
-                    match String::from_utf8(decoded_bytes) {
-                        Ok(decoded_string) => success_result(decoded_string),
-                        Err(_) => error_result("Decoded data is not valid UTF-8 text".to_string()),
-                    }
-                }
-                Err(e) => error_result(format!("Invalid base64: {}", e)),
+    let parsed = parse_text_arg(arguments).and_then(|text| {
+        let output = parse_optional_arg::<String>(arguments, "output")?;
+        Ok((decode_base64(&text)?, output))
+    });
+    let (bytes, output) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    let length = bytes.len();
+    let (output, data, text) = match output.as_deref() {
+        None => match String::from_utf8(bytes) {
+            Ok(decoded) => ("text", decoded.clone(), decoded),
+            Err(e) => {
+                let hex = to_hex(e.as_bytes());
+                let text = format!("Decoded {} bytes of binary data (not valid UTF-8), shown as hex: {}", length, hex);
+                ("hex", hex, text)
+            }
+        },
+        Some("text") => match String::from_utf8(bytes) {
+            Ok(decoded) => ("text", decoded.clone(), decoded),
+            Err(e) => {
+                return error_result(format!(
+                    "Decoded data is not valid UTF-8 text (invalid byte at offset {}); use output hex or base64url for binary data",
+                    e.utf8_error().valid_up_to()
+                ))
             }
+        },
+        Some("hex") => {
+            let hex = to_hex(&bytes);
+            ("hex", hex.clone(), hex)
         }
+        Some("base64url") => {
+            use base64::Engine as _;
+            let encoded = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&bytes);
+            ("base64url", encoded.clone(), encoded)
+        }
+        Some(other) => {
+            return error_result(format!("Invalid output '{}': expected one of text, hex, base64url", other))
+        }
+    };
+
+    structured_success_result(
+        text,
+        serde_json::json!({
+            "output": output,
+            "data": data,
+            "length": length,
+        }),
+    )
+}
+
+fn execute_hex_encode(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => success_result(to_hex(text.as_bytes())),
         Err(msg) => error_result(msg),
     }
 }
 
-// Simple random number generators for UUID
-// In a real application, use a proper random number generator
-fn random_u16() -> u16 {
-    let time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos() as u16;
-    time.wrapping_mul(40503) // Simple hash (prime number that fits in u16)
+fn execute_hex_decode(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => match from_hex(&text) {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(decoded_string) => success_result(decoded_string),
+                Err(_) => error_result("Decoded data is not valid UTF-8 text".to_string()),
+            },
+            Err(msg) => error_result(msg),
+        },
+        Err(msg) => error_result(msg),
+    }
 }
 
-fn random_u32() -> u32 {
-    let time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos() as u32;
-    time.wrapping_mul(2654435761) // Simple hash
+/// Lowercase hex digest of the text's UTF-8 bytes
+fn execute_hash<D: Digest>(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments) {
+        Ok(text) => success_result(to_hex(&D::digest(text.as_bytes()))),
+        Err(msg) => error_result(msg),
+    }
 }
 
-fn random_u64() -> u64 {
-    let time = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_nanos() as u64;
-    time.wrapping_mul(11400714819323198485) // Simple hash
+const HASH_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "algorithm": {"type": "string", "enum": ["sha256", "sha512", "sha1", "md5", "crc32"]},
+        "digest": {"type": "string", "description": "Lowercase hex digest"},
+        "length": {"type": "integer", "minimum": 1, "description": "Digest length in bytes"}
+    },
+    "required": ["algorithm", "digest", "length"]
+}"#;
+
+fn execute_hash_with_algorithm(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_hash_input(arguments).and_then(|bytes| {
+        let algorithm = parse_optional_arg::<String>(arguments, "algorithm")?;
+        Ok((bytes, hashing::Algorithm::parse(algorithm.as_deref().unwrap_or("sha256"))?))
+    });
+    let (bytes, algorithm) = match parsed {
+        Ok(parsed) => parsed,
+        Err(msg) => return error_result(msg),
+    };
+
+    let digest = algorithm.digest(&bytes);
+    let hex = to_hex(&digest);
+    structured_success_result(
+        hex.clone(),
+        serde_json::json!({
+            "algorithm": algorithm.name(),
+            "digest": hex,
+            "length": digest.len(),
+        }),
+    )
 }
 
-fn parse_text_arg(arguments: &Option<String>) -> Result<String, String> {
-    let args_str = arguments
-        .as_ref()
-        .ok_or_else(|| "Missing arguments".to_string())?;
+/// The bytes to hash, from exactly one of `text` and `base64`
+fn parse_hash_input(arguments: &Option<String>) -> Result<Vec<u8>, String> {
+    let text = parse_optional_arg::<String>(arguments, "text")?;
+    let base64 = parse_optional_arg::<String>(arguments, "base64")?;
 
-    let json: serde_json::Value =
-        serde_json::from_str(args_str).map_err(|e| format!("Invalid JSON arguments: {}", e))?;
+    match (text, base64) {
+        (Some(_), Some(_)) => Err(
+            "Parameters 'text' and 'base64' are mutually exclusive: pass text as 'text' or binary data as 'base64'"
+                .to_string(),
+        ),
+        (Some(text), None) => Ok(text.into_bytes()),
+        (None, Some(base64)) => decode_base64(&base64),
+        (None, None) => Err("Missing input: pass either 'text' or 'base64'".to_string()),
+    }
+}
 
-    let text = json
-        .get("text")
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing or invalid parameter 'text'".to_string())?;
+/// Lowercase hex HMAC-SHA256 of the message's UTF-8 bytes, keyed by the key's UTF-8 bytes
+fn execute_hmac_sha256(arguments: &Option<String>) -> CallToolResult {
+    match parse_two_string_args(arguments, "message", "key") {
+        Ok((message, key)) => success_result(to_hex(&hmac_sha256(key.as_bytes(), &message).finalize().into_bytes())),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_hmac_sign(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_hmac_args(arguments).and_then(|mac| {
+        let encoding = parse_optional_arg::<String>(arguments, "encoding")?;
+        Ok((mac, SignatureEncoding::parse(encoding.as_deref().unwrap_or("hex"))?))
+    });
 
-    Ok(text.to_string())
+    match parsed {
+        Ok((mac, encoding)) => success_result(encoding.encode(&mac.finalize().into_bytes())),
+        Err(msg) => error_result(msg),
+    }
 }
 
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
+const HMAC_VERIFY_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "valid": {"type": "boolean"}
+    },
+    "required": ["valid"]
+}"#;
+
+fn execute_hmac_verify(arguments: &Option<String>) -> CallToolResult {
+    let parsed = parse_hmac_args(arguments).and_then(|mac| {
+        let signature: String = parse_arg(arguments, "signature")?;
+        let encoding = SignatureEncoding::parse(&parse_arg::<String>(arguments, "encoding")?)?;
+        Ok((mac, encoding.decode(&signature)?))
+    });
+
+    match parsed {
+        // verify_slice compares in constant time, and rejects wrong lengths
+        Ok((mac, signature)) => json_result(serde_json::json!({ "valid": mac.verify_slice(&signature).is_ok() })),
+        Err(msg) => error_result(msg),
     }
 }
 
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
+/// HMAC-SHA256 over the `message`, keyed by `key` decoded per `key_encoding`
+fn parse_hmac_args(arguments: &Option<String>) -> Result<Hmac<Sha256>, String> {
+    let (message, key) = parse_two_string_args(arguments, "message", "key")?;
+    let key = match parse_optional_arg::<String>(arguments, "key_encoding")?.as_deref() {
+        None | Some("utf8") => key.into_bytes(),
+        Some("hex") => from_hex(&key)?,
+        Some("base64") => decode_base64(&key)?,
+        Some(other) => {
+            return Err(format!("Invalid key_encoding '{}': expected one of utf8, hex, base64", other));
+        }
+    };
+    Ok(hmac_sha256(&key, &message))
+}
+
+fn hmac_sha256(key: &[u8], message: &str) -> Hmac<Sha256> {
+    // HMAC accepts keys of any length, hashing long ones down first
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(message.as_bytes());
+    mac
+}
+
+/// How an HMAC signature is written
+enum SignatureEncoding {
+    Hex,
+    Base64,
+}
+
+impl SignatureEncoding {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "hex" => Ok(SignatureEncoding::Hex),
+            "base64" => Ok(SignatureEncoding::Base64),
+            other => Err(format!("Invalid encoding '{}': expected hex or base64", other)),
+        }
+    }
+
+    fn encode(&self, bytes: &[u8]) -> String {
+        use base64::Engine as _;
+        match self {
+            SignatureEncoding::Hex => to_hex(bytes),
+            SignatureEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(bytes),
+        }
+    }
+
+    fn decode(&self, text: &str) -> Result<Vec<u8>, String> {
+        match self {
+            SignatureEncoding::Hex => from_hex(text),
+            SignatureEncoding::Base64 => decode_base64(text),
+        }
+    }
+}
+
+/// Lowercase hex, two digits per byte
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(text: &str) -> Result<Vec<u8>, String> {
+    if let Some((position, c)) = text.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid hex: '{}' at position {} is not a hex digit", c, position));
+    }
+    if !text.len().is_multiple_of(2) {
+        return Err(format!("Invalid hex: expected an even number of digits, got {}", text.len()));
+    }
+
+    // All digits are ASCII, so byte offsets and pairs line up
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).map_err(|e| format!("Invalid hex: {}", e)))
+        .collect()
+}
+
+fn execute_url_encode(arguments: &Option<String>) -> CallToolResult {
+    match parse_text_arg(arguments).and_then(|text| Ok((text, parse_url_mode(arguments)?))) {
+        Ok((text, UrlMode::Form)) => success_result(form_url_encode(&text)),
+        Ok((text, UrlMode::Component)) => success_result(utf8_percent_encode(&text, URL_COMPONENT).to_string()),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_url_decode(arguments: &Option<String>) -> CallToolResult {
+    let decoded = parse_text_arg(arguments).and_then(|text| {
+        let mode = parse_url_mode(arguments)?;
+        check_percent_sequences(&text)?;
+        Ok(match mode {
+            UrlMode::Form => percent_decode_str(&text.replace('+', " ")).collect::<Vec<u8>>(),
+            UrlMode::Component => percent_decode_str(&text).collect(),
+        })
+    });
+
+    match decoded {
+        Ok(bytes) => match String::from_utf8(bytes) {
+            Ok(decoded_string) => success_result(decoded_string),
+            Err(_) => error_result("Decoded data is not valid UTF-8 text".to_string()),
+        },
+        Err(msg) => error_result(msg),
+    }
+}
+
+/// How `url_encode` and `url_decode` treat spaces and reserved characters
+enum UrlMode {
+    /// application/x-www-form-urlencoded, as in query strings and form bodies
+    Form,
+    /// RFC 3986 percent-encoding, safe in any URL component
+    Component,
+}
+
+fn parse_url_mode(arguments: &Option<String>) -> Result<UrlMode, String> {
+    match parse_optional_arg::<String>(arguments, "mode")?.as_deref() {
+        None | Some("form") => Ok(UrlMode::Form),
+        Some("component") => Ok(UrlMode::Component),
+        Some(other) => Err(format!("Invalid mode '{}': expected form or component", other)),
+    }
+}
+
+/// Everything but the RFC 3986 unreserved characters
+const URL_COMPONENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// Bytes escaped by form encoding as in the WHATWG URL standard: everything
+/// but alphanumerics and `*-._`. Spaces are left for [`form_url_encode`] to
+/// turn into `+`.
+const URL_FORM: &AsciiSet = &NON_ALPHANUMERIC.remove(b'*').remove(b'-').remove(b'.').remove(b'_').remove(b' ');
+
+fn form_url_encode(text: &str) -> String {
+    utf8_percent_encode(text, URL_FORM).to_string().replace(' ', "+")
+}
+
+/// `percent_decode_str` passes malformed escapes through unchanged; unlike
+/// browsers, a `%` not followed by two hex digits is rejected instead
+fn check_percent_sequences(text: &str) -> Result<(), String> {
+    let bytes = text.as_bytes();
+    match bytes.iter().enumerate().find(|&(i, &byte)| {
+        byte == b'%' && !bytes.get(i + 1..i + 3).is_some_and(|digits| digits.iter().all(u8::is_ascii_hexdigit))
+    }) {
+        Some((position, _)) => Err(format!(
+            "Invalid percent-encoding: '%' at position {} is not followed by two hex digits",
+            position
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Decode base64 in the standard or the URL-safe alphabet, or a mix of the
+/// two, with or without padding
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    use base64::Engine as _;
+    use base64::engine::general_purpose::{GeneralPurpose, GeneralPurposeConfig};
+    use base64::engine::DecodePaddingMode;
+
+    const LENIENT: GeneralPurpose = GeneralPurpose::new(
+        &base64::alphabet::STANDARD,
+        GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+    );
+
+    // One character for another, so error offsets still point into `text`
+    let standard: String = text
+        .chars()
+        .map(|c| match c {
+            '-' => '+',
+            '_' => '/',
+            c => c,
+        })
+        .collect();
+    LENIENT.decode(standard).map_err(|e| format!("Invalid base64: {}", e))
+}
+
+/// Select the base64 engine from `alphabet` and `padding`, or from the older
+/// `variant`
+fn base64_engine(arguments: &Option<String>) -> Result<base64::engine::GeneralPurpose, String> {
+    use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+
+    let variant = parse_optional_arg::<String>(arguments, "variant")?;
+    let alphabet = parse_optional_arg::<String>(arguments, "alphabet")?;
+    let padding = parse_optional_arg::<bool>(arguments, "padding")?;
+
+    let (url, padded) = match variant.as_deref() {
+        Some(_) if alphabet.is_some() || padding.is_some() => {
+            return Err("Parameter 'variant' cannot be combined with 'alphabet' or 'padding'".to_string())
+        }
+        None | Some("standard") => (false, true),
+        Some("url_safe") => (true, true),
+        Some("url_safe_no_pad") => (true, false),
+        Some(other) => {
+            return Err(format!(
+                "Invalid variant '{}': expected one of standard, url_safe, url_safe_no_pad",
+                other
+            ))
+        }
+    };
+    let url = match alphabet.as_deref() {
+        None => url,
+        Some("standard") => false,
+        Some("url") => true,
+        Some(other) => return Err(format!("Invalid alphabet '{}': expected standard or url", other)),
+    };
+
+    Ok(match (url, padding.unwrap_or(padded)) {
+        (false, true) => STANDARD,
+        (false, false) => STANDARD_NO_PAD,
+        (true, true) => URL_SAFE,
+        (true, false) => URL_SAFE_NO_PAD,
+    })
+}
+
+/// Largest input `base_encode` and `base_decode` accept; conversion time grows
+/// with the square of the length
+const MAX_RADIX_INPUT_BYTES: usize = 4096;
+
+fn execute_base_encode(arguments: &Option<String>) -> CallToolResult {
+    use base64::Engine as _;
+
+    let parsed = parse_alphabet_arg(arguments).and_then(|alphabet| {
+        let data = parse_optional_arg::<String>(arguments, "data")?
+            .ok_or_else(|| "Missing or invalid parameter 'data'".to_string())?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&data)
+            .map_err(|e| format!("Parameter 'data' is not valid base64: {}", e))?;
+        Ok((alphabet, bytes))
+    });
+
+    match parsed {
+        Ok((_, bytes)) if bytes.len() > MAX_RADIX_INPUT_BYTES => error_result(format!(
+            "Parameter 'data' is {} bytes, which exceeds the limit of {}",
+            bytes.len(),
+            MAX_RADIX_INPUT_BYTES
+        )),
+        Ok((alphabet, bytes)) => success_result(radix::encode(&bytes, &alphabet)),
+        Err(msg) => error_result(msg),
+    }
+}
+
+fn execute_base_decode(arguments: &Option<String>) -> CallToolResult {
+    use base64::Engine as _;
+
+    let parsed = parse_alphabet_arg(arguments).and_then(|alphabet| Ok((alphabet, parse_text_arg(arguments)?)));
+
+    match parsed {
+        Ok((_, text)) if text.len() > MAX_RADIX_INPUT_BYTES => error_result(format!(
+            "Parameter 'text' is {} bytes, which exceeds the limit of {}",
+            text.len(),
+            MAX_RADIX_INPUT_BYTES
+        )),
+        Ok((alphabet, text)) => match radix::decode(&text, &alphabet) {
+            Ok(bytes) => success_result(base64::engine::general_purpose::STANDARD.encode(bytes)),
+            Err(msg) => error_result(msg),
+        },
+        Err(msg) => error_result(msg),
     }
 }
 
-bindings::export!(SystemInfo with_types_in bindings);
\ No newline at end of file
+/// Environment variables `env_var` may read. Anything else could expose
+/// secrets handed to the component, so names must be added here explicitly.
+const ENV_VAR_ALLOWLIST: [&str; 5] = ["LANG", "TZ", "HOSTNAME", "APP_ENV", "APP_VERSION"];
+
+fn execute_env_var(arguments: &Option<String>) -> CallToolResult {
+    let name = match parse_optional_arg::<String>(arguments, "name") {
+        Ok(Some(name)) => name,
+        Ok(None) => return error_result("Missing or invalid parameter 'name'".to_string()),
+        Err(msg) => return error_result(msg),
+    };
+
+    if !ENV_VAR_ALLOWLIST.contains(&name.as_str()) {
+        return error_result(format!(
+            "Environment variable '{}' is not on the allowlist ({})",
+            name,
+            ENV_VAR_ALLOWLIST.join(", ")
+        ));
+    }
+
+    match std::env::var(&name) {
+        Ok(value) => success_result(value),
+        Err(std::env::VarError::NotPresent) => {
+            error_result(format!("Environment variable '{}' is not set", name))
+        }
+        Err(std::env::VarError::NotUnicode(_)) => {
+            error_result(format!("Environment variable '{}' is not valid UTF-8", name))
+        }
+    }
+}
+
+fn parse_alphabet_arg(arguments: &Option<String>) -> Result<radix::Alphabet, String> {
+    let alphabet = parse_optional_arg::<String>(arguments, "alphabet")?
+        .ok_or_else(|| "Missing or invalid parameter 'alphabet'".to_string())?;
+    radix::Alphabet::new(&alphabet)
+}
+
+bindings::export!(SystemInfo with_types_in bindings);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(result: &CallToolResult) -> &str {
+        match &result.content[0] {
+            ContentBlock::Text(TextContent { text: TextData::Text(text), .. }) => text,
+            _ => panic!("expected a text block"),
+        }
+    }
+
+    fn sign(arguments: serde_json::Value) -> String {
+        let result = execute_hmac_sign(&Some(arguments.to_string()));
+        assert_ne!(result.is_error, Some(true), "{}", text(&result));
+        text(&result).to_string()
+    }
+
+    fn verify(arguments: serde_json::Value) -> Option<String> {
+        execute_hmac_verify(&Some(arguments.to_string())).structured_content
+    }
+
+    /// RFC 4231 test cases whose data is valid UTF-8; cases 3 and 4 sign
+    /// raw bytes a string `message` cannot carry, and case 5 truncates
+    const RFC_4231: [(&str, &str, &str); 4] = [
+        (
+            "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b",
+            "Hi There",
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+        ),
+        (
+            "4a656665",
+            "what do ya want for nothing?",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        ),
+        (
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "Test Using Larger Than Block-Size Key - Hash Key First",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        ),
+        (
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+            "This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm.",
+            "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+        ),
+    ];
+
+    #[test]
+    fn sign_matches_rfc_4231() {
+        for (key, message, expected) in RFC_4231 {
+            let signature = sign(serde_json::json!({ "message": message, "key": key, "key_encoding": "hex" }));
+            assert_eq!(signature, expected, "key {}", key);
+        }
+    }
+
+    #[test]
+    fn keys_can_be_written_in_any_encoding() {
+        use base64::Engine as _;
+        let (key, message, expected) = RFC_4231[2];
+        let key = base64::engine::general_purpose::STANDARD.encode(from_hex(key).expect("hex key"));
+        let signature = sign(serde_json::json!({ "message": message, "key": key, "key_encoding": "base64" }));
+        assert_eq!(signature, expected);
+
+        let (_, message, expected) = RFC_4231[1];
+        assert_eq!(sign(serde_json::json!({ "message": message, "key": "Jefe" })), expected);
+    }
+
+    #[test]
+    fn verify_accepts_rfc_4231_signatures_in_hex_and_base64() {
+        use base64::Engine as _;
+        for (key, message, expected) in RFC_4231 {
+            let hex = serde_json::json!({
+                "message": message, "key": key, "key_encoding": "hex", "signature": expected, "encoding": "hex",
+            });
+            assert_eq!(verify(hex).as_deref(), Some(r#"{"valid":true}"#));
+
+            let signature = base64::engine::general_purpose::STANDARD.encode(from_hex(expected).expect("hex"));
+            let base64 = serde_json::json!({
+                "message": message, "key": key, "key_encoding": "hex", "signature": signature, "encoding": "base64",
+            });
+            assert_eq!(verify(base64).as_deref(), Some(r#"{"valid":true}"#));
+        }
+    }
+
+    #[test]
+    fn verify_reports_a_mismatch_as_a_result() {
+        let (key, message, expected) = RFC_4231[0];
+        let mut tampered = expected.to_string();
+        tampered.replace_range(..2, "00");
+
+        for signature in [tampered.as_str(), &expected[..32]] {
+            let arguments = serde_json::json!({
+                "message": message, "key": key, "key_encoding": "hex", "signature": signature, "encoding": "hex",
+            });
+            let result = execute_hmac_verify(&Some(arguments.to_string()));
+            assert_ne!(result.is_error, Some(true));
+            assert_eq!(result.structured_content.as_deref(), Some(r#"{"valid":false}"#));
+        }
+
+        let malformed = serde_json::json!({
+            "message": message, "key": key, "signature": "not hex", "encoding": "hex",
+        });
+        assert_eq!(execute_hmac_verify(&Some(malformed.to_string())).is_error, Some(true));
+    }
+
+    fn assert_distinct_v4(uuids: &[String]) {
+        let distinct: std::collections::HashSet<&String> = uuids.iter().collect();
+        assert_eq!(distinct.len(), uuids.len());
+        for uuid in uuids {
+            assert_eq!(&uuid[14..15], "4", "{}", uuid);
+            assert!(matches!(&uuid[19..20], "8" | "9" | "a" | "b"), "{}", uuid);
+        }
+    }
+
+    #[test]
+    fn uuids_in_a_tight_loop_are_distinct_version_4() {
+        let mut source = RandomSource::Seeded(Box::new(ChaCha20Rng::seed_from_u64(7)));
+        let uuids: Vec<String> = (0..10_000)
+            .map(|_| match random_uuid(&mut source) {
+                (uuid, Entropy::Source) => uuid,
+                (_, Entropy::TimeSeeded) => panic!("seeded source returned the wrong length"),
+            })
+            .collect();
+        assert_distinct_v4(&uuids);
+    }
+
+    #[test]
+    fn fallback_uuids_in_a_tight_loop_are_distinct_version_4() {
+        let uuids: Vec<String> = (0..10_000).map(|_| uuid_v4(fallback_bytes())).collect();
+        assert_distinct_v4(&uuids);
+    }
+
+    /// `base64_decode` called with `arguments`, returning its structured content
+    fn base64_decode(arguments: serde_json::Value) -> serde_json::Value {
+        let result = execute_base64_decode(&Some(arguments.to_string()));
+        assert_ne!(result.is_error, Some(true), "{}", text(&result));
+        serde_json::from_str(result.structured_content.as_deref().expect("structured content")).expect("JSON")
+    }
+
+    #[test]
+    fn decode_accepts_padded_and_unpadded_input() {
+        for (encoded, expected) in [
+            ("aGk=", "hi"),
+            ("aGk", "hi"),
+            ("aGVsbG8gd29ybGQ=", "hello world"),
+            ("aGVsbG8gd29ybGQ", "hello world"),
+        ] {
+            let decoded = base64_decode(serde_json::json!({ "text": encoded }));
+            assert_eq!(decoded, serde_json::json!({ "output": "text", "data": expected, "length": expected.len() }));
+        }
+    }
+
+    #[test]
+    fn decode_accepts_the_url_safe_alphabet() {
+        // 0xfb 0xff 0xbf is "+/+/" in the standard alphabet
+        for encoded in ["-_-_", "+/+/", "-/+_"] {
+            let decoded = base64_decode(serde_json::json!({ "text": encoded, "output": "hex" }));
+            assert_eq!(decoded["data"], "fbffbf");
+            assert_eq!(decoded["length"], 3);
+        }
+        let decoded = base64_decode(serde_json::json!({ "text": "Pz8_", "output": "text" }));
+        assert_eq!(decoded["data"], "???");
+    }
+
+    #[test]
+    fn decode_returns_binary_data_as_hex() {
+        // The eight-byte PNG signature
+        let result = execute_base64_decode(&Some(r#"{"text": "iVBORw0KGgo="}"#.to_string()));
+        assert_ne!(result.is_error, Some(true));
+        assert!(text(&result).starts_with("Decoded 8 bytes of binary data"), "{}", text(&result));
+        let decoded: serde_json::Value =
+            serde_json::from_str(result.structured_content.as_deref().expect("structured content")).expect("JSON");
+        assert_eq!(decoded, serde_json::json!({ "output": "hex", "data": "89504e470d0a1a0a", "length": 8 }));
+
+        let decoded = base64_decode(serde_json::json!({ "text": "iVBORw0KGgo", "output": "base64url" }));
+        assert_eq!(decoded["data"], "iVBORw0KGgo");
+
+        let result = execute_base64_decode(&Some(r#"{"text": "iVBORw0KGgo=", "output": "text"}"#.to_string()));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn decode_rejects_invalid_input() {
+        for encoded in ["a", "aGk=!", "a=Gk"] {
+            let result = execute_base64_decode(&Some(serde_json::json!({ "text": encoded }).to_string()));
+            assert_eq!(result.is_error, Some(true), "{}", encoded);
+            assert!(text(&result).starts_with("Invalid base64: "), "{}", text(&result));
+        }
+    }
+
+    #[test]
+    fn every_encode_variant_round_trips_through_decode() {
+        // Two trailing bytes of padding, and characters from both alphabet tails
+        let input = "subjects?_d>~";
+        let variants = [
+            (serde_json::json!({}), "c3ViamVjdHM/X2Q+fg=="),
+            (serde_json::json!({ "alphabet": "standard", "padding": false }), "c3ViamVjdHM/X2Q+fg"),
+            (serde_json::json!({ "alphabet": "url" }), "c3ViamVjdHM_X2Q-fg=="),
+            (serde_json::json!({ "alphabet": "url", "padding": false }), "c3ViamVjdHM_X2Q-fg"),
+            (serde_json::json!({ "variant": "url_safe_no_pad" }), "c3ViamVjdHM_X2Q-fg"),
+        ];
+
+        for (mut options, expected) in variants {
+            options["text"] = input.into();
+            let result = execute_base64_encode(&Some(options.to_string()));
+            assert_eq!(text(&result), expected, "{}", options);
+
+            let decoded = base64_decode(serde_json::json!({ "text": expected }));
+            assert_eq!(decoded["data"], input, "{}", expected);
+        }
+    }
+
+    #[test]
+    fn encode_rejects_conflicting_options() {
+        let result = execute_base64_encode(&Some(r#"{"text": "a", "variant": "url_safe", "padding": false}"#.to_string()));
+        assert_eq!(result.is_error, Some(true));
+        let result = execute_base64_encode(&Some(r#"{"text": "a", "alphabet": "web"}"#.to_string()));
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    /// Text of a successful call to `execute` with `arguments`
+    fn seeded(execute: fn(&Option<String>) -> CallToolResult, arguments: serde_json::Value) -> String {
+        let result = execute(&Some(arguments.to_string()));
+        assert_ne!(result.is_error, Some(true), "{}", text(&result));
+        text(&result).to_string()
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_output() {
+        let uuid = |seed: u64| seeded(execute_random_uuid, serde_json::json!({"seed": seed}));
+        assert_eq!(uuid(42), uuid(42));
+        assert_ne!(uuid(42), uuid(43));
+        assert_ne!(uuid(0), uuid(u64::MAX));
+
+        let bytes = |seed: u64| seeded(execute_random_bytes, serde_json::json!({"length": 32, "seed": seed}));
+        assert_eq!(bytes(42), bytes(42));
+        assert_ne!(bytes(42), bytes(43));
+        // A longer request starts with the same bytes
+        assert!(seeded(execute_random_bytes, serde_json::json!({"length": 64, "seed": 42})).starts_with(&bytes(42)));
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_random_bits_of_time_ordered_ids() {
+        // Only the bits after the millisecond timestamp come from the seed
+        let uuid_v7 = |seed: u64| seeded(execute_uuid_v7, serde_json::json!({"seed": seed}))[14..].to_string();
+        assert_eq!(uuid_v7(42), uuid_v7(42));
+        assert_ne!(uuid_v7(42), uuid_v7(43));
+
+        let ulid = |seed: u64| seeded(execute_ulid, serde_json::json!({"seed": seed}))[10..].to_string();
+        assert_eq!(ulid(42), ulid(42));
+        assert_ne!(ulid(42), ulid(43));
+    }
+
+    #[test]
+    fn seed_must_be_an_unsigned_64_bit_integer() {
+        for seed in [serde_json::json!(-1), serde_json::json!(1.5), serde_json::json!("42")] {
+            let result = execute_random_uuid(&Some(serde_json::json!({"seed": seed}).to_string()));
+            assert_eq!(result.is_error, Some(true), "{}", seed);
+            assert!(text(&result).starts_with("Invalid parameter 'seed'"), "{}", text(&result));
+        }
+        assert!(matches!(parse_random_source(&None), Ok(RandomSource::Secure)));
+        assert!(matches!(parse_random_source(&Some(r#"{"seed": null}"#.to_string())), Ok(RandomSource::Secure)));
+    }
+
+    #[test]
+    fn rfc3339_of_known_epochs() {
+        assert_eq!(format_rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_rfc3339(1_000_000_000), "2001-09-09T01:46:40Z");
+        // One second past the signed 32-bit limit, and a century that is not a leap year
+        assert_eq!(format_rfc3339(2_147_483_648), "2038-01-19T03:14:08Z");
+        assert_eq!(format_rfc3339(4_107_542_399), "2100-02-28T23:59:59Z");
+        assert_eq!(format_rfc3339(4_107_542_400), "2100-03-01T00:00:00Z");
+        assert_eq!(format_rfc3339_millis(1_700_000_000_123), "2023-11-14T22:13:20.123Z");
+    }
+
+    #[test]
+    fn civil_dates_around_the_epoch() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(11_017), (2000, 3, 1));
+    }
+}
@@ -0,0 +1,166 @@
+//! Arbitrary-radix encoding of byte strings
+//!
+//! Bytes are read as one big-endian integer and written in the radix of the
+//! alphabet, most significant digit first. As in base58, each leading zero
+//! byte becomes one leading zero symbol (the alphabet's first symbol), so
+//! encoding round-trips exactly.
+
+/// Smallest and largest number of symbols an alphabet may have
+pub const MIN_SYMBOLS: usize = 2;
+pub const MAX_SYMBOLS: usize = 255;
+
+/// An ordered set of distinct symbols, the first of which stands for zero
+pub struct Alphabet {
+    symbols: Vec<char>,
+}
+
+impl Alphabet {
+    pub fn new(symbols: &str) -> Result<Self, String> {
+        let symbols: Vec<char> = symbols.chars().collect();
+        if !(MIN_SYMBOLS..=MAX_SYMBOLS).contains(&symbols.len()) {
+            return Err(format!(
+                "Alphabet must have between {} and {} symbols, got {}",
+                MIN_SYMBOLS,
+                MAX_SYMBOLS,
+                symbols.len()
+            ));
+        }
+        for (i, symbol) in symbols.iter().enumerate() {
+            if symbols[..i].contains(symbol) {
+                return Err(format!("Alphabet contains '{}' more than once", symbol));
+            }
+        }
+        Ok(Alphabet { symbols })
+    }
+
+    fn radix(&self) -> u32 {
+        self.symbols.len() as u32
+    }
+
+    fn digit(&self, symbol: char) -> Option<u32> {
+        self.symbols.iter().position(|&s| s == symbol).map(|i| i as u32)
+    }
+}
+
+pub fn encode(bytes: &[u8], alphabet: &Alphabet) -> String {
+    let radix = alphabet.radix();
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+
+    // Little-endian digits in the target radix
+    let mut digits: Vec<u32> = Vec::new();
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += *digit << 8;
+            *digit = carry % radix;
+            carry /= radix;
+        }
+        while carry > 0 {
+            digits.push(carry % radix);
+            carry /= radix;
+        }
+    }
+
+    let zero = alphabet.symbols[0];
+    std::iter::repeat_n(zero, zeros)
+        .chain(digits.iter().rev().map(|&d| alphabet.symbols[d as usize]))
+        .collect()
+}
+
+pub fn decode(text: &str, alphabet: &Alphabet) -> Result<Vec<u8>, String> {
+    let radix = alphabet.radix();
+    let zero = alphabet.symbols[0];
+    let zeros = text.chars().take_while(|&c| c == zero).count();
+
+    // Little-endian bytes
+    let mut bytes: Vec<u8> = Vec::new();
+    for (position, symbol) in text.chars().enumerate().skip(zeros) {
+        let mut carry = alphabet
+            .digit(symbol)
+            .ok_or_else(|| format!("Symbol '{}' at position {} is not in the alphabet", symbol, position))?;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * radix;
+            *byte = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push((carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    Ok(std::iter::repeat_n(0, zeros).chain(bytes.into_iter().rev()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE58: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+    const BASE62: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+    fn round_trip(bytes: &[u8], alphabet: &Alphabet) -> String {
+        let text = encode(bytes, alphabet);
+        assert_eq!(decode(&text, alphabet).as_deref(), Ok(bytes), "{:?} via {}", bytes, text);
+        text
+    }
+
+    #[test]
+    fn custom_alphabets_round_trip() {
+        let base58 = Alphabet::new(BASE58).unwrap();
+        assert_eq!(round_trip(b"Hello World!", &base58), "2NEpo7TZRRrLZSi2U");
+
+        let base62 = Alphabet::new(BASE62).unwrap();
+        assert_eq!(base62.radix(), 62);
+        assert_eq!(round_trip(&[0xff], &base62), "47");
+        assert_eq!(round_trip(&[0x01, 0x00], &base62), "48");
+
+        let binary = Alphabet::new("01").unwrap();
+        assert_eq!(round_trip(&[5], &binary), "101");
+
+        let emoji = Alphabet::new("🌑🌒🌓🌔🌕").unwrap();
+        round_trip(b"moon", &emoji);
+
+        let widest: String = (0..MAX_SYMBOLS as u32).filter_map(|i| char::from_u32(0x100 + i)).collect();
+        let widest = Alphabet::new(&widest).unwrap();
+        let bytes: Vec<u8> = (0..=255).rev().collect();
+        for alphabet in [&base58, &base62, &binary, &emoji, &widest] {
+            round_trip(&bytes, alphabet);
+        }
+    }
+
+    #[test]
+    fn leading_zero_bytes_become_leading_zero_symbols() {
+        let base62 = Alphabet::new(BASE62).unwrap();
+        assert_eq!(round_trip(&[0, 0, 1], &base62), "001");
+        assert_eq!(round_trip(&[0, 0, 0], &base62), "000");
+        assert_eq!(round_trip(&[], &base62), "");
+
+        let base58 = Alphabet::new(BASE58).unwrap();
+        assert_eq!(round_trip(&[0, 0x3a], &base58), "121");
+    }
+
+    #[test]
+    fn alphabets_need_two_distinct_symbols() {
+        assert_eq!(
+            Alphabet::new("0120").err().as_deref(),
+            Some("Alphabet contains '0' more than once")
+        );
+        assert_eq!(
+            Alphabet::new("x").err().as_deref(),
+            Some("Alphabet must have between 2 and 255 symbols, got 1")
+        );
+        assert!(Alphabet::new("").is_err());
+        let too_many: String = (0..=MAX_SYMBOLS as u32).filter_map(|i| char::from_u32(0x100 + i)).collect();
+        assert!(Alphabet::new(&too_many).is_err());
+    }
+
+    #[test]
+    fn decode_rejects_symbols_outside_the_alphabet() {
+        let base58 = Alphabet::new(BASE58).unwrap();
+        assert_eq!(
+            decode("12O", &base58),
+            Err("Symbol 'O' at position 2 is not in the alphabet".to_string())
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! Per-tool token-bucket rate limiting
+//!
+//! Limits are read from the `TOOL_RATE_LIMITS` environment variable as a
+//! comma-separated list of `tool=calls/seconds` entries, for example
+//! `TOOL_RATE_LIMITS="random_uuid=10/60,base64_decode=100/1"`. Tools without
+//! an entry are not limited. A malformed entry fails only the calls to the tool
+//! it names; an entry that names no tool is reported on stderr and ignored.
+//!
+//! Buckets live in component instance memory, so limits are scoped to a single
+//! instance. Hosts that instantiate the component per request (such as
+//! `wasmtime serve`) start every request with full buckets; hosts that reuse
+//! an instance enforce the limit across the calls it serves.
+
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const RATE_LIMITS_ENV: &str = "TOOL_RATE_LIMITS";
+
+static BUCKETS: Mutex<BTreeMap<String, Bucket>> = Mutex::new(BTreeMap::new());
+
+/// Allowed number of calls per period
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Limit {
+    calls: u32,
+    period: Duration,
+}
+
+struct Bucket {
+    limit: Limit,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn new(limit: Limit, now: Instant) -> Self {
+        Bucket {
+            limit,
+            tokens: limit.calls as f64,
+            updated: now,
+        }
+    }
+
+    /// Take a token, or return how long until the next one is available
+    fn try_acquire(&mut self, now: Instant) -> Result<(), Duration> {
+        let rate = self.limit.calls as f64 / self.limit.period.as_secs_f64();
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(self.limit.calls as f64);
+        self.updated = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        }
+    }
+}
+
+/// Consume one call for `tool`, returning an error message when it is rate limited
+pub fn check(tool: &str) -> Result<(), String> {
+    let spec = match std::env::var(RATE_LIMITS_ENV) {
+        Ok(spec) => spec,
+        Err(_) => return Ok(()),
+    };
+    let limit = match limit_for(&spec, tool)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+
+    let now = Instant::now();
+    let mut buckets = BUCKETS.lock().unwrap_or_else(|e| e.into_inner());
+    let bucket = buckets
+        .entry(tool.to_string())
+        .or_insert_with(|| Bucket::new(limit, now));
+
+    // Start over if the configured limit changed since the bucket was created
+    if bucket.limit != limit {
+        *bucket = Bucket::new(limit, now);
+    }
+
+    bucket.try_acquire(now).map_err(|wait| {
+        format!(
+            "Rate limit exceeded for tool '{}': {} calls per {}s allowed, retry in {:.1}s",
+            tool,
+            limit.calls,
+            limit.period.as_secs_f64(),
+            wait.as_secs_f64()
+        )
+    })
+}
+
+/// The limit configured for `tool` in `spec`, or an error if its entry is
+/// malformed. The last entry for a tool wins.
+fn limit_for(spec: &str, tool: &str) -> Result<Option<Limit>, String> {
+    let mut limit = None;
+
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let Some((name, rate)) = entry.split_once('=').filter(|(name, _)| !name.trim().is_empty()) else {
+            eprintln!(
+                "Ignoring {} entry '{}': expected tool=calls/seconds",
+                RATE_LIMITS_ENV, entry
+            );
+            continue;
+        };
+        if name.trim() == tool {
+            limit = Some(parse_rate(rate).ok_or_else(|| {
+                format!(
+                    "Invalid {} entry '{}': expected tool=calls/seconds",
+                    RATE_LIMITS_ENV, entry
+                )
+            })?);
+        }
+    }
+
+    Ok(limit)
+}
+
+/// `calls/seconds` with at least one call and a positive period
+fn parse_rate(rate: &str) -> Option<Limit> {
+    let (calls, seconds) = rate.split_once('/')?;
+    let calls: u32 = calls.trim().parse().ok()?;
+    let seconds: f64 = seconds.trim().parse().ok()?;
+
+    if calls == 0 || !seconds.is_finite() || seconds <= 0.0 {
+        return None;
+    }
+    Some(Limit {
+        calls,
+        period: Duration::from_secs_f64(seconds),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(calls: u32, seconds: u64) -> Option<Limit> {
+        Some(Limit {
+            calls,
+            period: Duration::from_secs(seconds),
+        })
+    }
+
+    #[test]
+    fn entries_are_looked_up_by_tool() {
+        let spec = " random_uuid = 10/60 , base64_decode=100/1,";
+        assert_eq!(limit_for(spec, "random_uuid"), Ok(limit(10, 60)));
+        assert_eq!(limit_for(spec, "base64_decode"), Ok(limit(100, 1)));
+        assert_eq!(limit_for(spec, "hash_md5"), Ok(None));
+        assert_eq!(limit_for("", "random_uuid"), Ok(None));
+    }
+
+    #[test]
+    fn a_malformed_entry_fails_only_its_own_tool() {
+        let spec = "random_uuid=10/60,random_bytes=0/1,ulid=fast,uuid_v7=5/-1";
+        assert_eq!(limit_for(spec, "random_uuid"), Ok(limit(10, 60)));
+        for tool in ["random_bytes", "ulid", "uuid_v7"] {
+            let error = limit_for(spec, tool).expect_err("malformed entry");
+            assert!(error.starts_with(&format!("Invalid TOOL_RATE_LIMITS entry '{}=", tool)), "{}", error);
+        }
+    }
+
+    #[test]
+    fn entries_without_a_tool_are_ignored() {
+        let spec = "garbage,random_uuid=10/60,=";
+        assert_eq!(limit_for(spec, "random_uuid"), Ok(limit(10, 60)));
+        assert_eq!(limit_for(spec, "hash_md5"), Ok(None));
+    }
+
+    #[test]
+    fn bucket_rejects_calls_past_the_limit_and_recovers() {
+        let start = Instant::now();
+        let mut bucket = Bucket::new(limit(2, 10).expect("limit"), start);
+
+        assert_eq!(bucket.try_acquire(start), Ok(()));
+        assert_eq!(bucket.try_acquire(start), Ok(()));
+        let wait = bucket.try_acquire(start).expect_err("bucket is empty");
+        assert_eq!(wait, Duration::from_secs(5));
+
+        // One token refills every 5s
+        assert!(bucket.try_acquire(start + Duration::from_secs(4)).is_err());
+        assert_eq!(bucket.try_acquire(start + Duration::from_secs(6)), Ok(()));
+        assert_eq!(bucket.try_acquire(start + Duration::from_secs(60)), Ok(()));
+        assert_eq!(bucket.try_acquire(start + Duration::from_secs(60)), Ok(()));
+        assert!(bucket.try_acquire(start + Duration::from_secs(60)).is_err());
+    }
+}
@@ -0,0 +1,95 @@
+//! Time-ordered identifiers: UUID v7 (RFC 9562) and ULID
+//!
+//! Both are 128 bits with a 48-bit big-endian Unix millisecond timestamp in
+//! front of random bits, so they sort by creation time. UUID v7 spends 6 of
+//! the remaining 80 bits on its version and variant, leaving 74 random bits;
+//! a ULID keeps all 80.
+//!
+//! Within one component instance, an id generated in the same millisecond as
+//! the previous one, or after the clock stepped back, keeps the previous
+//! timestamp and increments the previous random bits by one instead of drawing
+//! new ones, as both specs describe. Ids from one instance therefore strictly
+//! increase. If the increment would overflow, the timestamp is advanced by one
+//! millisecond and fresh random bits are drawn. Separate instances are not
+//! coordinated.
+
+use std::sync::Mutex;
+
+/// Monotonic state for one kind of identifier
+pub struct Generator {
+    random_bits: u32,
+    /// Timestamp and random bits of the last id handed out
+    last: Mutex<Option<(u64, u128)>>,
+}
+
+pub static UUID_V7: Generator = Generator::new(74);
+pub static ULID: Generator = Generator::new(80);
+
+/// Largest timestamp that fits in 48 bits, in the year 10889
+const MAX_MILLIS: u64 = (1 << 48) - 1;
+
+impl Generator {
+    const fn new(random_bits: u32) -> Self {
+        Generator { random_bits, last: Mutex::new(None) }
+    }
+
+    fn mask(&self) -> u128 {
+        (1 << self.random_bits) - 1
+    }
+
+    /// Timestamp and random bits for the next id; `fresh` random bits are
+    /// used only when a new millisecond starts
+    pub fn next(&self, now_millis: u64, fresh: u128) -> Result<(u64, u128), String> {
+        let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+
+        let next = match *last {
+            Some((millis, random)) if now_millis <= millis && random < self.mask() => (millis, random + 1),
+            Some((millis, _)) if now_millis <= millis => (millis + 1, fresh & self.mask()),
+            _ => (now_millis, fresh & self.mask()),
+        };
+        if next.0 > MAX_MILLIS {
+            return Err(format!("Timestamp {} ms does not fit in 48 bits", next.0));
+        }
+
+        *last = Some(next);
+        Ok(next)
+    }
+
+    /// Timestamp and random bits without monotonic handling, for seeded calls
+    /// whose random bits must depend only on the seed
+    pub fn unordered(&self, now_millis: u64, random: u128) -> Result<(u64, u128), String> {
+        if now_millis > MAX_MILLIS {
+            return Err(format!("Timestamp {} ms does not fit in 48 bits", now_millis));
+        }
+        Ok((now_millis, random & self.mask()))
+    }
+}
+
+/// UUID v7 layout: timestamp, version 7, 12 random bits, variant 10, 62 random bits
+pub fn uuid_v7(millis: u64, random: u128) -> [u8; 16] {
+    let rand_a = random >> 62;
+    let rand_b = random & ((1 << 62) - 1);
+    let value = (millis as u128) << 80 | 0x7 << 76 | rand_a << 64 | 0b10 << 62 | rand_b;
+    value.to_be_bytes()
+}
+
+/// ULID layout: timestamp then 80 random bits
+pub fn ulid(millis: u64, random: u128) -> [u8; 16] {
+    ((millis as u128) << 80 | random).to_be_bytes()
+}
+
+/// The 26 Crockford base32 digits a ULID is written as
+pub fn ulid_text(bytes: &[u8; 16]) -> String {
+    const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+    let value = u128::from_be_bytes(*bytes);
+    // 26 digits hold 130 bits, so the first digit only carries the top 3
+    (0..26)
+        .map(|i| CROCKFORD[((value >> (125 - 5 * i)) & 0x1f) as usize] as char)
+        .collect()
+}
+
+/// The millisecond timestamp in the first 48 bits of an id
+pub fn timestamp_millis(bytes: &[u8; 16]) -> u64 {
+    (u128::from_be_bytes(*bytes) >> 80) as u64
+}
@@ -22,6 +22,13 @@ use bindings::wasmcp::protocol::mcp::*;
 use bindings::wasmcp::protocol::server_messages::Context;
 use bindings::wasmcp::server::handler as downstream;
 use bindings::wasi::io::streams::OutputStream;
+use mcp_utils::annotations::ToolHints;
+
+mcp_utils::result_builders!();
+use result_builder::{error_result, number_result};
+
+mcp_utils::annotation_builder!();
+use annotation_builder::build_annotations;
 
 struct VarianceMiddleware;
 
@@ -96,11 +103,11 @@ fn handle_tools_list(
         .to_string(),
         options: Some(ToolOptions {
             meta: None,
-            annotations: None,
+            annotations: Some(build_annotations(ToolHints::SAFE)),
             description: Some(
                 "Calculate the variance of an array of numbers: Σ(x - μ)² / n".to_string(),
             ),
-            output_schema: None,
+            output_schema: Some(NUMBER_OUTPUT_SCHEMA.to_string()),
             title: Some("Variance".to_string()),
         }),
     });
@@ -148,9 +155,7 @@ fn handle_variance_call(
     // Step 3: Calculate mean of squared differences
     let variance = squared_diffs.iter().sum::<f64>() / squared_diffs.len() as f64;
 
-    Ok(ServerResponse::ToolsCall(success_result(
-        variance.to_string(),
-    )))
+    Ok(ServerResponse::ToolsCall(number_result(variance)))
 }
 
 fn call_mean_tool(
@@ -222,28 +227,13 @@ fn extract_number_from_result(result: &CallToolResult) -> Result<f64, String> {
     Err("No text content found in result".to_string())
 }
 
-fn success_result(result: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(result),
-            options: None,
-        })],
-        is_error: None,
-        meta: None,
-        structured_content: None,
-    }
-}
-
-fn error_result(message: String) -> CallToolResult {
-    CallToolResult {
-        content: vec![ContentBlock::Text(TextContent {
-            text: TextData::Text(message),
-            options: None,
-        })],
-        is_error: Some(true),
-        meta: None,
-        structured_content: None,
-    }
-}
+/// Shape of the `structured_content` returned alongside the text result
+const NUMBER_OUTPUT_SCHEMA: &str = r#"{
+    "type": "object",
+    "properties": {
+        "result": {"type": "number"}
+    },
+    "required": ["result"]
+}"#;
 
 bindings::export!(VarianceMiddleware with_types_in bindings);
@@ -0,0 +1,221 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::kubectl_cmd;
+
+/// Lines of each container's log kept in the artifacts
+const POD_LOG_TAIL_LINES: usize = 1000;
+
+const TOOLS_LIST_REQUEST: &str = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list","params":{}}"#;
+
+/// Outcome of sending tools/list to the MCP endpoint
+struct Probe {
+    http_status: Option<u16>,
+    body: String,
+    error: Option<String>,
+}
+
+impl Probe {
+    /// Tool names from a successful response, or None if the server is unhealthy
+    fn tools(&self) -> Option<Vec<String>> {
+        if self.error.is_some() || self.http_status != Some(200) {
+            return None;
+        }
+        response_json(&self.body)?
+            .pointer("/result/tools")?
+            .as_array()?
+            .iter()
+            .map(|tool| tool.get("name")?.as_str().map(str::to_string))
+            .collect()
+    }
+}
+
+/// Probe the MCP endpoint with tools/list, saving the application's pods,
+/// their logs and the namespace events on failure (or always, with `always`)
+pub fn run(url: &str, namespace: &str, app_name: &str, artifacts_dir: &str, always: bool) -> Result<()> {
+    println!("{}", format!("Checking MCP endpoint: {}", url).cyan());
+
+    let probe = probe_tools_list(url);
+    report(url, probe, always, |probe| {
+        capture_artifacts(Path::new(artifacts_dir), probe, namespace, app_name)
+    })
+}
+
+/// Print the outcome of `probe`, calling `capture` when it failed (or always,
+/// with `always`), and fail if it did
+fn report(url: &str, probe: Probe, always: bool, capture: impl FnOnce(&Probe) -> Result<PathBuf>) -> Result<()> {
+    let tools = probe.tools();
+
+    match &tools {
+        Some(tools) => println!("{} tools/list returned {} tools", "✓".green(), tools.len()),
+        None => println!("{} tools/list failed", "✗".red()),
+    }
+
+    if tools.is_none() || always {
+        let dir = capture(&probe)?;
+        println!("{} Artifacts saved to {}", "✓".green(), dir.display());
+    }
+
+    match tools {
+        Some(_) => Ok(()),
+        None => Err(anyhow::anyhow!(
+            "Healthcheck failed: {}",
+            probe.error.unwrap_or_else(|| match probe.http_status {
+                Some(status) => format!("HTTP {} from {}", status, url),
+                None => format!("no HTTP status from {}", url),
+            })
+        )),
+    }
+}
+
+fn probe_tools_list(url: &str) -> Probe {
+    // The status code is appended on its own line after the body
+    let output = Command::new("curl")
+        .args([
+            "-sS",
+            "--max-time",
+            "10",
+            "-X",
+            "POST",
+            "-H",
+            "Content-Type: application/json",
+            "-H",
+            "Accept: application/json, text/event-stream",
+            "-d",
+            TOOLS_LIST_REQUEST,
+            "-w",
+            "\n%{http_code}",
+            url,
+        ])
+        .output();
+
+    match output {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+            let http_status = status.trim().parse().ok().filter(|&status| status != 0);
+            let error = (!output.status.success())
+                .then(|| String::from_utf8_lossy(&output.stderr).trim().to_string());
+            Probe {
+                http_status,
+                body: body.to_string(),
+                error,
+            }
+        }
+        Err(e) => Probe {
+            http_status: None,
+            body: String::new(),
+            error: Some(format!("Failed to run curl: {}", e)),
+        },
+    }
+}
+
+/// Parse a JSON-RPC response sent either as plain JSON or as a server-sent event
+fn response_json(body: &str) -> Option<Value> {
+    serde_json::from_str(body).ok().or_else(|| {
+        body.lines()
+            .filter_map(|line| line.strip_prefix("data:"))
+            .find_map(|data| serde_json::from_str(data.trim()).ok())
+    })
+}
+
+/// Write the probe result, the application's pods and their log tails, and
+/// the namespace events into a timestamped directory under `artifacts_dir`
+fn capture_artifacts(artifacts_dir: &Path, probe: &Probe, namespace: &str, app_name: &str) -> Result<PathBuf> {
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let dir = artifacts_dir.join(format!("healthcheck-{}", timestamp));
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+
+    let mut tools_list = format!(
+        "HTTP status: {}\n",
+        probe.http_status.map_or("none".to_string(), |s| s.to_string())
+    );
+    if let Some(error) = &probe.error {
+        tools_list.push_str(&format!("Error: {}\n", error));
+    }
+    tools_list.push('\n');
+    tools_list.push_str(&probe.body);
+    write_artifact(&dir, "tools-list.txt", &tools_list)?;
+
+    let selector = format!("app={}", app_name);
+    let tail = format!("--tail={}", POD_LOG_TAIL_LINES);
+    for (name, args) in [
+        ("pods.txt", vec!["get", "pods", "-l", &selector, "-n", namespace, "-o", "wide"]),
+        ("pod-logs.txt", vec!["logs", "-l", &selector, "-n", namespace, "--all-containers", "--prefix", &tail]),
+        ("events.txt", vec!["get", "events", "-n", namespace, "--sort-by=.lastTimestamp"]),
+    ] {
+        write_artifact(&dir, name, &kubectl_text(&args))?;
+    }
+
+    Ok(dir)
+}
+
+/// Output of a kubectl query, or its error message so the artifact still
+/// explains what went wrong
+fn kubectl_text(args: &[&str]) -> String {
+    match kubectl_cmd().args(args).output() {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout).to_string(),
+        Ok(output) => format!("kubectl {} failed:\n{}", args.join(" "), String::from_utf8_lossy(&output.stderr)),
+        Err(e) => format!("Failed to run kubectl: {}", e),
+    }
+}
+
+fn write_artifact(dir: &Path, name: &str, contents: &str) -> Result<()> {
+    let path = dir.join(name);
+    fs::write(&path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    const URL: &str = "http://localhost:30950/mcp";
+
+    fn probe(http_status: Option<u16>, body: &str, error: Option<&str>) -> Probe {
+        Probe {
+            http_status,
+            body: body.to_string(),
+            error: error.map(str::to_string),
+        }
+    }
+
+    fn healthy() -> Probe {
+        probe(Some(200), r#"{"jsonrpc":"2.0","id":1,"result":{"tools":[{"name":"add"}]}}"#, None)
+    }
+
+    /// Run `report`, returning its result and how many times it captured artifacts
+    fn report_counting(probe: Probe, always: bool) -> (Result<()>, usize) {
+        let captures = Cell::new(0);
+        let result = report(URL, probe, always, |_| {
+            captures.set(captures.get() + 1);
+            Ok(PathBuf::from("build/healthcheck/healthcheck-0"))
+        });
+        (result, captures.get())
+    }
+
+    #[test]
+    fn success_captures_nothing_unless_always() {
+        assert!(matches!(report_counting(healthy(), false), (Ok(()), 0)));
+        assert!(matches!(report_counting(healthy(), true), (Ok(()), 1)));
+    }
+
+    #[test]
+    fn failure_captures_artifacts() {
+        for failed in [
+            probe(Some(502), "Bad Gateway", None),
+            probe(None, "", Some("curl: (7) Failed to connect")),
+        ] {
+            let (result, captures) = report_counting(failed, false);
+            assert!(result.is_err());
+            assert_eq!(captures, 1);
+        }
+    }
+}
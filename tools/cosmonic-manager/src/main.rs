@@ -1,10 +1,14 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use colored::Colorize;
+use serde::Deserialize;
 use std::fs;
 use std::process::Command;
+use std::time::{Duration, Instant};
 use tera::{Tera, Context as TeraContext};
 
+mod healthcheck;
+
 #[derive(Parser)]
 #[command(name = "cosmonic-manager")]
 #[command(about = "Manage Cosmonic Control deployments", long_about = None)]
@@ -23,13 +27,17 @@ enum Commands {
         /// Cosmonic license key (or set COSMONIC_LICENSE_KEY env var)
         #[arg(long)]
         license_key: String,
+        /// Extra arguments appended verbatim to `helm install cosmonic-control`, given after `--`.
+        /// They are not checked, so a wrong flag fails the install or changes what it does
+        #[arg(last = true)]
+        raw: Vec<String>,
     },
     /// Deploy application to cluster
     Deploy {
         /// Deployment type (httptrigger or deployment)
         #[arg(short, long, default_value = "httptrigger")]
         deploy_type: String,
-        /// Application version (can be overridden by --image-tag)
+        /// Application version, used as the image tag (overridden by --image and --image-tag-from-git)
         #[arg(short, long, default_value = "latest")]
         version: String,
         /// Namespace
@@ -38,12 +46,22 @@ enum Commands {
         /// Application name
         #[arg(long, default_value = "mcp-multi-tools")]
         app_name: String,
-        /// Full image reference (e.g., ghcr.io/user/image:tag) - overrides --image-base and --version
+        /// Full image reference with a tag or digest (e.g., ghcr.io/user/image:tag) - overrides --image-base, --version and --image-tag-from-git
         #[arg(long)]
         image: Option<String>,
         /// Image base without tag (e.g., ghcr.io/user/image)
         #[arg(long, default_value = "ghcr.io/wasmcp/example-mcp")]
         image_base: String,
+        /// Tag the image with the current git commit's short hash instead of --version
+        #[arg(long)]
+        image_tag_from_git: bool,
+        /// Format of the generated manifest (yaml or json)
+        #[arg(long, default_value = "yaml", value_parser = ["yaml", "json"])]
+        manifest_format: String,
+        /// Extra arguments appended verbatim to `kubectl apply`, given after `--`.
+        /// They are not checked, so a wrong flag fails the deploy or changes what it does
+        #[arg(last = true)]
+        raw: Vec<String>,
     },
     /// Check deployment status
     Status {
@@ -62,6 +80,30 @@ enum Commands {
         /// Application name
         #[arg(long, default_value = "mcp-multi-tools")]
         app_name: String,
+        /// Wait until the deleted resources are gone, reporting any that are stuck
+        #[arg(long)]
+        wait_for_delete: bool,
+        /// Seconds to wait with --wait-for-delete before giving up
+        #[arg(long, default_value = "120")]
+        delete_timeout: u64,
+    },
+    /// Check that the MCP endpoint answers tools/list, saving debugging artifacts on failure
+    Healthcheck {
+        /// MCP endpoint to probe
+        #[arg(short, long, default_value = "http://localhost:30950/mcp")]
+        url: String,
+        /// Namespace
+        #[arg(short, long, default_value = "default")]
+        namespace: String,
+        /// Application name
+        #[arg(long, default_value = "mcp-multi-tools")]
+        app_name: String,
+        /// Directory to save the tools/list response, pods, pod logs and events into
+        #[arg(long, default_value = "build/healthcheck")]
+        artifacts_dir: String,
+        /// Save artifacts even when the check passes
+        #[arg(long)]
+        always: bool,
     },
 }
 
@@ -69,12 +111,26 @@ fn main() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Setup { cluster, license_key } => setup_cluster(&cluster, &license_key)?,
-        Commands::Deploy { deploy_type, version, namespace, app_name, image, image_base } => {
-            deploy(&deploy_type, &version, &namespace, &app_name, image.as_deref(), &image_base)?
+        Commands::Setup { cluster, license_key, raw } => setup_cluster(&cluster, &license_key, &raw)?,
+        Commands::Deploy { deploy_type, version, namespace, app_name, image, image_base, image_tag_from_git, manifest_format, raw } => {
+            if image_tag_from_git && image.is_some() {
+                println!("{} --image takes precedence; ignoring --image-tag-from-git", "⚠".yellow());
+            }
+            let git_tag = if image_tag_from_git && image.is_none() {
+                Some(git_short_hash()?)
+            } else {
+                None
+            };
+            let (image, version) = resolve_image(image.as_deref(), &image_base, &version, git_tag.as_deref())?;
+            deploy(&deploy_type, &version, &namespace, &app_name, &image, &manifest_format, &raw)?
         }
         Commands::Status { namespace, app_name } => check_status(&namespace, &app_name)?,
-        Commands::Clean { namespace, app_name } => clean(&namespace, &app_name)?,
+        Commands::Clean { namespace, app_name, wait_for_delete, delete_timeout } => {
+            clean(&namespace, &app_name, wait_for_delete.then(|| Duration::from_secs(delete_timeout)))?
+        }
+        Commands::Healthcheck { url, namespace, app_name, artifacts_dir, always } => {
+            healthcheck::run(&url, &namespace, &app_name, &artifacts_dir, always)?
+        }
     }
 
     Ok(())
@@ -92,7 +148,38 @@ fn kind_cmd() -> Command {
     Command::new("kind")
 }
 
-fn setup_cluster(cluster_name: &str, license_key: &str) -> Result<()> {
+/// `helm install` for Cosmonic Control, followed by the `--` passthrough arguments in order
+fn install_cosmonic_cmd(namespace: &str, license_key: &str, raw: &[String]) -> Command {
+    let mut cmd = helm_cmd();
+    cmd.args([
+        "install", "cosmonic-control",
+        "oci://ghcr.io/cosmonic/cosmonic-control",
+        "--version", "0.3.0",
+        "--namespace", namespace,
+        "--set", &format!("cosmonicLicenseKey={}", license_key),
+        "--set", "envoy.service.type=NodePort",
+        "--set", "envoy.service.httpNodePort=30950",
+        "--wait",
+        "--timeout", "5m"
+    ])
+    .args(raw);
+    cmd
+}
+
+/// `kubectl apply` for a rendered manifest, followed by the `--` passthrough arguments in order
+fn apply_manifest_cmd(manifest: &str, raw: &[String]) -> Command {
+    let mut cmd = kubectl_cmd();
+    cmd.args(["apply", "-f", manifest]).args(raw);
+    cmd
+}
+
+fn warn_raw(program: &str, raw: &[String]) {
+    if !raw.is_empty() {
+        println!("{} Passing extra arguments to {}: {}", "⚠".yellow(), program, raw.join(" "));
+    }
+}
+
+fn setup_cluster(cluster_name: &str, license_key: &str, raw: &[String]) -> Result<()> {
     println!("{}", format!("Setting up cluster: {}", cluster_name).cyan());
 
     // Check if cluster exists
@@ -108,6 +195,7 @@ fn setup_cluster(cluster_name: &str, license_key: &str) -> Result<()> {
         println!("{}", "Creating kind cluster...".cyan());
 
         // Create kind config
+        #[allow(clippy::useless_format)]
         let kind_config = format!(r#"kind: Cluster
 apiVersion: kind.x-k8s.io/v1alpha4
 nodes:
@@ -225,18 +313,8 @@ containerdConfigPatches:
         println!("{} Cosmonic Control already installed", "✓".green());
     } else {
         // Install Cosmonic Control with helm
-        let install = helm_cmd()
-            .args([
-                "install", "cosmonic-control",
-                "oci://ghcr.io/cosmonic/cosmonic-control",
-                "--version", "0.3.0",
-                "--namespace", namespace,
-                "--set", &format!("cosmonicLicenseKey={}", license_key),
-                "--set", "envoy.service.type=NodePort",
-                "--set", "envoy.service.httpNodePort=30950",
-                "--wait",
-                "--timeout", "5m"
-            ])
+        warn_raw("helm", raw);
+        let install = install_cosmonic_cmd(namespace, license_key, raw)
             .output()
             .context("Failed to install Cosmonic Control")?;
 
@@ -293,7 +371,7 @@ containerdConfigPatches:
     Ok(())
 }
 
-fn deploy(deploy_type: &str, version: &str, namespace: &str, app_name: &str, image_override: Option<&str>, image_base: &str) -> Result<()> {
+fn deploy(deploy_type: &str, version: &str, namespace: &str, app_name: &str, image: &str, manifest_format: &str, raw: &[String]) -> Result<()> {
     println!("{}", format!("Deploying {} as {}", app_name, deploy_type).cyan());
 
     // Verify prerequisites
@@ -327,18 +405,11 @@ fn deploy(deploy_type: &str, version: &str, namespace: &str, app_name: &str, ima
 
         let cluster_name = std::env::var("CLUSTER_NAME").unwrap_or_else(|_| "cosmonic-cluster".to_string());
 
-        setup_cluster(&cluster_name, &license_key)?;
+        setup_cluster(&cluster_name, &license_key, &[])?;
     } else {
         println!("{} Prerequisites verified", "✓".green());
     }
 
-    // Determine final image reference
-    let image = if let Some(img) = image_override {
-        img.to_string()
-    } else {
-        format!("{}:{}", image_base, version)
-    };
-
     // Ensure namespace exists (suppress warning for default namespace)
     if namespace != "default" {
         kubectl_cmd()
@@ -376,7 +447,7 @@ fn deploy(deploy_type: &str, version: &str, namespace: &str, app_name: &str, ima
     context.insert("app_name", app_name);
     context.insert("namespace", namespace);
     context.insert("version", version);
-    context.insert("image", &image);
+    context.insert("image", image);
 
     let template_name = if deploy_type == "httptrigger" {
         "httptrigger.yaml.tpl"
@@ -387,11 +458,19 @@ fn deploy(deploy_type: &str, version: &str, namespace: &str, app_name: &str, ima
     let rendered = tera.render(template_name, &context)
         .context("Failed to render template")?;
 
-    let output_file = output_dir.join(if deploy_type == "httptrigger" {
-        "httptrigger.yaml"
+    let manifest_name = if deploy_type == "httptrigger" {
+        "httptrigger"
     } else {
-        "deployment.yaml"
-    });
+        "deployment"
+    };
+
+    let rendered = if manifest_format == "json" {
+        yaml_to_json(&rendered)?
+    } else {
+        rendered
+    };
+
+    let output_file = output_dir.join(format!("{}.{}", manifest_name, manifest_format));
 
     fs::write(&output_file, rendered)
         .context("Failed to write manifest")?;
@@ -399,8 +478,8 @@ fn deploy(deploy_type: &str, version: &str, namespace: &str, app_name: &str, ima
     println!("{} Manifest generated: {}", "✓".green(), output_file.display());
 
     // Apply manifest
-    let apply = kubectl_cmd()
-        .args(["apply", "-f", output_file.to_str().unwrap()])
+    warn_raw("kubectl", raw);
+    let apply = apply_manifest_cmd(output_file.to_str().unwrap(), raw)
         .output()
         .context("Failed to apply manifest")?;
 
@@ -464,8 +543,80 @@ fn deploy(deploy_type: &str, version: &str, namespace: &str, app_name: &str, ima
     Ok(())
 }
 
+/// Pick the image to deploy and the version label that goes with it. In
+/// order of precedence: `--image` (which must carry a tag or digest, so it
+/// never silently means `:latest`), then `--image-base` tagged with the git
+/// hash from `--image-tag-from-git`, then `--image-base` tagged with `--version`.
+fn resolve_image(image: Option<&str>, image_base: &str, version: &str, git_tag: Option<&str>) -> Result<(String, String)> {
+    if let Some(image) = image {
+        // A digest pins the image without a tag to use as the version
+        if image.contains('@') {
+            return Ok((image.to_string(), version.to_string()));
+        }
+        let tag = image_tag(image).ok_or_else(|| {
+            anyhow::anyhow!(
+                "--image {} has no tag, so it would deploy :latest implicitly; add a tag (e.g. {}:{}) or a digest",
+                image,
+                image,
+                version
+            )
+        })?;
+        return Ok((image.to_string(), tag.to_string()));
+    }
+
+    let tag = git_tag.unwrap_or(version);
+    Ok((format!("{}:{}", image_base, tag), tag.to_string()))
+}
+
+/// Tag of an image reference, if it has one. A `:` before the last `/`
+/// belongs to a registry port, as in `localhost:5000/image`.
+fn image_tag(image: &str) -> Option<&str> {
+    let name = image.rsplit('/').next().unwrap_or(image);
+    name.split_once(':').map(|(_, tag)| tag).filter(|tag| !tag.is_empty())
+}
+
+fn git_short_hash() -> Result<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "--image-tag-from-git needs a git checkout: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Convert a rendered YAML manifest to JSON. Multi-document YAML becomes a
+/// `v1/List` of its documents, which kubectl applies like the YAML stream.
+fn yaml_to_json(yaml: &str) -> Result<String> {
+    let mut documents = Vec::new();
+    for document in serde_yaml::Deserializer::from_str(yaml) {
+        let value = serde_json::Value::deserialize(document)
+            .context("Failed to parse rendered manifest as YAML")?;
+        // Skip empty documents, such as one before a leading `---`
+        if !value.is_null() {
+            documents.push(value);
+        }
+    }
+
+    let manifest = match documents.len() {
+        1 => documents.remove(0),
+        _ => serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "List",
+            "items": documents,
+        }),
+    };
+
+    serde_json::to_string_pretty(&manifest).context("Failed to serialize manifest as JSON")
+}
+
 fn check_status(namespace: &str, app_name: &str) -> Result<()> {
     println!("{}", "Checking deployment status...".cyan());
+    ensure_namespace(namespace, app_name)?;
 
     // Check for HTTPTriggers
     let httptrigger = kubectl_cmd()
@@ -514,29 +665,326 @@ fn check_status(namespace: &str, app_name: &str) -> Result<()> {
     Ok(())
 }
 
-fn clean(namespace: &str, app_name: &str) -> Result<()> {
-    println!("{}", format!("Cleaning up deployment: {}", app_name).cyan());
+/// Fail with a hint when `namespace` does not exist, rather than letting each
+/// query report its own "not found". Other failures, such as an unreachable
+/// cluster, are left to the queries that follow.
+fn ensure_namespace(namespace: &str, app_name: &str) -> Result<()> {
+    let output = kubectl_cmd()
+        .args(["get", "namespace", namespace, "-o", "name"])
+        .output()
+        .context("Failed to check namespace")?;
 
-    // Delete HTTPTrigger
-    let _ = kubectl_cmd()
-        .args(["delete", "httptrigger", app_name, "-n", namespace])
-        .output();
+    if output.status.success() || !String::from_utf8_lossy(&output.stderr).contains("NotFound") {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(missing_namespace_message(
+        namespace,
+        app_name,
+        &namespaces_with_app(app_name)
+    )))
+}
 
-    // Delete Deployment
-    let _ = kubectl_cmd()
-        .args(["delete", "deployment", app_name, "-n", namespace])
-        .output();
+/// Namespaces holding any of the application's resources, sorted
+fn namespaces_with_app(app_name: &str) -> Vec<String> {
+    let mut namespaces: Vec<String> = APP_RESOURCE_KINDS
+        .iter()
+        .filter_map(|kind| {
+            kubectl_cmd()
+                .args([
+                    "get",
+                    kind,
+                    "--all-namespaces",
+                    "--field-selector",
+                    &format!("metadata.name={}", app_name),
+                    "-o",
+                    "jsonpath={.items[*].metadata.namespace}",
+                ])
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+        })
+        .flat_map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .split_whitespace()
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    namespaces.sort();
+    namespaces.dedup();
+    namespaces
+}
 
-    // Delete Service
-    let _ = kubectl_cmd()
-        .args(["delete", "service", app_name, "-n", namespace])
-        .output();
+fn missing_namespace_message(namespace: &str, app_name: &str, candidates: &[String]) -> String {
+    let hint = match candidates {
+        [] => format!("No namespace contains {} resources", app_name),
+        [only] => format!("{} resources were found in namespace '{}'; try --namespace {}", app_name, only, only),
+        _ => format!("Namespaces with {} resources: {}", app_name, candidates.join(", ")),
+    };
+    format!(
+        "namespace '{}' does not exist; did you deploy to a different one?\n{}",
+        namespace, hint
+    )
+}
 
-    // Delete Ingress
-    let _ = kubectl_cmd()
-        .args(["delete", "ingress", app_name, "-n", namespace])
-        .output();
+/// Kinds of the resources `clean` deletes, all named after the application
+const APP_RESOURCE_KINDS: [&str; 4] = ["httptrigger", "deployment", "service", "ingress"];
+
+/// How often to check whether deleted resources are gone
+const DELETE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Delete the application's resources. With `wait_for_delete`, block until they
+/// are gone or the timeout elapses, failing with the resources still present.
+fn clean(namespace: &str, app_name: &str, wait_for_delete: Option<Duration>) -> Result<()> {
+    println!("{}", format!("Cleaning up deployment: {}", app_name).cyan());
+    ensure_namespace(namespace, app_name)?;
+
+    for kind in APP_RESOURCE_KINDS {
+        let mut delete = kubectl_cmd();
+        delete.args(["delete", kind, app_name, "-n", namespace]);
+        if wait_for_delete.is_some() {
+            // Waiting is done below, with a timeout and a report of stuck resources
+            delete.arg("--wait=false");
+        }
+        let _ = delete.output();
+    }
+
+    if let Some(timeout) = wait_for_delete {
+        println!("{}", format!("Waiting up to {}s for resources to be deleted...", timeout.as_secs()).cyan());
+
+        let stuck = poll_until_gone(&APP_RESOURCE_KINDS, timeout, DELETE_POLL_INTERVAL, |kind| {
+            resource_exists(kind, app_name, namespace)
+        });
+
+        if !stuck.is_empty() {
+            for kind in &stuck {
+                println!(
+                    "{} {}/{} still present{}",
+                    "⚠".yellow(),
+                    kind,
+                    app_name,
+                    finalizers(kind, app_name, namespace)
+                        .map(|f| format!(" (finalizers: {})", f))
+                        .unwrap_or_default()
+                );
+            }
+            return Err(anyhow::anyhow!(
+                "Timed out after {}s waiting for {} to be deleted",
+                timeout.as_secs(),
+                stuck.join(", ")
+            ));
+        }
+        println!("{} All resources deleted", "✓".green());
+    }
 
     println!("{} Cleanup complete", "✓".green());
     Ok(())
 }
+
+/// Poll `exists` for each kind until none remain or `timeout` elapses, and
+/// return the kinds that were still present at the deadline
+fn poll_until_gone<'a>(
+    kinds: &[&'a str],
+    timeout: Duration,
+    interval: Duration,
+    mut exists: impl FnMut(&str) -> bool,
+) -> Vec<&'a str> {
+    let deadline = Instant::now() + timeout;
+    let mut remaining = kinds.to_vec();
+
+    loop {
+        remaining.retain(|kind| exists(kind));
+        if remaining.is_empty() || Instant::now() >= deadline {
+            return remaining;
+        }
+        std::thread::sleep(interval.min(deadline.saturating_duration_since(Instant::now())));
+    }
+}
+
+/// Unknown kinds (such as HTTPTrigger without Cosmonic Control) count as absent
+fn resource_exists(kind: &str, name: &str, namespace: &str) -> bool {
+    kubectl_cmd()
+        .args(["get", kind, name, "-n", namespace, "--ignore-not-found", "-o", "name"])
+        .output()
+        .map(|output| output.status.success() && !output.stdout.trim_ascii().is_empty())
+        .unwrap_or(false)
+}
+
+/// Finalizers holding up the deletion of a resource, if any
+fn finalizers(kind: &str, name: &str, namespace: &str) -> Option<String> {
+    let output = kubectl_cmd()
+        .args(["get", kind, name, "-n", namespace, "-o", "jsonpath={.metadata.finalizers}"])
+        .output()
+        .ok()?;
+    let finalizers = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !finalizers.is_empty()).then_some(finalizers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poll_until_gone_stops_once_everything_is_deleted() {
+        let mut polls = 0;
+        let stuck = poll_until_gone(&APP_RESOURCE_KINDS, Duration::from_secs(60), Duration::ZERO, |kind| {
+            polls += 1;
+            // Each kind disappears on its second check
+            polls <= APP_RESOURCE_KINDS.len() && kind != "ingress"
+        });
+        assert!(stuck.is_empty());
+        assert_eq!(polls, APP_RESOURCE_KINDS.len() * 2 - 1);
+    }
+
+    #[test]
+    fn poll_until_gone_reports_what_is_left_at_the_deadline() {
+        let stuck = poll_until_gone(&APP_RESOURCE_KINDS, Duration::from_millis(20), Duration::from_millis(5), |kind| {
+            kind == "deployment" || kind == "service"
+        });
+        assert_eq!(stuck, ["deployment", "service"]);
+    }
+
+    #[test]
+    fn poll_until_gone_checks_once_without_a_timeout() {
+        let mut polls = 0;
+        let stuck = poll_until_gone(&["service"], Duration::ZERO, Duration::from_secs(60), |_| {
+            polls += 1;
+            true
+        });
+        assert_eq!(stuck, ["service"]);
+        assert_eq!(polls, 1);
+    }
+
+    fn args(cmd: &Command) -> Vec<String> {
+        cmd.get_args().map(|arg| arg.to_string_lossy().into_owned()).collect()
+    }
+
+    fn raw() -> Vec<String> {
+        ["--set", "replicas=2", "--debug"].map(String::from).to_vec()
+    }
+
+    #[test]
+    fn install_appends_raw_arguments_after_the_built_in_ones() {
+        let install = args(&install_cosmonic_cmd("cosmonic-system", "KEY", &raw()));
+        assert_eq!(install[..2], ["install", "cosmonic-control"]);
+        assert!(install.contains(&"cosmonicLicenseKey=KEY".to_string()));
+        assert_eq!(install[install.len() - 5..], ["--timeout", "5m", "--set", "replicas=2", "--debug"]);
+        assert_eq!(args(&install_cosmonic_cmd("ns", "KEY", &[])).last().map(String::as_str), Some("5m"));
+    }
+
+    #[test]
+    fn apply_appends_raw_arguments_after_the_built_in_ones() {
+        assert_eq!(
+            args(&apply_manifest_cmd("build/manifest.yaml", &raw())),
+            ["apply", "-f", "build/manifest.yaml", "--set", "replicas=2", "--debug"]
+        );
+        assert_eq!(args(&apply_manifest_cmd("-", &[])), ["apply", "-f", "-"]);
+    }
+
+    const BASE: &str = "ghcr.io/example/math";
+
+    #[test]
+    fn image_without_a_tag_is_rejected() {
+        for image in ["ghcr.io/example/math", "localhost:5000/img", "img:"] {
+            let error = resolve_image(Some(image), BASE, "0.1.0", None).unwrap_err().to_string();
+            assert!(error.contains("has no tag"), "{}: {}", image, error);
+        }
+    }
+
+    #[test]
+    fn image_tag_becomes_the_version() {
+        for (image, tag) in [
+            ("ghcr.io/example/math:1.2.3", "1.2.3"),
+            ("localhost:5000/img:tag", "tag"),
+            ("img:dev", "dev"),
+        ] {
+            assert_eq!(
+                resolve_image(Some(image), BASE, "0.1.0", None).unwrap(),
+                (image.to_string(), tag.to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn image_digest_keeps_the_version() {
+        let image = "ghcr.io/example/math@sha256:0123456789abcdef";
+        assert_eq!(
+            resolve_image(Some(image), BASE, "0.1.0", Some("abc1234")).unwrap(),
+            (image.to_string(), "0.1.0".to_string())
+        );
+    }
+
+    #[test]
+    fn image_beats_git_tag_which_beats_version() {
+        assert_eq!(
+            resolve_image(None, BASE, "0.1.0", None).unwrap(),
+            (format!("{}:0.1.0", BASE), "0.1.0".to_string())
+        );
+        assert_eq!(
+            resolve_image(None, BASE, "0.1.0", Some("abc1234")).unwrap(),
+            (format!("{}:abc1234", BASE), "abc1234".to_string())
+        );
+        assert_eq!(
+            resolve_image(Some("other/img:v2"), BASE, "0.1.0", Some("abc1234")).unwrap(),
+            ("other/img:v2".to_string(), "v2".to_string())
+        );
+    }
+
+    fn parse_json(json: &str) -> serde_json::Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn single_document_is_converted_as_is() {
+        let yaml = "apiVersion: v1\nkind: Service\nmetadata:\n  name: math\nspec:\n  ports:\n    - port: 80\n";
+        assert_eq!(
+            parse_json(&yaml_to_json(yaml).unwrap()),
+            serde_json::json!({
+                "apiVersion": "v1",
+                "kind": "Service",
+                "metadata": {"name": "math"},
+                "spec": {"ports": [{"port": 80}]},
+            })
+        );
+        assert_eq!(parse_json(&yaml_to_json(&format!("---\n{}", yaml)).unwrap())["kind"], "Service");
+    }
+
+    #[test]
+    fn several_documents_become_a_list() {
+        let yaml = "---\nkind: Service\nmetadata:\n  name: math\n---\nkind: Deployment\nmetadata:\n  name: math\n";
+        assert_eq!(
+            parse_json(&yaml_to_json(yaml).unwrap()),
+            serde_json::json!({
+                "apiVersion": "v1",
+                "kind": "List",
+                "items": [
+                    {"kind": "Service", "metadata": {"name": "math"}},
+                    {"kind": "Deployment", "metadata": {"name": "math"}},
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_yaml_is_an_error() {
+        assert!(yaml_to_json("kind: [Service").is_err());
+    }
+
+    #[test]
+    fn missing_namespace_hints_at_the_candidates() {
+        let message = |candidates: &[&str]| {
+            let candidates: Vec<String> = candidates.iter().map(|c| c.to_string()).collect();
+            missing_namespace_message("prod", "math", &candidates)
+        };
+        let prefix = "namespace 'prod' does not exist; did you deploy to a different one?\n";
+        assert_eq!(message(&[]), format!("{}No namespace contains math resources", prefix));
+        assert_eq!(
+            message(&["staging"]),
+            format!("{}math resources were found in namespace 'staging'; try --namespace staging", prefix)
+        );
+        assert_eq!(
+            message(&["default", "staging"]),
+            format!("{}Namespaces with math resources: default, staging", prefix)
+        );
+    }
+}
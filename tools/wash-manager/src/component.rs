@@ -0,0 +1,211 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use wasmparser::{ComponentExternalKind, ComponentTypeRef, Encoding, Parser, Payload, Validator, WasmFeatures};
+
+/// Top-level interface imports and exports of a component
+#[derive(Debug, Default)]
+pub struct ComponentInterfaces {
+    pub imports: Vec<String>,
+    pub exports: Vec<String>,
+}
+
+/// Read a component from disk and list the interfaces it imports and exports
+pub fn inspect(path: &Path) -> Result<ComponentInterfaces> {
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    interfaces(&bytes).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Check that a component file exists and is a valid component before handing it to wash
+pub fn check_file(path: &Path) -> Result<()> {
+    if !path.is_file() {
+        return Err(anyhow::anyhow!("Component file not found: {}", path.display()));
+    }
+    let bytes = fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if bytes.is_empty() {
+        return Err(anyhow::anyhow!("Component file is empty: {}", path.display()));
+    }
+    if !bytes.starts_with(b"\0asm") {
+        return Err(anyhow::anyhow!(
+            "Not a WebAssembly file (missing \\0asm magic bytes): {}",
+            path.display()
+        ));
+    }
+
+    interfaces(&bytes).with_context(|| format!("Failed to parse {}", path.display()))?;
+    validate(&bytes).with_context(|| format!("{} failed validation", path.display()))
+}
+
+/// Validate bytes as a WebAssembly component; core modules are rejected
+pub fn validate(bytes: &[u8]) -> Result<()> {
+    if !Parser::is_component(bytes) {
+        return Err(anyhow::anyhow!("Not a component (found a core module or no WebAssembly header)"));
+    }
+    Validator::new_with_features(WasmFeatures::all())
+        .validate_all(bytes)
+        .map(|_| ())
+        .map_err(|e| anyhow::anyhow!("Invalid WebAssembly component: {}", e))
+}
+
+fn interfaces(bytes: &[u8]) -> Result<ComponentInterfaces> {
+    let mut result = ComponentInterfaces::default();
+    // Nested modules and components are reported inline, so only the
+    // sections at depth 1 belong to the outer component.
+    let mut depth = 0;
+
+    for payload in Parser::new(0).parse_all(bytes) {
+        match payload? {
+            Payload::Version { encoding, .. } => {
+                if depth == 0 && encoding != Encoding::Component {
+                    return Err(anyhow::anyhow!("Not a component (found a core module)"));
+                }
+                depth += 1;
+            }
+            Payload::End(_) => depth -= 1,
+            Payload::ComponentImportSection(reader) if depth == 1 => {
+                for import in reader {
+                    let import = import?;
+                    if matches!(import.ty, ComponentTypeRef::Instance(_)) {
+                        result.imports.push(import.name.0.to_string());
+                    }
+                }
+            }
+            Payload::ComponentExportSection(reader) if depth == 1 => {
+                for export in reader {
+                    let export = export?;
+                    if export.kind == ComponentExternalKind::Instance {
+                        result.exports.push(export.name.0.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(result)
+}
+
+/// Small components for tests, built with `wasm-encoder`
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use std::fs;
+    use std::path::PathBuf;
+    use wasm_encoder::{
+        Alias, Component, ComponentAliasSection, ComponentExportKind, ComponentExportSection,
+        ComponentImportSection, ComponentInstanceSection, ComponentSectionId, ComponentTypeRef,
+        ComponentTypeSection, InstanceType, RawSection,
+    };
+
+    pub const TOOLS: &str = "wasmcp:protocol/tools@0.1.0";
+
+    /// A component importing one empty instance named `interface`
+    pub fn importing(interface: &str) -> Vec<u8> {
+        let mut types = ComponentTypeSection::new();
+        types.instance(&InstanceType::new());
+        let mut imports = ComponentImportSection::new();
+        imports.import(interface, ComponentTypeRef::Instance(0));
+
+        let mut component = Component::new();
+        component.section(&types).section(&imports);
+        component.finish()
+    }
+
+    /// A component exporting one empty instance named `interface`
+    pub fn exporting(interface: &str) -> Vec<u8> {
+        let mut instances = ComponentInstanceSection::new();
+        instances.export_items(Vec::<(&str, ComponentExportKind, u32)>::new());
+        let mut exports = ComponentExportSection::new();
+        exports.export(interface, ComponentExportKind::Instance, 0, None);
+
+        let mut component = Component::new();
+        component.section(&instances).section(&exports);
+        component.finish()
+    }
+
+    /// What `wac plug` produces: `socket` instantiated with the `interface`
+    /// export of an instance of `plug`
+    pub fn plugged(socket: &[u8], plug: &[u8], interface: &str) -> Vec<u8> {
+        let nested = |data| RawSection {
+            id: ComponentSectionId::Component as u8,
+            data,
+        };
+        let mut plug_instance = ComponentInstanceSection::new();
+        plug_instance.instantiate(1, Vec::<(&str, ComponentExportKind, u32)>::new());
+        let mut aliases = ComponentAliasSection::new();
+        aliases.alias(Alias::InstanceExport {
+            instance: 0,
+            kind: ComponentExportKind::Instance,
+            name: interface,
+        });
+        let mut socket_instance = ComponentInstanceSection::new();
+        socket_instance.instantiate(0, [(interface, ComponentExportKind::Instance, 1)]);
+
+        let mut component = Component::new();
+        component
+            .section(&nested(socket))
+            .section(&nested(plug))
+            .section(&plug_instance)
+            .section(&aliases)
+            .section(&socket_instance);
+        component.finish()
+    }
+
+    /// Write `bytes` to a file in the temp directory unique to this process
+    pub fn write(name: &str, bytes: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("wash-manager-{}-{}", std::process::id(), name));
+        fs::write(&path, bytes).expect("write fixture");
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::{self, TOOLS};
+    use super::*;
+    use wasm_encoder::Module;
+
+    #[test]
+    fn components_are_accepted_and_inspected() {
+        let bytes = fixtures::importing(TOOLS);
+        validate(&bytes).expect("valid component");
+
+        let path = fixtures::write("tools.wasm", &bytes);
+        check_file(&path).expect("valid component file");
+        let found = inspect(&path).expect("inspect");
+        fs::remove_file(&path).ok();
+        assert_eq!(found.imports, [TOOLS]);
+        assert!(found.exports.is_empty());
+    }
+
+    #[test]
+    fn exports_are_inspected() {
+        let path = fixtures::write("exporter.wasm", &fixtures::exporting(TOOLS));
+        let found = inspect(&path).expect("inspect");
+        fs::remove_file(&path).ok();
+        assert!(found.imports.is_empty());
+        assert_eq!(found.exports, [TOOLS]);
+    }
+
+    #[test]
+    fn core_modules_are_rejected() {
+        let bytes = Module::new().finish();
+        let error = validate(&bytes).expect_err("core module");
+        assert!(error.to_string().contains("Not a component"), "{}", error);
+
+        let path = fixtures::write("core.wasm", &bytes);
+        let error = check_file(&path).expect_err("core module file");
+        fs::remove_file(&path).ok();
+        assert!(format!("{:#}", error).contains("Not a component"), "{:#}", error);
+    }
+
+    #[test]
+    fn files_that_are_not_webassembly_are_rejected() {
+        let path = fixtures::write("text.wasm", b"not wasm");
+        let error = check_file(&path).expect_err("not wasm");
+        fs::remove_file(&path).ok();
+        assert!(error.to_string().contains("missing \\0asm magic bytes"), "{}", error);
+
+        assert!(validate(b"").is_err());
+        assert!(check_file(Path::new("/nonexistent/component.wasm")).is_err());
+    }
+}
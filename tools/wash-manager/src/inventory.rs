@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::{Map, Value, json};
+use std::fs;
+use std::path::Path;
+
+use crate::{HTTP_CONFIG_NAME, wash_json};
+
+/// Named configs whose contents are included in the dump
+const KNOWN_CONFIGS: &[&str] = &[HTTP_CONFIG_NAME];
+
+/// Collect the wash state into one JSON document and write it to `output`,
+/// or to stdout if no path is given
+pub fn run(output: Option<&str>) -> Result<()> {
+    let dump = serde_json::to_string_pretty(&collect(wash_json))?;
+
+    match output {
+        Some(path) => {
+            let path = Path::new(path);
+            if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                fs::create_dir_all(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(path, dump).with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("{} Inventory written to {}", "✓".green(), path.display());
+        }
+        // Nothing else goes to stdout, so the dump can be piped into jq
+        None => println!("{}", dump),
+    }
+    Ok(())
+}
+
+/// Assemble the dump from `query`, which runs a wash command and returns its
+/// parsed JSON output (`Null` if it failed). Hosts come from `wash get hosts`
+/// and each one gets its own inventory.
+fn collect(query: impl Fn(&[&str]) -> Value) -> Value {
+    let hosts = query(&["get", "hosts", "-o", "json"]);
+
+    let inventories: Map<String, Value> = hosts["hosts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|host| host["id"].as_str())
+        .map(|id| (id.to_string(), query(&["get", "inventory", id, "-o", "json"])))
+        .collect();
+
+    let configs: Map<String, Value> = KNOWN_CONFIGS
+        .iter()
+        .map(|name| (name.to_string(), query(&["config", "get", name, "-o", "json"])))
+        .collect();
+
+    json!({
+        "hosts": hosts,
+        "inventories": inventories,
+        "links": query(&["get", "links", "-o", "json"]),
+        "configs": configs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Canned `wash` output for `args`, as `wash_json` would parse it
+    fn mocked_wash(args: &[&str]) -> Value {
+        match args {
+            ["get", "hosts", ..] => json!({"success": true, "hosts": [{"id": "NHOST1"}, {"id": "NHOST2"}]}),
+            ["get", "inventory", "NHOST1", ..] => {
+                json!({"success": true, "inventories": [{"host_id": "NHOST1", "components": [{"id": "math"}]}]})
+            }
+            // The second host's inventory query fails
+            ["get", "inventory", "NHOST2", ..] => Value::Null,
+            ["get", "links", ..] => json!({"success": true, "links": [{"source_id": "http-server", "target": "math"}]}),
+            ["config", "get", HTTP_CONFIG_NAME, ..] => json!({"address": "0.0.0.0:8080"}),
+            _ => panic!("unexpected wash query {:?}", args),
+        }
+    }
+
+    #[test]
+    fn bundle_assembles_every_query() {
+        let queries = RefCell::new(Vec::new());
+        let dump = collect(|args| {
+            queries.borrow_mut().push(args.join(" "));
+            mocked_wash(args)
+        });
+
+        assert_eq!(dump["hosts"], mocked_wash(&["get", "hosts"]));
+        assert_eq!(dump["inventories"]["NHOST1"]["inventories"][0]["components"][0]["id"], "math");
+        assert_eq!(dump["inventories"]["NHOST2"], Value::Null);
+        assert_eq!(dump["inventories"].as_object().map(Map::len), Some(2));
+        assert_eq!(dump["links"]["links"][0]["target"], "math");
+        assert_eq!(dump["configs"][HTTP_CONFIG_NAME]["address"], "0.0.0.0:8080");
+        assert_eq!(
+            queries.into_inner(),
+            [
+                "get hosts -o json".to_string(),
+                "get inventory NHOST1 -o json".to_string(),
+                "get inventory NHOST2 -o json".to_string(),
+                format!("config get {} -o json", HTTP_CONFIG_NAME),
+                "get links -o json".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn failed_host_query_leaves_no_inventories() {
+        let dump = collect(|args| match args {
+            ["get", "hosts", ..] => Value::Null,
+            _ => mocked_wash(args),
+        });
+
+        assert_eq!(dump["hosts"], Value::Null);
+        assert_eq!(dump["inventories"], json!({}));
+        assert_eq!(dump["links"]["links"][0]["source_id"], "http-server");
+    }
+}
@@ -1,13 +1,25 @@
+mod component;
+mod healthcheck;
+mod inventory;
+mod wash_bin;
+mod watch;
+
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
 use colored::Colorize;
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::{Duration, Instant};
 
 #[derive(Parser)]
 #[command(name = "wash-manager")]
 #[command(about = "Manage wasmCloud development environment", long_about = None)]
 struct Cli {
+    /// Path to the wash binary (default: $WASH_BIN, then wash on PATH)
+    #[arg(long, global = true)]
+    wash_path: Option<PathBuf>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -15,18 +27,35 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     /// Check if wash is currently running
-    Status,
+    Status {
+        /// Keep refreshing, slowing down while nothing changes
+        #[arg(short, long)]
+        watch: bool,
+        /// Seconds between refreshes with --watch (minimum 1)
+        #[arg(long, default_value = "5")]
+        interval: u64,
+        /// Print human-readable text, or one JSON summary for scripts (text or json)
+        #[arg(short, long, default_value = "text", value_parser = ["text", "json"], conflicts_with = "watch")]
+        output: String,
+    },
     /// Start the development environment
     Start {
-        /// Path to the component WASM file
-        #[arg(short, long)]
-        component: String,
-        /// Component ID to use
-        #[arg(short, long, default_value = "mcp-multi-tools")]
-        id: String,
+        /// Path to a component WASM file (repeatable, each linked to the shared HTTP server)
+        #[arg(short, long = "component", required = true)]
+        components: Vec<String>,
+        /// Component ID to use, one per --component and in the same order
+        #[arg(short, long = "id", default_value = "mcp-multi-tools")]
+        ids: Vec<String>,
         /// Port to bind HTTP server to
         #[arg(short, long, default_value = "8080")]
         port: u16,
+        /// Seconds to wait for wash, the component and the provider to become ready
+        #[arg(long, default_value = "60")]
+        timeout: u64,
+        /// Extra arguments appended verbatim to `wash start component`, given after `--`.
+        /// They are not checked, so a wrong flag fails the start or changes what it does
+        #[arg(last = true)]
+        raw: Vec<String>,
     },
     /// Stop the development environment and clean up
     Stop {
@@ -36,29 +65,99 @@ enum Commands {
         /// Clean up configs
         #[arg(short, long, default_value = "true")]
         cleanup: bool,
+        /// Show what would be removed, and what else depends on it, without removing anything
+        #[arg(long)]
+        explain: bool,
+        /// Leave the HTTP provider running so the next start can reuse it
+        #[arg(long)]
+        keep_provider: bool,
     },
     /// Clean up persistent configurations and links
     Clean,
+    /// Compose multiple components into a single component with `wac plug`
+    Compose {
+        /// Component whose imports are satisfied by the plugs
+        #[arg(short, long)]
+        socket: String,
+        /// Component whose exports are plugged into the socket (repeatable)
+        #[arg(short, long = "plug", required = true)]
+        plugs: Vec<String>,
+        /// Path to write the composed component to
+        #[arg(short, long, default_value = "build/composed.wasm")]
+        output: String,
+    },
+    /// Check that the MCP endpoint answers tools/list, saving debugging artifacts on failure
+    Healthcheck {
+        /// MCP endpoint to probe
+        #[arg(short, long, default_value = "http://localhost:8080/mcp")]
+        url: String,
+        /// Directory to save the tools/list response, wash inventory, links and host log into
+        #[arg(long, default_value = "build/healthcheck")]
+        artifacts_dir: String,
+        /// Save artifacts even when the check passes
+        #[arg(long)]
+        always: bool,
+    },
+    /// Write hosts, per-host inventories, links and known configs as one JSON document
+    DumpInventory {
+        /// File to write the dump to (default: stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // compose only runs wac
+    if !matches!(cli.command, Commands::Compose { .. }) {
+        wash_bin::init(cli.wash_path)?;
+    }
+
     match cli.command {
-        Commands::Status => check_status()?,
-        Commands::Start { component, id, port } => start_env(&component, &id, port)?,
-        Commands::Stop { id, cleanup } => stop_env(&id, cleanup)?,
+        Commands::Status { watch, interval, output } => {
+            if output == "json" {
+                println!("{}", serde_json::to_string_pretty(&status_summary(wash_json))?);
+            } else if watch {
+                watch::run(Duration::from_secs(interval), check_status)?
+            } else {
+                check_status()?;
+            }
+        }
+        Commands::Start { components, ids, port, timeout, raw } => {
+            start_env(&component_pairs(components, ids)?, port, Duration::from_secs(timeout), &raw)?
+        }
+        Commands::Stop { id, cleanup, explain, keep_provider } => {
+            if explain {
+                explain_stop(&id, cleanup, keep_provider)?
+            } else {
+                stop_env(&id, cleanup, keep_provider)?
+            }
+        }
         Commands::Clean => clean_configs()?,
+        Commands::Compose { socket, plugs, output } => compose(&socket, &plugs, &output)?,
+        Commands::Healthcheck { url, artifacts_dir, always } => {
+            healthcheck::run(&url, &artifacts_dir, always)?
+        }
+        Commands::DumpInventory { output } => inventory::run(output.as_deref())?,
     }
 
     Ok(())
 }
 
+const HTTP_PROVIDER_ID: &str = "httpserver";
+const HTTP_CONFIG_NAME: &str = "httpserver-config";
+
 fn wash_cmd() -> Command {
-    Command::new("/opt/homebrew/Cellar/wash/0.42.0/bin/wash")
+    Command::new(wash_bin::path())
 }
 
-fn check_status() -> Result<()> {
+fn wac_cmd() -> Command {
+    Command::new("wac")
+}
+
+/// Print the wash status, returning what was observed for `status --watch`
+fn check_status() -> Result<watch::Snapshot> {
     println!("{}", "Checking wasmCloud status...".cyan());
 
     // Check if wash host is actually running by trying to get hosts
@@ -68,6 +167,10 @@ fn check_status() -> Result<()> {
         .context("Failed to check hosts")?;
 
     let wash_running = hosts_output.status.success() && !hosts_output.stdout.is_empty();
+    let mut snapshot = watch::Snapshot {
+        wash_running,
+        ..Default::default()
+    };
 
     if wash_running {
         println!("{} {}", "✓".green(), "wash is running".green());
@@ -84,6 +187,7 @@ fn check_status() -> Result<()> {
         }
 
         // Get inventory if we can find a host
+        #[allow(clippy::collapsible_if)]
         if let Ok(json_str) = String::from_utf8(hosts_output.stdout) {
             if let Ok(json) = serde_json::from_str::<Value>(&json_str) {
                 if let Some(hosts) = json["hosts"].as_array() {
@@ -102,16 +206,83 @@ fn check_status() -> Result<()> {
                 }
             }
         }
+
+        snapshot.components = running_components(&wash_json(&["get", "inventory", "-o", "json"]));
+        snapshot.components.sort();
     } else {
         println!("{} {}", "✗".red(), "wash is not running".red());
         println!("\n{}", "To start wash, run: wash up".yellow());
     }
 
-    Ok(())
+    Ok(snapshot)
+}
+
+/// Whether wash is running, the host IDs, and the components, providers and
+/// links across all hosts, from `query` as in [`inventory`]. Components and
+/// providers are sorted IDs; links are as `wash get links` reports them.
+fn status_summary(query: impl Fn(&[&str]) -> Value) -> Value {
+    let hosts = query(&["get", "hosts", "-o", "json"]);
+    let host_ids: Vec<&str> = hosts["hosts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|host| host["id"].as_str())
+        .collect();
+
+    let inventory = query(&["get", "inventory", "-o", "json"]);
+    let mut components = running_components(&inventory);
+    components.sort();
+    let mut providers = running_providers(&inventory);
+    providers.sort();
+    let links = query(&["get", "links", "-o", "json"]);
+
+    serde_json::json!({
+        "wash_running": !hosts.is_null(),
+        "hosts": host_ids,
+        "components": components,
+        "providers": providers,
+        "links": links["links"].as_array().cloned().unwrap_or_default(),
+    })
+}
+
+/// Pair each `--component` with the `--id` at the same position
+fn component_pairs(components: Vec<String>, ids: Vec<String>) -> Result<Vec<(String, String)>> {
+    if components.len() != ids.len() {
+        return Err(anyhow::anyhow!(
+            "Got {} --component and {} --id values; pass one --id per --component, in the same order",
+            components.len(),
+            ids.len()
+        ));
+    }
+
+    let mut seen = HashSet::new();
+    if let Some(duplicate) = ids.iter().find(|id| !seen.insert(id.as_str())) {
+        return Err(anyhow::anyhow!(
+            "Component ID '{}' is given more than once; each --component needs its own --id",
+            duplicate
+        ));
+    }
+
+    Ok(components.into_iter().zip(ids).collect())
 }
 
-fn start_env(component_path: &str, component_id: &str, port: u16) -> Result<()> {
-    println!("{}", format!("Starting development environment for component: {}", component_id).cyan());
+/// Start every `(path, id)` component and link each one to the shared HTTP server
+fn start_env(components: &[(String, String)], port: u16, timeout: Duration, raw: &[String]) -> Result<()> {
+    let ids: Vec<&str> = components.iter().map(|(_, id)| id.as_str()).collect();
+    println!(
+        "{}",
+        format!("Starting development environment for component(s): {}", ids.join(", ")).cyan()
+    );
+
+    // Catch broken builds before touching wash, whose errors for bad files are opaque
+    for (component_path, _) in components {
+        let local_path = component_path.strip_prefix("file://").unwrap_or(component_path);
+        component::check_file(Path::new(local_path))?;
+    }
+    println!("{} Component file(s) valid", "✓".green());
+
+    // A taken port only shows up later as a provider that silently serves nothing
+    check_port(port)?;
 
     // Step 1: Start wash if needed
     let hosts_check = wash_cmd()
@@ -135,16 +306,18 @@ fn start_env(component_path: &str, component_id: &str, port: u16) -> Result<()>
             ));
         }
 
+        wait_until("a wash host to come up", timeout, || {
+            wash_json(&["get", "hosts", "--output", "json"])["hosts"]
+                .as_array()
+                .is_some_and(|hosts| !hosts.is_empty())
+        })?;
         println!("{} wash started", "✓".green());
-
-        // Wait a moment for wash to fully initialize
-        std::thread::sleep(std::time::Duration::from_secs(2));
     } else {
-        println!("{} {}", "✓".green(), "wash is running");
+        println!("{} wash is running", "✓".green());
     }
 
     // Step 2: Ensure HTTP server config exists
-    let config_name = "httpserver-config";
+    let config_name = HTTP_CONFIG_NAME;
     let check_config = wash_cmd()
         .args(["config", "get", config_name])
         .output()
@@ -180,50 +353,60 @@ fn start_env(component_path: &str, component_id: &str, port: u16) -> Result<()>
     }
     println!("{} Config ready", "✓".green());
 
-    // Step 4: Start component (check if already running first)
-    let check_component = wash_cmd()
-        .args(["get", "inventory", "--output", "json"])
-        .output()
-        .context("Failed to check components")?;
+    // Step 4: Start each component (stopping it first if already running)
+    if !raw.is_empty() {
+        println!("{} Passing extra arguments to wash: {}", "⚠".yellow(), raw.join(" "));
+    }
+    for (component_path, component_id) in components {
+        let (component_path, component_id) = (component_path.as_str(), component_id.as_str());
+        let check_component = wash_cmd()
+            .args(["get", "inventory", "--output", "json"])
+            .output()
+            .context("Failed to check components")?;
 
-    let component_exists = if check_component.status.success() {
-        let inventory = String::from_utf8_lossy(&check_component.stdout);
-        inventory.contains(component_id)
-    } else {
-        false
-    };
+        let component_exists = if check_component.status.success() {
+            let inventory = String::from_utf8_lossy(&check_component.stdout);
+            inventory.contains(component_id)
+        } else {
+            false
+        };
 
-    if component_exists {
-        // Stop existing component
-        let stop_component = wash_cmd()
-            .args(["stop", "component", component_id])
+        if component_exists {
+            // Stop existing component
+            let stop_component = wash_cmd()
+                .args(["stop", "component", component_id])
+                .output()
+                .context("Failed to stop existing component")?;
+
+            if !stop_component.status.success() {
+                return Err(anyhow::anyhow!(
+                    "Failed to stop existing component: {}",
+                    String::from_utf8_lossy(&stop_component.stderr)
+                ));
+            }
+        }
+
+        // Start component
+        let start_component = start_component_cmd(component_path, component_id, raw)
             .output()
-            .context("Failed to stop existing component")?;
+            .context("Failed to start component")?;
 
-        if !stop_component.status.success() {
+        if !start_component.status.success() {
             return Err(anyhow::anyhow!(
-                "Failed to stop existing component: {}",
-                String::from_utf8_lossy(&stop_component.stderr)
+                "Failed to start component: {}",
+                String::from_utf8_lossy(&start_component.stderr)
             ));
         }
+        wait_until(&format!("component {} to run", component_id), timeout, || {
+            running_components(&wash_json(&["get", "inventory", "--output", "json"]))
+                .iter()
+                .any(|id| id == component_id)
+        })?;
+        println!("{} Component {} ready", "✓".green(), component_id);
     }
 
-    // Start component
-    let start_component = wash_cmd()
-        .args(["start", "component", component_path, component_id])
-        .output()
-        .context("Failed to start component")?;
-
-    if !start_component.status.success() {
-        return Err(anyhow::anyhow!(
-            "Failed to start component: {}",
-            String::from_utf8_lossy(&start_component.stderr)
-        ));
-    }
-    println!("{} Component ready", "✓".green());
-
     // Step 5: Start HTTP provider (check if already running first)
-    let provider_id = "httpserver";
+    let provider_id = HTTP_PROVIDER_ID;
     let check_provider = wash_cmd()
         .args(["get", "inventory", "--output", "json"])
         .output()
@@ -254,50 +437,55 @@ fn start_env(component_path: &str, component_id: &str, port: u16) -> Result<()>
             ));
         }
     }
+    wait_until(&format!("provider {} to run", provider_id), timeout, || {
+        running_providers(&wash_json(&["get", "inventory", "--output", "json"]))
+            .iter()
+            .any(|id| id == provider_id)
+    })?;
     println!("{} Provider ready", "✓".green());
 
-    // Wait for provider to fully initialize
-    std::thread::sleep(std::time::Duration::from_secs(2));
-
-    // Step 6: Create link and validate
-    let link = wash_cmd()
-        .args([
-            "link",
-            "put",
-            "httpserver",
-            component_id,
-            "wasi",
-            "http",
-            "--source-config",
-            config_name,
-            "--interface",
-            "incoming-handler",
-        ])
-        .output()
-        .context("Failed to create link")?;
+    // Step 6: Link each component to the HTTP server and validate
+    for (_, component_id) in components {
+        let component_id = component_id.as_str();
+        let link = wash_cmd()
+            .args([
+                "link",
+                "put",
+                "httpserver",
+                component_id,
+                "wasi",
+                "http",
+                "--source-config",
+                config_name,
+                "--interface",
+                "incoming-handler",
+            ])
+            .output()
+            .context("Failed to create link")?;
 
-    if !link.status.success() {
-        return Err(anyhow::anyhow!(
-            "Failed to create link: {}",
-            String::from_utf8_lossy(&link.stderr)
-        ));
-    }
+        if !link.status.success() {
+            return Err(anyhow::anyhow!(
+                "Failed to create link: {}",
+                String::from_utf8_lossy(&link.stderr)
+            ));
+        }
 
-    // Validate link exists
-    let verify_link = wash_cmd()
-        .args(["get", "links", "--output", "json"])
-        .output()
-        .context("Failed to verify links")?;
+        // Validate link exists
+        let verify_link = wash_cmd()
+            .args(["get", "links", "--output", "json"])
+            .output()
+            .context("Failed to verify links")?;
 
-    if verify_link.status.success() {
-        let link_output = String::from_utf8_lossy(&verify_link.stdout);
-        if link_output.contains(component_id) && link_output.contains("httpserver") {
-            println!("{} Link ready", "✓".green());
+        if verify_link.status.success() {
+            let link_output = String::from_utf8_lossy(&verify_link.stdout);
+            if link_output.contains(component_id) && link_output.contains("httpserver") {
+                println!("{} Link for {} ready", "✓".green(), component_id);
+            } else {
+                return Err(anyhow::anyhow!("Link not found in validation"));
+            }
         } else {
-            return Err(anyhow::anyhow!("Link not found in validation"));
+            return Err(anyhow::anyhow!("Failed to validate link"));
         }
-    } else {
-        return Err(anyhow::anyhow!("Failed to validate link"));
     }
 
     println!(
@@ -309,29 +497,151 @@ fn start_env(component_path: &str, component_id: &str, port: u16) -> Result<()>
     Ok(())
 }
 
-fn stop_env(component_id: &str, cleanup: bool) -> Result<()> {
+/// Fail unless `port` can be bound, or is already held by our running HTTP
+/// provider (as when restarting an environment) with a link configured for it
+fn check_port(port: u16) -> Result<()> {
+    let error = match std::net::TcpListener::bind(("0.0.0.0", port)) {
+        // The listener is dropped straight away, freeing the port for the provider
+        Ok(_) => {
+            println!("{} Port {} is free", "✓".green(), port);
+            return Ok(());
+        }
+        Err(error) => error,
+    };
+
+    if error.kind() != std::io::ErrorKind::AddrInUse {
+        return Err(anyhow::anyhow!("Cannot listen on port {}: {}", port, error));
+    }
+    let provider_running = running_providers(&wash_json(&["get", "inventory", "--output", "json"]))
+        .iter()
+        .any(|id| id == HTTP_PROVIDER_ID);
+    if provider_running {
+        let links = wash_json(&["get", "links", "--output", "json"]);
+        let ports = http_link_ports(&links, |name| wash_json(&["config", "get", name, "--output", "json"]));
+        if ports.contains(&port) {
+            println!("{} Port {} is held by the running provider {}", "✓".green(), port, HTTP_PROVIDER_ID);
+            return Ok(());
+        }
+        if !ports.is_empty() {
+            let ports: Vec<String> = ports.iter().map(u16::to_string).collect();
+            return Err(anyhow::anyhow!(
+                "Port {} is already in use, and the running provider {} is configured for port {}, so it is not the holder. Stop the other process (see `lsof -i :{}`) or pick another port with --port",
+                port,
+                HTTP_PROVIDER_ID,
+                ports.join(", "),
+                port
+            ));
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "Port {} is already in use by another process. Stop it (see `lsof -i :{}`) or pick another port with --port",
+        port,
+        port
+    ))
+}
+
+/// Ports the HTTP server provider is configured to listen on: the `address`
+/// of each config named in the `source_config` of its links, where `config`
+/// returns `wash config get` output for a config name
+fn http_link_ports(links: &Value, config: impl Fn(&str) -> Value) -> Vec<u16> {
+    let mut ports = Vec::new();
+    let configs = links["links"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|link| link["source_id"].as_str() == Some(HTTP_PROVIDER_ID))
+        .filter_map(|link| link["source_config"].as_array())
+        .flatten()
+        .filter_map(Value::as_str);
+
+    for name in configs {
+        let port = config(name)["address"]
+            .as_str()
+            .and_then(|address| address.rsplit_once(':'))
+            .and_then(|(_, port)| port.parse().ok());
+        if let Some(port) = port.filter(|port| !ports.contains(port)) {
+            ports.push(port);
+        }
+    }
+    ports
+}
+
+/// `wash start component`, followed by the `--` passthrough arguments in order
+fn start_component_cmd(component_path: &str, component_id: &str, raw: &[String]) -> Command {
+    let mut cmd = wash_cmd();
+    cmd.args(["start", "component", component_path, component_id]).args(raw);
+    cmd
+}
+
+/// A wash invocation performed while tearing down the environment
+struct TeardownStep {
+    /// Resource removed by this step, e.g. "provider httpserver"
+    resource: String,
+    args: Vec<String>,
+}
+
+impl TeardownStep {
+    fn new(resource: impl Into<String>, args: &[&str]) -> Self {
+        TeardownStep {
+            resource: resource.into(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    fn run(&self) {
+        let _ = wash_cmd().args(&self.args).output();
+    }
+}
+
+/// Steps performed by `stop`, in order
+fn stop_steps(component_id: &str, keep_provider: bool) -> Vec<TeardownStep> {
+    let mut steps = vec![TeardownStep::new(
+        format!("link {} wasi:http", component_id),
+        &["link", "del", component_id, "wasi", "http"],
+    )];
+
+    if !keep_provider {
+        steps.push(TeardownStep::new(
+            format!("provider {}", HTTP_PROVIDER_ID),
+            &["stop", "provider", HTTP_PROVIDER_ID],
+        ));
+    }
+
+    steps.push(TeardownStep::new(
+        format!("component {}", component_id),
+        &["stop", "component", component_id],
+    ));
+    steps
+}
+
+/// Steps performed by `clean` (and by `stop` when cleanup is enabled)
+fn clean_steps() -> Vec<TeardownStep> {
+    vec![
+        TeardownStep::new(
+            format!("config {}", HTTP_CONFIG_NAME),
+            &["config", "del", HTTP_CONFIG_NAME],
+        ),
+        // Format: wash link del <source-id> <wit-namespace> <wit-package>
+        TeardownStep::new(
+            "link mcp-multi-tools wasi:http",
+            &["link", "del", "mcp-multi-tools", "wasi", "http"],
+        ),
+    ]
+}
+
+fn stop_env(component_id: &str, cleanup: bool, keep_provider: bool) -> Result<()> {
     println!("{}", format!("Stopping environment for component: {}", component_id).cyan());
 
-    // Delete link
-    println!("{}", "Deleting link...".cyan());
-    let _ = wash_cmd()
-        .args(["link", "del", component_id, "wasi", "http"])
-        .output();
-    println!("{} Link deleted", "✓".green());
-
-    // Stop provider
-    println!("{}", "Stopping HTTP provider...".cyan());
-    let _ = wash_cmd()
-        .args(["stop", "provider", "httpserver"])
-        .output();
-    println!("{} Provider stopped", "✓".green());
-
-    // Stop component
-    println!("{}", "Stopping component...".cyan());
-    let _ = wash_cmd()
-        .args(["stop", "component", component_id])
-        .output();
-    println!("{} Component stopped", "✓".green());
+    for step in stop_steps(component_id, keep_provider) {
+        println!("{}", format!("Removing {}...", step.resource).cyan());
+        step.run();
+        println!("{} Removed {}", "✓".green(), step.resource);
+    }
+
+    if keep_provider {
+        println!("{} Kept provider {} running", "✓".green(), HTTP_PROVIDER_ID);
+    }
 
     if cleanup {
         clean_configs()?;
@@ -344,16 +654,437 @@ fn stop_env(component_id: &str, cleanup: bool) -> Result<()> {
 fn clean_configs() -> Result<()> {
     println!("{}", "Cleaning up persistent configurations and links...".cyan());
 
-    // Delete httpserver-config
-    let _ = wash_cmd()
-        .args(["config", "del", "httpserver-config"])
-        .output();
-
-    // Delete link (format: wash link del <source-id> <wit-namespace> <wit-package>)
-    let _ = wash_cmd()
-        .args(["link", "del", "mcp-multi-tools", "wasi", "http"])
-        .output();
+    for step in clean_steps() {
+        step.run();
+    }
 
     println!("{} Configs and links cleaned", "✓".green());
     Ok(())
 }
+
+/// Components, other than the one being stopped, that still depend on shared resources
+#[derive(Debug, Default, PartialEq)]
+struct SharedUsers {
+    /// Running components linked to the HTTP provider
+    provider: Vec<String>,
+    /// Running components whose links use the HTTP server config
+    config: Vec<String>,
+}
+
+/// Run a wash query and parse its JSON output, yielding `Null` if it fails
+fn wash_json(args: &[&str]) -> Value {
+    wash_cmd()
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice(&output.stdout).ok())
+        .unwrap_or(Value::Null)
+}
+
+/// IDs of the components running across all hosts in `wash get inventory` output
+fn running_components(inventory: &Value) -> Vec<String> {
+    inventory_ids(inventory, "components")
+}
+
+/// IDs of the providers running across all hosts in `wash get inventory` output
+fn running_providers(inventory: &Value) -> Vec<String> {
+    inventory_ids(inventory, "providers")
+}
+
+/// IDs listed under `kind` in every host's inventory
+fn inventory_ids(inventory: &Value, kind: &str) -> Vec<String> {
+    let inventories = inventory["inventories"]
+        .as_array()
+        .cloned()
+        .unwrap_or_else(|| vec![inventory.clone()]);
+
+    inventories
+        .iter()
+        .filter_map(|inv| inv[kind].as_array())
+        .flatten()
+        .filter_map(|item| item["id"].as_str().map(String::from))
+        .collect()
+}
+
+/// Time between readiness checks in `start`
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Poll `ready` until it holds, failing once `timeout` elapses
+fn wait_until(what: &str, timeout: Duration, mut ready: impl FnMut() -> bool) -> Result<()> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if ready() {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            return Err(anyhow::anyhow!(
+                "Timed out after {}s waiting for {}; pass a larger --timeout on slow machines",
+                timeout.as_secs(),
+                what
+            ));
+        }
+        std::thread::sleep(READY_POLL_INTERVAL.min(deadline.saturating_duration_since(Instant::now())));
+    }
+}
+
+/// Find running components other than `component_id` that share the provider or config
+fn shared_users(links: &Value, inventory: &Value, component_id: &str) -> SharedUsers {
+    let running = running_components(inventory);
+    let mut users = SharedUsers::default();
+
+    for link in links["links"].as_array().into_iter().flatten() {
+        let target = match link["target"].as_str() {
+            Some(target) if target != component_id && running.iter().any(|id| id == target) => {
+                target.to_string()
+            }
+            _ => continue,
+        };
+
+        if link["source_id"].as_str() == Some(HTTP_PROVIDER_ID) && !users.provider.contains(&target) {
+            users.provider.push(target.clone());
+        }
+
+        let uses_config = link["source_config"]
+            .as_array()
+            .is_some_and(|configs| configs.iter().any(|c| c.as_str() == Some(HTTP_CONFIG_NAME)));
+        if uses_config && !users.config.contains(&target) {
+            users.config.push(target);
+        }
+    }
+
+    users
+}
+
+/// What `stop --explain` reports
+struct StopPlan {
+    /// Steps `stop` would run, including the cleanup steps if enabled
+    steps: Vec<TeardownStep>,
+    /// Other running components that would lose a resource the steps remove:
+    /// the provider unless it is kept, the config only with cleanup
+    affected: SharedUsers,
+}
+
+/// Build the stop plan for `component_id` from `wash get links` and
+/// `wash get inventory` output
+fn stop_plan(component_id: &str, cleanup: bool, keep_provider: bool, links: &Value, inventory: &Value) -> StopPlan {
+    let mut steps = stop_steps(component_id, keep_provider);
+    if cleanup {
+        steps.extend(clean_steps());
+    }
+
+    let mut affected = shared_users(links, inventory, component_id);
+    if keep_provider {
+        affected.provider.clear();
+    }
+    if !cleanup {
+        affected.config.clear();
+    }
+
+    StopPlan { steps, affected }
+}
+
+fn explain_stop(component_id: &str, cleanup: bool, keep_provider: bool) -> Result<()> {
+    println!(
+        "{}",
+        format!("Stop plan for component: {} (dry run, nothing will be removed)", component_id).cyan()
+    );
+
+    let inventory = wash_json(&["get", "inventory", "--output", "json"]);
+    let links = wash_json(&["get", "links", "--output", "json"]);
+    let plan = stop_plan(component_id, cleanup, keep_provider, &links, &inventory);
+
+    println!("\n{}", "Resources that would be removed:".cyan());
+    for step in &plan.steps {
+        println!("  - {} (wash {})", step.resource, step.args.join(" "));
+    }
+
+    if !plan.affected.provider.is_empty() {
+        println!(
+            "\n{} provider {} is also serving: {}",
+            "⚠".yellow(),
+            HTTP_PROVIDER_ID,
+            plan.affected.provider.join(", ")
+        );
+    }
+    if !plan.affected.config.is_empty() {
+        println!(
+            "{} config {} is also used by links to: {}",
+            "⚠".yellow(),
+            HTTP_CONFIG_NAME,
+            plan.affected.config.join(", ")
+        );
+    }
+    if plan.affected == SharedUsers::default() {
+        println!("\n{} No other running components share these resources", "✓".green());
+    }
+
+    Ok(())
+}
+
+fn compose(socket: &str, plugs: &[String], output: &str) -> Result<()> {
+    compose_with(socket, plugs, output, wac_plug)
+}
+
+/// Check that `plugs` fit `socket`, combine them with `plug` and validate the
+/// component it writes to `output`
+fn compose_with(
+    socket: &str,
+    plugs: &[String],
+    output: &str,
+    plug: impl FnOnce(&str, &[String], &str) -> Result<()>,
+) -> Result<()> {
+    println!("{}", format!("Composing {} plug(s) into {}", plugs.len(), socket).cyan());
+
+    // Validate that every plug satisfies at least one socket import
+    let socket_interfaces = component::inspect(Path::new(socket))?;
+    let mut providers: HashMap<&str, &str> = HashMap::new();
+    let mut mismatches = Vec::new();
+
+    for plug in plugs {
+        let plug_interfaces = component::inspect(Path::new(plug))?;
+        let matched: Vec<&String> = socket_interfaces
+            .imports
+            .iter()
+            .filter(|import| plug_interfaces.exports.contains(import))
+            .collect();
+
+        if matched.is_empty() {
+            mismatches.push(format!(
+                "{} exports [{}] but {} imports none of them",
+                plug,
+                plug_interfaces.exports.join(", "),
+                socket
+            ));
+            continue;
+        }
+
+        for interface in matched {
+            if let Some(previous) = providers.insert(interface, plug) {
+                mismatches.push(format!(
+                    "{} is exported by both {} and {}",
+                    interface, previous, plug
+                ));
+            }
+        }
+    }
+
+    if !mismatches.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Incompatible components:\n  {}\nSocket imports: [{}]",
+            mismatches.join("\n  "),
+            socket_interfaces.imports.join(", ")
+        ));
+    }
+
+    for (interface, plug) in &providers {
+        println!("{} {} <- {}", "✓".green(), interface, plug);
+    }
+    for import in socket_interfaces
+        .imports
+        .iter()
+        .filter(|import| !providers.contains_key(import.as_str()))
+    {
+        println!("{} {} left as an import", "⚠".yellow(), import);
+    }
+
+    if let Some(parent) = Path::new(output).parent() {
+        std::fs::create_dir_all(parent).context("Failed to create output directory")?;
+    }
+
+    plug(socket, plugs, output)?;
+
+    // Confirm the result is a valid component
+    let composed = std::fs::read(output).context("Failed to read composed component")?;
+    component::validate(&composed)?;
+
+    println!(
+        "\n{} {}",
+        "Composition complete!".green().bold(),
+        output.cyan()
+    );
+    Ok(())
+}
+
+/// Run `wac plug`, filling the socket's imports from the plugs' exports
+fn wac_plug(socket: &str, plugs: &[String], output: &str) -> Result<()> {
+    let mut plug_cmd = wac_cmd();
+    plug_cmd.args(["plug", socket]);
+    for plug in plugs {
+        plug_cmd.args(["--plug", plug]);
+    }
+    let plug_output = plug_cmd
+        .args(["-o", output])
+        .output()
+        .context("Failed to run wac (is it installed?)")?;
+
+    if !plug_output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to compose components: {}",
+            String::from_utf8_lossy(&plug_output.stderr)
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn configs(name: &str) -> Value {
+        match name {
+            HTTP_CONFIG_NAME => json!({"address": "0.0.0.0:8080", "success": true}),
+            "other-http" => json!({"address": "127.0.0.1:9090", "success": true}),
+            _ => Value::Null,
+        }
+    }
+
+    #[test]
+    fn http_link_ports_come_from_the_provider_links_config() {
+        let links = json!({"links": [
+            {"source_id": HTTP_PROVIDER_ID, "target": "math", "source_config": [HTTP_CONFIG_NAME]},
+            {"source_id": HTTP_PROVIDER_ID, "target": "statistics", "source_config": [HTTP_CONFIG_NAME, "other-http"]},
+            {"source_id": "keyvalue", "target": "math", "source_config": ["other-http"]},
+        ]});
+        assert_eq!(http_link_ports(&links, configs), [8080, 9090]);
+    }
+
+    #[test]
+    fn http_link_ports_skip_links_without_an_address() {
+        let links = json!({"links": [
+            {"source_id": HTTP_PROVIDER_ID, "target": "math", "source_config": ["missing"]},
+            {"source_id": HTTP_PROVIDER_ID, "target": "statistics"},
+            {"source_id": "keyvalue", "target": "math", "source_config": [HTTP_CONFIG_NAME]},
+        ]});
+        assert!(http_link_ports(&links, configs).is_empty());
+        assert!(http_link_ports(&Value::Null, configs).is_empty());
+    }
+
+    fn sample_inventory() -> Value {
+        json!({"inventories": [
+            {"components": [{"id": "math"}, {"id": "statistics"}], "providers": [{"id": HTTP_PROVIDER_ID}]},
+            {"components": [{"id": "string-utils"}], "providers": []},
+        ]})
+    }
+
+    fn sample_links() -> Value {
+        json!({"links": [
+            {"source_id": HTTP_PROVIDER_ID, "target": "math", "source_config": [HTTP_CONFIG_NAME]},
+            {"source_id": HTTP_PROVIDER_ID, "target": "statistics", "source_config": [HTTP_CONFIG_NAME]},
+            {"source_id": HTTP_PROVIDER_ID, "target": "string-utils", "source_config": ["other-http"]},
+            // Links to components that are not running do not count
+            {"source_id": HTTP_PROVIDER_ID, "target": "stopped", "source_config": [HTTP_CONFIG_NAME]},
+        ]})
+    }
+
+    fn resources(plan: &StopPlan) -> Vec<&str> {
+        plan.steps.iter().map(|step| step.resource.as_str()).collect()
+    }
+
+    #[test]
+    fn shared_users_excludes_the_stopped_component() {
+        let users = shared_users(&sample_links(), &sample_inventory(), "math");
+        assert_eq!(
+            users,
+            SharedUsers {
+                provider: vec!["statistics".to_string(), "string-utils".to_string()],
+                config: vec!["statistics".to_string()],
+            }
+        );
+        assert_eq!(shared_users(&Value::Null, &Value::Null, "math"), SharedUsers::default());
+    }
+
+    #[test]
+    fn stop_plan_with_cleanup_removes_and_warns_about_everything() {
+        let plan = stop_plan("math", true, false, &sample_links(), &sample_inventory());
+        assert_eq!(
+            resources(&plan),
+            [
+                "link math wasi:http",
+                "provider httpserver",
+                "component math",
+                "config httpserver-config",
+                "link mcp-multi-tools wasi:http",
+            ]
+        );
+        assert_eq!(plan.affected.provider, ["statistics", "string-utils"]);
+        assert_eq!(plan.affected.config, ["statistics"]);
+    }
+
+    #[test]
+    fn stop_plan_only_warns_about_resources_it_removes() {
+        let plan = stop_plan("math", false, true, &sample_links(), &sample_inventory());
+        assert_eq!(resources(&plan), ["link math wasi:http", "component math"]);
+        assert_eq!(plan.affected, SharedUsers::default());
+
+        let plan = stop_plan("math", false, false, &sample_links(), &sample_inventory());
+        assert_eq!(plan.affected.provider, ["statistics", "string-utils"]);
+        assert!(plan.affected.config.is_empty());
+    }
+
+    /// Socket and plug fixture files, removed again by [`remove`]
+    fn compose_fixtures(name: &str, plug_export: &str) -> [String; 3] {
+        use component::fixtures;
+        let socket = fixtures::write(&format!("{}-socket.wasm", name), &fixtures::importing(fixtures::TOOLS));
+        let plug = fixtures::write(&format!("{}-plug.wasm", name), &fixtures::exporting(plug_export));
+        let output = std::env::temp_dir().join(format!("wash-manager-{}-{}-composed.wasm", std::process::id(), name));
+        [socket, plug, output].map(|path| path.display().to_string())
+    }
+
+    fn remove(paths: &[String]) {
+        for path in paths {
+            std::fs::remove_file(path).ok();
+        }
+    }
+
+    #[test]
+    fn compose_produces_a_valid_component() {
+        let [socket, plug, output] = compose_fixtures("valid", component::fixtures::TOOLS);
+        let result = compose_with(&socket, std::slice::from_ref(&plug), &output, |socket, plugs, output| {
+            let socket = std::fs::read(socket)?;
+            let plug = std::fs::read(&plugs[0])?;
+            std::fs::write(output, component::fixtures::plugged(&socket, &plug, component::fixtures::TOOLS))?;
+            Ok(())
+        });
+        let composed = std::fs::read(&output);
+        remove(&[socket, plug, output]);
+
+        result.expect("compose");
+        let composed = composed.expect("composed component written");
+        component::validate(&composed).expect("valid composed component");
+    }
+
+    #[test]
+    fn compose_reports_mismatched_interfaces() {
+        let [socket, plug, output] = compose_fixtures("mismatch", "wasmcp:protocol/resources@0.1.0");
+        let result = compose_with(&socket, std::slice::from_ref(&plug), &output, |_, _, _| {
+            panic!("wac should not run for incompatible components")
+        });
+        remove(&[socket.clone(), plug.clone(), output]);
+
+        let error = result.expect_err("mismatched interfaces").to_string();
+        assert!(
+            error.contains(&format!(
+                "{} exports [wasmcp:protocol/resources@0.1.0] but {} imports none of them",
+                plug, socket
+            )),
+            "{}",
+            error
+        );
+        assert!(error.contains(&format!("Socket imports: [{}]", component::fixtures::TOOLS)), "{}", error);
+    }
+
+    #[test]
+    fn start_component_appends_raw_arguments_in_order() {
+        let raw = ["--max-instances", "4", "--skip-wait"].map(String::from);
+        let cmd = start_component_cmd("build/math.wasm", "math", &raw);
+        let args: Vec<_> = cmd.get_args().map(|arg| arg.to_string_lossy().into_owned()).collect();
+        assert_eq!(
+            args,
+            ["start", "component", "build/math.wasm", "math", "--max-instances", "4", "--skip-wait"]
+        );
+        assert_eq!(start_component_cmd("build/math.wasm", "math", &[]).get_args().count(), 4);
+    }
+}
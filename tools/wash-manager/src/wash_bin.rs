@@ -0,0 +1,53 @@
+//! Locating the wash binary
+//!
+//! In order of precedence: the `--wash-path` flag, the `WASH_BIN`
+//! environment variable, then `wash` on `PATH`. The binary is resolved once at
+//! startup, so a missing wash fails before any step has run.
+
+use anyhow::{Result, anyhow};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const WASH_BIN_ENV: &str = "WASH_BIN";
+
+static WASH_BIN: OnceLock<PathBuf> = OnceLock::new();
+
+/// Resolve the wash binary for the rest of the run
+pub fn init(flag: Option<PathBuf>) -> Result<()> {
+    let path = resolve(flag, std::env::var_os(WASH_BIN_ENV).map(PathBuf::from))?;
+    let _ = WASH_BIN.set(path);
+    Ok(())
+}
+
+/// The resolved binary, or plain `wash` for commands that skipped `init`
+pub fn path() -> &'static Path {
+    WASH_BIN.get().map(PathBuf::as_path).unwrap_or(Path::new("wash"))
+}
+
+fn resolve(flag: Option<PathBuf>, env: Option<PathBuf>) -> Result<PathBuf> {
+    if let Some(path) = flag {
+        return check_file(path, "--wash-path");
+    }
+    if let Some(path) = env.filter(|path| !path.as_os_str().is_empty()) {
+        return check_file(path, WASH_BIN_ENV);
+    }
+
+    let name = format!("wash{}", std::env::consts::EXE_SUFFIX);
+    std::env::var_os("PATH")
+        .and_then(|paths| std::env::split_paths(&paths).map(|dir| dir.join(&name)).find(|path| path.is_file()))
+        .ok_or_else(|| {
+            anyhow!(
+                "wash was not found on PATH. Install it (https://wasmcloud.com/docs/installation), \
+                 or point --wash-path or {} at the binary",
+                WASH_BIN_ENV
+            )
+        })
+}
+
+fn check_file(path: PathBuf, source: &str) -> Result<PathBuf> {
+    if path.is_file() {
+        Ok(path)
+    } else {
+        Err(anyhow!("wash binary {} (from {}) does not exist or is not a file", path.display(), source))
+    }
+}
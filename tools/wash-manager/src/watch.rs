@@ -0,0 +1,117 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::time::Duration;
+
+/// Shortest refresh interval; smaller `--interval` values are raised to this
+pub const MIN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// While nothing changes, refreshes slow down to at most this multiple of the
+/// requested interval
+const MAX_BACKOFF_FACTOR: u32 = 8;
+
+/// What one status refresh observed, compared between refreshes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub wash_running: bool,
+    /// IDs of the running components, sorted
+    pub components: Vec<String>,
+}
+
+impl Snapshot {
+    /// The environment is still coming up (or going down) while wash is not
+    /// running or no component has started yet
+    fn is_transitioning(&self) -> bool {
+        !self.wash_running || self.components.is_empty()
+    }
+}
+
+/// Raise a requested interval to [`MIN_INTERVAL`]
+pub fn clamp_interval(requested: Duration) -> Duration {
+    requested.max(MIN_INTERVAL)
+}
+
+/// Interval before the next refresh: `base` while the state is changing or
+/// transitioning, otherwise double the current one, up to
+/// `MAX_BACKOFF_FACTOR` times `base`. Saturates rather than overflowing for
+/// huge intervals.
+pub fn next_interval(current: Duration, base: Duration, previous: Option<&Snapshot>, latest: &Snapshot) -> Duration {
+    let changed = previous != Some(latest);
+    if changed || latest.is_transitioning() {
+        base
+    } else {
+        let ceiling = base.saturating_mul(MAX_BACKOFF_FACTOR);
+        current.saturating_mul(2).min(ceiling)
+    }
+}
+
+/// Run `refresh` until interrupted, adapting the interval to how much the
+/// state is changing
+pub fn run(interval: Duration, mut refresh: impl FnMut() -> Result<Snapshot>) -> Result<()> {
+    let base = clamp_interval(interval);
+    if base != interval {
+        println!(
+            "{} Interval raised to the minimum of {}s",
+            "⚠".yellow(),
+            MIN_INTERVAL.as_secs()
+        );
+    }
+
+    let mut previous = None;
+    let mut current = base;
+    loop {
+        let latest = refresh()?;
+        current = next_interval(current, base, previous.as_ref(), &latest);
+        previous = Some(latest);
+
+        println!(
+            "{}",
+            format!("Refreshing in {}s (Ctrl-C to stop)", current.as_secs()).dimmed()
+        );
+        std::thread::sleep(current);
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(components: &[&str]) -> Snapshot {
+        Snapshot {
+            wash_running: true,
+            components: components.iter().map(|id| id.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn stable_snapshots_back_off_up_to_the_ceiling() {
+        let base = Duration::from_secs(2);
+        let stable = snapshot(&["math"]);
+        let mut current = base;
+        let mut seen = Vec::new();
+        for _ in 0..5 {
+            current = next_interval(current, base, Some(&stable), &stable);
+            seen.push(current.as_secs());
+        }
+        assert_eq!(seen, [4, 8, 16, 16, 16]);
+    }
+
+    #[test]
+    fn changing_or_transitioning_snapshots_reset_to_base() {
+        let base = Duration::from_secs(2);
+        let slow = Duration::from_secs(16);
+        assert_eq!(next_interval(slow, base, None, &snapshot(&["math"])), base);
+        assert_eq!(next_interval(slow, base, Some(&snapshot(&["math"])), &snapshot(&["math", "statistics"])), base);
+
+        let starting = snapshot(&[]);
+        assert_eq!(next_interval(slow, base, Some(&starting), &starting), base);
+    }
+
+    #[test]
+    fn huge_intervals_saturate() {
+        let stable = snapshot(&["math"]);
+        assert_eq!(next_interval(Duration::MAX, Duration::MAX, Some(&stable), &stable), Duration::MAX);
+        let base = Duration::MAX / 4;
+        assert_eq!(next_interval(base, base, Some(&stable), &stable), base * 2);
+    }
+}